@@ -34,12 +34,31 @@ lazy_static! {
             .unwrap_or_default();
         Finder::from_compressed_topo_with_options(tzs, FinderOptions::y_stripes())
     };
+    static ref FINDER_RTREE: Finder = {
+        let tzs = pbgen::CompressedTopoTimezones::try_from(load_topology_compress_topo())
+            .unwrap_or_default();
+        Finder::from_compressed_topo_with_options(tzs, FinderOptions::rtree())
+    };
+    static ref FINDER_COMPRESSED_QUAD: Finder = {
+        let tzs = pbgen::CompressedTopoTimezones::try_from(load_topology_compress_topo())
+            .unwrap_or_default();
+        Finder::from_compressed_topo_with_options(tzs, FinderOptions::compressed_quad())
+    };
+    static ref FINDER_AUTO: Finder = {
+        let tzs = pbgen::CompressedTopoTimezones::try_from(load_topology_compress_topo())
+            .unwrap_or_default();
+        Finder::from_compressed_topo_with_options(tzs, FinderOptions::auto(AUTO_INDEX_BUDGET))
+    };
     static ref DEFAULT_FINDER_NO_INDEX: DefaultFinder =
         DefaultFinder::new_with_options(FinderOptions::no_index());
     static ref DEFAULT_FINDER_YSTRIPES: DefaultFinder =
         DefaultFinder::new_with_options(FinderOptions::y_stripes());
 }
 
+/// Per-polygon index budget of the `Auto` bench: enough for an RTree on
+/// polygons up to ~10k segments, Y stripes up to ~32k.
+const AUTO_INDEX_BUDGET: usize = 256 * 1024;
+
 static EDGE_IDX: AtomicUsize = AtomicUsize::new(0);
 
 fn next_edge_city() -> &'static EdgeCity {
@@ -82,6 +101,9 @@ fn bench_finder_index_modes(c: &mut Criterion) {
 
     let _ = FINDER_NO_INDEX.get_tz_name(0.0, 0.0);
     let _ = FINDER_YSTRIPES.get_tz_name(0.0, 0.0);
+    let _ = FINDER_RTREE.get_tz_name(0.0, 0.0);
+    let _ = FINDER_COMPRESSED_QUAD.get_tz_name(0.0, 0.0);
+    let _ = FINDER_AUTO.get_tz_name(0.0, 0.0);
 
     let i = &0;
     group.bench_with_input(BenchmarkId::new("NoIndex", i), i, |b, _| {
@@ -102,6 +124,24 @@ fn bench_finder_index_modes(c: &mut Criterion) {
             let _ = FINDER_YSTRIPES.get_tz_name(city.lng, city.lat);
         });
     });
+    group.bench_with_input(BenchmarkId::new("RTree", i), i, |b, _| {
+        b.iter(|| {
+            let city = next_edge_city();
+            let _ = FINDER_RTREE.get_tz_name(city.lng, city.lat);
+        });
+    });
+    group.bench_with_input(BenchmarkId::new("CompressedQuad", i), i, |b, _| {
+        b.iter(|| {
+            let city = next_edge_city();
+            let _ = FINDER_COMPRESSED_QUAD.get_tz_name(city.lng, city.lat);
+        });
+    });
+    group.bench_with_input(BenchmarkId::new("Auto", i), i, |b, _| {
+        b.iter(|| {
+            let city = next_edge_city();
+            let _ = FINDER_AUTO.get_tz_name(city.lng, city.lat);
+        });
+    });
 
     group.finish();
 }
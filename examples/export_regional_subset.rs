@@ -0,0 +1,28 @@
+/// Example: Export a regional subset as a smaller data file
+///
+/// Edge devices that only see coordinates in one region can ship a
+/// `CompressedTopoTimezones` file holding just the timezones they need.
+use std::fs;
+use tzf_rs::Finder;
+
+fn main() {
+    fs::create_dir_all("tmp").expect("Failed to create tmp directory");
+
+    let finder = Finder::new();
+    let europe = finder.subset_by_bbox(-25.0, 34.0, 45.0, 72.0);
+    println!(
+        "Kept {} of {} timezones",
+        europe.timezonenames().len(),
+        finder.timezonenames().len()
+    );
+
+    let mut topo = Vec::new();
+    europe
+        .write_compressed_topo(&mut topo)
+        .expect("Failed to encode subset");
+    let filename = "tmp/europe.topology.compress.topo.bin";
+    fs::write(filename, &topo).expect("Failed to write file");
+    println!("✓ {} ({} bytes)", filename, topo.len());
+
+    println!("Paris: {}", europe.get_tz_name(2.3522, 48.8566));
+}
@@ -1,12 +1,18 @@
 //! Measure live heap bytes held by a Finder after construction, using a
 //! counting global allocator. Compare `ystripes` vs `noindex` to isolate the
-//! memory cost of the Y-stripes polygon index.
+//! memory cost of the Y-stripes polygon index, and a `-lazy` suffix to see
+//! what lazy polygon materialization saves at startup and after queries
+//! touching one region and the whole globe. A `-shared` suffix (before
+//! `-lazy`) keeps shared borders stored once. Query passes are timed too,
+//! since lazy finders pay for decoding zones, and for working out which
+//! embedded grid cells they cover, on first use.
 //!
-//! Usage: cargo run --release --example index_memory_probe_alloc -- [ystripes|noindex]
+//! Usage: cargo run --release --example index_memory_probe_alloc -- [ystripes|noindex|rtree|quad|auto][-shared][-lazy]
 
 use std::alloc::{GlobalAlloc, Layout, System};
 use std::env;
 use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
 use tzf_dist::load_topology_compress_topo;
 use tzf_rs::{Finder, FinderOptions, pbgen};
 
@@ -50,24 +56,74 @@ unsafe impl GlobalAlloc for CountingAlloc {
 #[global_allocator]
 static ALLOC: CountingAlloc = CountingAlloc;
 
+/// Query a lattice with the given step over a lng/lat box, returning how many
+/// points resolved.
+fn query_lattice(finder: &Finder, lng: (i32, i32), lat: (i32, i32), step: usize) -> usize {
+    let mut found = 0;
+    for lng in (lng.0..lng.1).step_by(step) {
+        for lat in (lat.0..lat.1).step_by(step) {
+            if !finder
+                .get_tz_name(f64::from(lng) + 0.5, f64::from(lat) + 0.5)
+                .is_empty()
+            {
+                found += 1;
+            }
+        }
+    }
+    found
+}
+
 fn main() {
     let mode = env::args().nth(1).unwrap_or_else(|| "ystripes".to_string());
-    let options = match mode.as_str() {
+    let (mode, lazy) = match mode.strip_suffix("-lazy") {
+        Some(mode) => (mode, true),
+        None => (mode.as_str(), false),
+    };
+    let (index, shared) = match mode.strip_suffix("-shared") {
+        Some(index) => (index, true),
+        None => (mode, false),
+    };
+    let options = match index {
         "noindex" => FinderOptions::no_index(),
+        "rtree" => FinderOptions::rtree(),
+        "quad" => FinderOptions::compressed_quad(),
+        "auto" => FinderOptions::auto(256 * 1024),
         _ => FinderOptions::y_stripes(),
-    };
+    }
+    .lazy(lazy)
+    .shared_edges(shared);
 
     let before = LIVE.load(Ordering::Relaxed);
+    let start = Instant::now();
     let tzs =
         pbgen::CompressedTopoTimezones::try_from(load_topology_compress_topo()).unwrap_or_default();
     let finder = Finder::from_compressed_topo_with_options(tzs, options);
+    let build_time = start.elapsed();
     let after = LIVE.load(Ordering::Relaxed);
 
     println!("mode: {mode}");
+    println!("build time: {build_time:?}");
     println!("finder live heap bytes: {}", after - before);
     println!("peak heap bytes: {}", PEAK.load(Ordering::Relaxed));
     println!(
         "sanity lookup Beijing: {}",
         finder.get_tz_name(116.3883, 39.9289)
     );
+
+    // Europe only, then the whole globe: with `-lazy` the live heap grows as
+    // zones are first hit.
+    let start = Instant::now();
+    let found = query_lattice(&finder, (-10, 40), (35, 70), 1);
+    println!(
+        "live heap bytes after {found} European queries ({:?}): {}",
+        start.elapsed(),
+        LIVE.load(Ordering::Relaxed) - before
+    );
+    let start = Instant::now();
+    let found = query_lattice(&finder, (-180, 180), (-90, 90), 1);
+    println!(
+        "live heap bytes after {found} global queries ({:?}): {}",
+        start.elapsed(),
+        LIVE.load(Ordering::Relaxed) - before
+    );
 }
@@ -2,11 +2,24 @@ use std::env;
 use tzf_dist_git::load_compress_topo;
 use tzf_rs::{DefaultFinder, Finder, FinderOptions, pbgen};
 
+/// `ystripes`, `noindex`, `rtree`, `quad` or `auto`, optionally suffixed with `-shared`, then `-lazy`.
 fn parse_mode(mode: &str) -> FinderOptions {
-    match mode {
+    let (mode, lazy) = match mode.strip_suffix("-lazy") {
+        Some(mode) => (mode, true),
+        None => (mode, false),
+    };
+    let (index, shared) = match mode.strip_suffix("-shared") {
+        Some(index) => (index, true),
+        None => (mode, false),
+    };
+    let options = match index {
         "noindex" => FinderOptions::no_index(),
+        "rtree" => FinderOptions::rtree(),
+        "quad" => FinderOptions::compressed_quad(),
+        "auto" => FinderOptions::auto(256 * 1024),
         _ => FinderOptions::y_stripes(),
-    }
+    };
+    options.lazy(lazy).shared_edges(shared)
 }
 
 fn main() {
@@ -0,0 +1,386 @@
+//! Antimeridian handling for GeoJSON export.
+//!
+//! The timezone data stores zones crossing ±180° (Fiji, Kiribati, Chukotka)
+//! as separate polygons on either side, which
+//! [`Finder::to_geojson`](crate::Finder::to_geojson) emits as is. The
+//! conversions here either cut any ring still crossing the antimeridian, as
+//! RFC 7946 asks, or merge the two sides into one geometry in a 0–360° frame.
+
+use crate::{BoundaryFile, FeatureItem, MultiPolygonCoordinates, PolygonCoordinates};
+use std::collections::{HashMap, HashSet};
+
+type Ring = Vec<[f64; 2]>;
+
+/// How [`BoundaryFile::with_antimeridian`] treats geometry at ±180°.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AntimeridianMode {
+    /// Cut every ring crossing the antimeridian into parts on either side,
+    /// per RFC 7946 section 3.1.9, so all longitudes lie in `[-180, 180]`.
+    /// Undoes [`AntimeridianMode::Merge360`].
+    Split,
+    /// Shift the western parts of zones lying on both sides of the
+    /// antimeridian by 360° and merge them with the eastern parts along
+    /// 180°, so each such zone is one continuous geometry with longitudes
+    /// up to 360. Other zones are left as is.
+    Merge360,
+}
+
+impl BoundaryFile {
+    /// Apply [`FeatureItem::with_antimeridian`] to every feature.
+    ///
+    /// ```rust
+    /// use tzf_rs::{AntimeridianMode, Finder};
+    ///
+    /// let finder = Finder::new();
+    /// let fiji = finder.get_tz_geojson("Pacific/Fiji").unwrap();
+    /// let merged = fiji.with_antimeridian(AntimeridianMode::Merge360);
+    /// assert!(!merged.features[0].geometry.coordinates.is_empty());
+    /// ```
+    #[must_use]
+    pub fn with_antimeridian(mut self, mode: AntimeridianMode) -> Self {
+        self.features = self
+            .features
+            .into_iter()
+            .map(|feature| feature.with_antimeridian(mode))
+            .collect();
+        self
+    }
+}
+
+impl FeatureItem {
+    /// The feature with its geometry at the antimeridian converted as
+    /// `mode` describes.
+    #[must_use]
+    pub fn with_antimeridian(mut self, mode: AntimeridianMode) -> Self {
+        let polygons = std::mem::take(&mut self.geometry.coordinates);
+        self.geometry.coordinates = match mode {
+            AntimeridianMode::Split => polygons.into_iter().flat_map(split_polygon).collect(),
+            AntimeridianMode::Merge360 => merge_360(polygons),
+        };
+        self
+    }
+}
+
+/// Whether `ring` reaches past ±180° or has an edge jumping more than 180°
+/// in longitude, i.e. taking the short way across the antimeridian.
+fn crosses(ring: &[[f64; 2]]) -> bool {
+    ring.iter().any(|p| p[0].abs() > 180.0)
+        || (0..ring.len()).any(|i| (ring[(i + 1) % ring.len()][0] - ring[i][0]).abs() > 180.0)
+}
+
+/// `ring` with longitudes made continuous, starting from its first vertex.
+fn unwrap_ring(ring: Ring) -> Ring {
+    let mut prev: Option<[f64; 2]> = None;
+    ring.into_iter()
+        .map(|[lng, lat]| {
+            let lng = match prev {
+                Some([prev_lng, _]) => {
+                    let delta = lng - prev_lng;
+                    if delta > 180.0 {
+                        lng - 360.0
+                    } else if delta < -180.0 {
+                        lng + 360.0
+                    } else {
+                        lng
+                    }
+                }
+                None => lng,
+            };
+            prev = Some([lng, lat]);
+            [lng, lat]
+        })
+        .collect()
+}
+
+fn shift(ring: &mut Ring, by: f64) {
+    for point in ring {
+        point[0] += by;
+    }
+}
+
+/// `polygon` cut at the antimeridian into parts within `[-180, 180]`, or as
+/// is when none of its rings crosses it.
+fn split_polygon(polygon: PolygonCoordinates) -> Vec<PolygonCoordinates> {
+    if !polygon.iter().any(|ring| crosses(ring)) {
+        return vec![polygon];
+    }
+    let mut rings = polygon.into_iter().map(unwrap_ring);
+    let Some(exterior) = rings.next() else {
+        return Vec::new();
+    };
+    let (min, max) = exterior
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), p| {
+            (min.min(p[0]), max.max(p[0]))
+        });
+    // Holes were unwrapped on their own; bring them next to the exterior.
+    let center = (min + max) / 2.0;
+    let holes: Vec<Ring> = rings
+        .map(|mut hole| {
+            if let Some(first) = hole.first() {
+                let by = ((center - first[0]) / 360.0).round() * 360.0;
+                shift(&mut hole, by);
+            }
+            hole
+        })
+        .collect();
+
+    // Each 360° window [360k - 180, 360k + 180] the exterior reaches yields
+    // one part, shifted back by 360k.
+    #[allow(clippy::cast_possible_truncation)]
+    let windows = ((min + 180.0) / 360.0).floor() as i32..((max + 180.0) / 360.0).ceil() as i32;
+    let mut parts = Vec::new();
+    for k in windows {
+        let offset = f64::from(k) * 360.0;
+        let clip = |ring: &[[f64; 2]]| {
+            let ring = clip_half_plane(ring, offset - 180.0, true);
+            let mut ring = clip_half_plane(&ring, offset + 180.0, false);
+            shift(&mut ring, -offset);
+            (ring.len() >= 3).then(|| close(ring))
+        };
+        let Some(part) = clip(&exterior) else {
+            continue;
+        };
+        let mut part = vec![part];
+        part.extend(holes.iter().filter_map(|hole| clip(hole)));
+        parts.push(part);
+    }
+    parts
+}
+
+/// Clip `ring` to the half-plane `lng >= x` (`keep_east`) or `lng <= x`
+/// (Sutherland–Hodgman).
+fn clip_half_plane(ring: &[[f64; 2]], x: f64, keep_east: bool) -> Ring {
+    let inside = |p: [f64; 2]| if keep_east { p[0] >= x } else { p[0] <= x };
+    let crossing = |a: [f64; 2], b: [f64; 2]| {
+        let t = (x - a[0]) / (b[0] - a[0]);
+        [x, a[1] + t * (b[1] - a[1])]
+    };
+    let mut out = Vec::with_capacity(ring.len());
+    for (i, &a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        match (inside(a), inside(b)) {
+            (true, true) => out.push(b),
+            (true, false) => out.push(crossing(a, b)),
+            (false, true) => {
+                out.push(crossing(a, b));
+                out.push(b);
+            }
+            (false, false) => {}
+        }
+    }
+    out
+}
+
+/// `ring` with its first vertex repeated at the end, as GeoJSON wants.
+fn close(mut ring: Ring) -> Ring {
+    let first = ring.first().copied();
+    if let Some(first) = first.filter(|first| ring.last() != Some(first)) {
+        ring.push(first);
+    }
+    ring
+}
+
+/// `ring` without the closing repeat of its first vertex.
+fn open(mut ring: Ring) -> Ring {
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    ring
+}
+
+/// Twice the signed area of `ring`, positive when counterclockwise.
+fn signed_area(ring: &[[f64; 2]]) -> f64 {
+    (0..ring.len())
+        .map(|i| {
+            let (a, b) = (ring[i], ring[(i + 1) % ring.len()]);
+            a[0] * b[1] - b[0] * a[1]
+        })
+        .sum()
+}
+
+/// Even-odd point-in-ring test.
+fn ring_contains(ring: &[[f64; 2]], [x, y]: [f64; 2]) -> bool {
+    let mut inside = false;
+    for (i, &a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        if (a[1] > y) != (b[1] > y) && x < a[0] + (y - a[1]) * (b[0] - a[0]) / (b[1] - a[1]) {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Merge the parts of a zone lying on both sides of the antimeridian; see
+/// [`AntimeridianMode::Merge360`].
+fn merge_360(polygons: MultiPolygonCoordinates) -> MultiPolygonCoordinates {
+    let touches = |polygon: &PolygonCoordinates, lng: f64| {
+        polygon
+            .first()
+            .is_some_and(|ring| ring.iter().any(|p| p[0] == lng))
+    };
+    if !(polygons.iter().any(|p| touches(p, 180.0)) && polygons.iter().any(|p| touches(p, -180.0)))
+    {
+        return polygons;
+    }
+    let (mut seam, mut rest) = (Vec::new(), Vec::new());
+    for mut polygon in polygons {
+        if touches(&polygon, -180.0) {
+            for ring in &mut polygon {
+                shift(ring, 360.0);
+            }
+        }
+        if touches(&polygon, 180.0) {
+            seam.push(polygon);
+        } else {
+            rest.push(polygon);
+        }
+    }
+    let mut merged = dissolve_seam(seam);
+    merged.extend(rest);
+    merged
+}
+
+/// Union polygons meeting along the 180° meridian by dropping the edges
+/// they share there and relinking the rest into rings.
+fn dissolve_seam(polygons: MultiPolygonCoordinates) -> MultiPolygonCoordinates {
+    // Exteriors counterclockwise and holes clockwise, so shared edges run in
+    // opposite directions.
+    let mut rings: Vec<Ring> = Vec::new();
+    for polygon in polygons {
+        for (i, ring) in polygon.into_iter().enumerate() {
+            let mut ring = open(ring);
+            if ring.len() < 3 {
+                continue;
+            }
+            if (signed_area(&ring) > 0.0) != (i == 0) {
+                ring.reverse();
+            }
+            rings.push(ring);
+        }
+    }
+
+    // Edges along the seam are split at every seam vertex, so the two sides
+    // share them exactly.
+    let mut seam_lats: Vec<f64> = rings
+        .iter()
+        .flatten()
+        .filter(|p| p[0] == 180.0)
+        .map(|p| p[1])
+        .collect();
+    seam_lats.sort_unstable_by(f64::total_cmp);
+    seam_lats.dedup();
+    let mut edges: Vec<([f64; 2], [f64; 2])> = Vec::new();
+    for ring in &rings {
+        for (i, &a) in ring.iter().enumerate() {
+            let b = ring[(i + 1) % ring.len()];
+            if a == b {
+                continue;
+            }
+            if a[0] != 180.0 || b[0] != 180.0 {
+                edges.push((a, b));
+                continue;
+            }
+            let (lo, hi) = (a[1].min(b[1]), a[1].max(b[1]));
+            let mut stops: Vec<f64> = seam_lats
+                .iter()
+                .copied()
+                .filter(|&lat| lo < lat && lat < hi)
+                .collect();
+            if a[1] > b[1] {
+                stops.reverse();
+            }
+            let mut from = a;
+            for lat in stops {
+                edges.push((from, [180.0, lat]));
+                from = [180.0, lat];
+            }
+            edges.push((from, b));
+        }
+    }
+
+    let key = |p: [f64; 2]| (p[0].to_bits(), p[1].to_bits());
+    let on_seam = |(a, b): ([f64; 2], [f64; 2])| a[0] == 180.0 && b[0] == 180.0;
+    let seam_edges: HashSet<_> = edges
+        .iter()
+        .filter(|&&edge| on_seam(edge))
+        .map(|&(a, b)| (key(a), key(b)))
+        .collect();
+    edges.retain(|&(a, b)| !on_seam((a, b)) || !seam_edges.contains(&(key(b), key(a))));
+
+    // Relink the remaining edges into rings.
+    let mut outgoing: HashMap<(u64, u64), Vec<usize>> = HashMap::new();
+    for (i, &(a, _)) in edges.iter().enumerate() {
+        outgoing.entry(key(a)).or_default().push(i);
+    }
+    let mut used = vec![false; edges.len()];
+    let (mut exteriors, mut holes) = (Vec::new(), Vec::new());
+    for start in 0..edges.len() {
+        if used[start] {
+            continue;
+        }
+        let mut ring = vec![edges[start].0];
+        let mut current = start;
+        let closed = loop {
+            used[current] = true;
+            let end = edges[current].1;
+            if end == ring[0] {
+                break true;
+            }
+            let next = outgoing.get_mut(&key(end)).and_then(|candidates| {
+                while let Some(i) = candidates.pop() {
+                    if !used[i] {
+                        return Some(i);
+                    }
+                }
+                None
+            });
+            let Some(next) = next else {
+                break false;
+            };
+            ring.push(end);
+            current = next;
+        };
+        // Vertices in the middle of a straight run along the seam.
+        let n = ring.len();
+        let seam_vertex = |i: usize| ring[i % n][0] == 180.0;
+        let ring: Ring = (0..n)
+            .filter(|&i| !(seam_vertex(i + n - 1) && seam_vertex(i) && seam_vertex(i + 1)))
+            .map(|i| ring[i])
+            .collect();
+        if !closed || ring.len() < 3 {
+            continue;
+        }
+        if signed_area(&ring) > 0.0 {
+            exteriors.push(ring);
+        } else {
+            holes.push(ring);
+        }
+    }
+
+    // Each hole goes to the smallest exterior holding it.
+    let mut polygons: MultiPolygonCoordinates =
+        exteriors.into_iter().map(|ring| vec![ring]).collect();
+    for hole in holes {
+        let probe = hole
+            .iter()
+            .copied()
+            .find(|p| p[0] != 180.0)
+            .unwrap_or(hole[0]);
+        let owner = polygons
+            .iter()
+            .enumerate()
+            .filter(|(_, polygon)| ring_contains(&polygon[0], probe))
+            .min_by(|(_, a), (_, b)| signed_area(&a[0]).total_cmp(&signed_area(&b[0])))
+            .map(|(i, _)| i);
+        if let Some(owner) = owner {
+            polygons[owner].push(hole);
+        }
+    }
+    for polygon in &mut polygons {
+        for ring in polygon.iter_mut() {
+            *ring = close(std::mem::take(ring));
+        }
+    }
+    polygons
+}
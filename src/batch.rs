@@ -0,0 +1,85 @@
+//! Batch lookups for large point sets.
+
+use crate::DefaultFinder;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Query order sorted by 1°×1° grid cell, so consecutive lookups hit the same
+/// grid candidates and polygons while they are still in cache.
+#[allow(clippy::cast_possible_truncation)]
+fn cell_order(len: usize, point: impl Fn(usize) -> (f64, f64)) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..len).collect();
+    order.sort_by_cached_key(|&i| {
+        let (lng, lat) = point(i);
+        (lat.floor() as i16, lng.floor() as i16)
+    });
+    order
+}
+
+impl DefaultFinder {
+    fn get_tz_names_batch_by<F>(&self, len: usize, point: F) -> Vec<&str>
+    where
+        F: Fn(usize) -> (f64, f64) + Sync,
+    {
+        let order = cell_order(len, &point);
+        let lookup = |&i: &usize| {
+            let (lng, lat) = point(i);
+            self.get_tz_name(lng, lat)
+        };
+
+        // Resolve in cell order (rayon hands each thread a contiguous run of
+        // it), then scatter back to input order.
+        #[cfg(feature = "parallel")]
+        let sorted: Vec<&str> = order.par_iter().map(lookup).collect();
+        #[cfg(not(feature = "parallel"))]
+        let sorted: Vec<&str> = order.iter().map(lookup).collect();
+
+        let mut ret = vec![""; len];
+        for (&i, name) in order.iter().zip(sorted) {
+            ret[i] = name;
+        }
+        ret
+    }
+
+    /// Resolve many `(lng, lat)` points at once; `ret[i]` is
+    /// [`DefaultFinder::get_tz_name`] of `points[i]`.
+    ///
+    /// Queries are processed in grid-cell order for cache locality. With the
+    /// `parallel` feature the batch is spread across threads with rayon.
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// let names = finder.get_tz_names_batch(&[(116.3883, 39.9289), (139.4382, 36.4432)]);
+    /// assert_eq!(names, ["Asia/Shanghai", "Asia/Tokyo"]);
+    /// ```
+    #[must_use]
+    pub fn get_tz_names_batch(&self, points: &[(f64, f64)]) -> Vec<&str> {
+        self.get_tz_names_batch_by(points.len(), |i| points[i])
+    }
+
+    /// Columnar variant of [`DefaultFinder::get_tz_names_batch`] taking
+    /// separate longitude and latitude slices.
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// let names = finder.get_tz_names_batch_columnar(&[116.3883, 139.4382], &[39.9289, 36.4432]);
+    /// assert_eq!(names, ["Asia/Shanghai", "Asia/Tokyo"]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lngs` and `lats` have different lengths.
+    #[must_use]
+    pub fn get_tz_names_batch_columnar(&self, lngs: &[f64], lats: &[f64]) -> Vec<&str> {
+        assert_eq!(
+            lngs.len(),
+            lats.len(),
+            "longitude and latitude slices differ in length"
+        );
+        self.get_tz_names_batch_by(lngs.len(), |i| (lngs[i], lats[i]))
+    }
+}
@@ -3,6 +3,7 @@
 use clap::{Args, Parser, ValueEnum};
 use std::error::Error;
 use std::io::{self, BufRead, Write};
+use std::path::PathBuf;
 use tzf_rs::DefaultFinder;
 
 #[derive(Parser, Debug)]
@@ -14,6 +15,14 @@ struct Cli {
     /// Read multiple coordinates from stdin in given order
     #[arg(long, conflicts_with("Params"))]
     stdin_order: Option<StdinOrder>,
+
+    /// Load polygon data from this compress.topo.bin file instead of the embedded data
+    #[arg(long, requires("preindex"))]
+    topo: Option<PathBuf>,
+
+    /// Load preindex data from this preindex.bin file instead of the embedded data
+    #[arg(long, requires("topo"))]
+    preindex: Option<PathBuf>,
 }
 
 #[derive(Args, Debug)]
@@ -35,13 +44,26 @@ enum StdinOrder {
     LatLng,
 }
 
+#[cfg(any(feature = "bundled", feature = "full"))]
+fn embedded_finder() -> Result<DefaultFinder, Box<dyn Error>> {
+    Ok(DefaultFinder::new())
+}
+
+#[cfg(not(any(feature = "bundled", feature = "full")))]
+fn embedded_finder() -> Result<DefaultFinder, Box<dyn Error>> {
+    Err("built without embedded data; pass --topo and --preindex".into())
+}
+
 fn is_delimiter(c: char) -> bool {
     matches!(c, ' ' | '\t' | ',' | ';')
 }
 
 pub fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
-    let finder = DefaultFinder::new();
+    let finder = match (cli.topo, cli.preindex) {
+        (Some(topo), Some(preindex)) => DefaultFinder::from_paths(topo, preindex)?,
+        _ => embedded_finder()?,
+    };
     if let Some(params) = cli.params {
         println!("{:?}", finder.get_tz_name(params.lng, params.lat));
     } else if let Some(stdin_order) = cli.stdin_order {
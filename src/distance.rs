@@ -0,0 +1,303 @@
+//! Great-circle distances between query points and polygon boundaries.
+
+use crate::{FinderCore, Zone, on_antimeridian};
+
+/// Distance from a point to the border of the timezone containing it, as
+/// returned by [`Finder::distance_to_border`](crate::Finder::distance_to_border).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BorderDistance<'a> {
+    /// Timezone containing the point.
+    pub name: &'a str,
+    /// Great-circle distance to the nearest edge of that timezone, in meters.
+    pub distance_m: f64,
+    /// Timezone across that edge, or `""` when no polygon lies across it.
+    pub across: &'a str,
+}
+
+/// How far past the nearest border point to probe for the zone across it:
+/// one step of the 1e-5° storage grid, about a meter.
+const ACROSS_PROBE_DEG: f64 = 1e-5;
+
+/// Mean Earth radius (IUGG), in kilometers.
+const EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// Length of one degree of latitude, in kilometers.
+const KM_PER_DEGREE: f64 = EARTH_RADIUS_KM * std::f64::consts::PI / 180.0;
+
+/// Haversine distance between two `(lng, lat)` points in degrees.
+pub(crate) fn haversine_km(lng1: f64, lat1: f64, lng2: f64, lat2: f64) -> f64 {
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let dphi = phi2 - phi1;
+    let dlambda = (lng2 - lng1).to_radians();
+    let a = (dphi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (dlambda / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+/// Longitude, or longitude difference, folded into `[-180, 180)`, so segments
+/// that cross the antimeridian measure the short way round.
+pub(crate) fn wrap_lng(d: f64) -> f64 {
+    (d + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Closest point to `(lng, lat)` on the segment `a`–`b`, all in degrees.
+///
+/// The segment is projected onto a local equirectangular plane centred on the
+/// query, which is exact enough for the short edges of timezone polygons.
+pub(crate) fn closest_point_on_segment(
+    lng: f64,
+    lat: f64,
+    a: (f64, f64),
+    b: (f64, f64),
+) -> (f64, f64) {
+    let kx = lat.to_radians().cos().max(1e-9);
+    let (ax, ay) = (wrap_lng(a.0 - lng) * kx, a.1 - lat);
+    let (bx, by) = (wrap_lng(b.0 - lng) * kx, b.1 - lat);
+    let (dx, dy) = (bx - ax, by - ay);
+    let len2 = dx * dx + dy * dy;
+    let t = if len2 > 0.0 {
+        (-(ax * dx + ay * dy) / len2).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    (lng + (ax + t * dx) / kx, lat + ay + t * dy)
+}
+
+/// Longitude half-width, in degrees, of the band `lat ± dlat` that holds
+/// every point within `dlat` degrees of arc of latitude `lat`. Measured at
+/// the band's highest latitude, where a degree of longitude is shortest;
+/// 180 once the band reaches a pole.
+fn lng_radius(lat: f64, dlat: f64) -> f64 {
+    let max_lat = lat.abs() + dlat;
+    if max_lat >= 90.0 {
+        return 180.0;
+    }
+    (dlat / max_lat.to_radians().cos()).min(180.0)
+}
+
+/// `[min_lng, min_lat, max_lng, max_lat]` holding every point within
+/// `radius_km` of `(lng, lat)`, spanning all longitudes when it would wrap
+/// across the antimeridian.
+fn search_box(lng: f64, lat: f64, radius_km: f64) -> [f64; 4] {
+    let dlat = radius_km / KM_PER_DEGREE;
+    let dlng = lng_radius(lat, dlat);
+    let (west, east) = if lng - dlng < -180.0 || lng + dlng > 180.0 {
+        (-180.0, 180.0)
+    } else {
+        (lng - dlng, lng + dlng)
+    };
+    [west, lat - dlat, east, lat + dlat]
+}
+
+/// Call `f` with each edge of a ring as two `(lng, lat)` points in degrees,
+/// including the closing edge from the last point back to the first.
+pub(crate) fn for_each_ring_edge(
+    ring: impl IntoIterator<Item = (f64, f64)>,
+    mut f: impl FnMut((f64, f64), (f64, f64)),
+) {
+    let mut iter = ring.into_iter();
+    let Some(first) = iter.next() else {
+        return;
+    };
+    let mut prev = first;
+    for p in iter {
+        f(prev, p);
+        prev = p;
+    }
+    f(prev, first);
+}
+
+/// Whether the edge `a`–`b` runs along the antimeridian, where zones crossing
+/// it are stored split: such edges are no border.
+fn on_antimeridian_edge(a: (f64, f64), b: (f64, f64)) -> bool {
+    on_antimeridian(a.0) && on_antimeridian(b.0)
+}
+
+/// Great-circle distance from `(lng, lat)` to the nearest polygon edge of
+/// `zone`, in kilometers.
+pub(crate) fn boundary_distance_km(zone: &impl Zone, lng: f64, lat: f64) -> f64 {
+    let mut best = f64::INFINITY;
+    zone.for_each_edge(|a, b| {
+        let (clng, clat) = closest_point_on_segment(lng, lat, a, b);
+        best = best.min(haversine_km(lng, lat, clng, clat));
+    });
+    best
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    /// Item indices whose grid cells come within `radius_km` of the point, or
+    /// every item when there is no grid. Cells missing from the grid hold no
+    /// zone.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn candidates_within(&self, lng: f64, lat: f64, radius_km: f64) -> Vec<usize> {
+        let Some(ref grid) = self.grid else {
+            return (0..self.all.len()).collect();
+        };
+        let dlat = radius_km / KM_PER_DEGREE;
+        let dlng = lng_radius(lat, dlat);
+        // Cell rows and columns, in cells of 1/n degrees.
+        let n = f64::from(grid.cells_per_degree);
+        let half = grid.lng_cells() / 2;
+        let (south, north) = (-half / 2, half / 2 - 1);
+        let lat_min = ((lat - dlat) * n).floor().max(f64::from(south)) as i16;
+        let lat_max = ((lat + dlat) * n).floor().min(f64::from(north)) as i16;
+        let lng_cells: Vec<i16> = if dlng >= 180.0 || lat_min == south || lat_max == north {
+            (-half..half).collect()
+        } else {
+            let (half, cols) = (i32::from(half), i32::from(grid.lng_cells()));
+            let lng_min = ((lng - dlng) * n).floor() as i32;
+            let lng_max = ((lng + dlng) * n).floor() as i32;
+            (lng_min..=lng_max)
+                .map(|x| ((x + half).rem_euclid(cols) - half) as i16)
+                .collect()
+        };
+
+        let mut ret: Vec<usize> = vec![];
+        for cell_lat in lat_min..=lat_max {
+            for &cell_lng in &lng_cells {
+                if let Some(indices) = grid.cells.get(&(cell_lng, cell_lat)) {
+                    ret.extend(indices.iter().map(|&idx| idx as usize));
+                }
+            }
+        }
+        ret.sort_unstable();
+        ret.dedup();
+        ret
+    }
+
+    pub(crate) fn get_tz_name_nearest(
+        &self,
+        lng: f64,
+        lat: f64,
+        max_distance_km: f64,
+    ) -> Option<(&str, f64)> {
+        let name = self.get_tz_name(lng, lat);
+        if !name.is_empty() {
+            return Some((name, 0.0));
+        }
+        let mut best: Option<(&str, f64)> = None;
+        for idx in self.candidates_within(lng, lat, max_distance_km) {
+            let item = &self.all[idx];
+            let d = boundary_distance_km(item, lng, lat);
+            if d <= max_distance_km && best.is_none_or(|(_, best_d)| d < best_d) {
+                best = Some((item.name(), d));
+            }
+        }
+        best
+    }
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    /// Whether any zone edge passes within `max_km` of the point.
+    pub(crate) fn near_border(&self, lng: f64, lat: f64, max_km: f64) -> bool {
+        if self.deep_in_covered_cell(lng, lat, max_km) {
+            return false;
+        }
+        let bbox = search_box(lng, lat, max_km);
+        self.candidates_within(lng, lat, max_km)
+            .into_iter()
+            .any(|idx| {
+                let mut near = false;
+                self.all[idx].for_each_edge_in(bbox, |a, b| {
+                    // Edges whose latitudes miss the band cannot be close.
+                    if near
+                        || a.1.min(b.1) > bbox[3]
+                        || a.1.max(b.1) < bbox[1]
+                        || on_antimeridian_edge(a, b)
+                    {
+                        return;
+                    }
+                    let c = closest_point_on_segment(lng, lat, a, b);
+                    near = haversine_km(lng, lat, c.0, c.1) <= max_km;
+                });
+                near
+            })
+    }
+
+    /// Whether the point lies in a grid cell entirely inside one zone and
+    /// more than `max_km` from the cell's sides, so no edge comes that
+    /// close.
+    fn deep_in_covered_cell(&self, lng: f64, lat: f64, max_km: f64) -> bool {
+        let Some(grid) = &self.grid else {
+            return false;
+        };
+        let key = grid.key(lng, lat);
+        if !grid.is_covered(key, &self.all) {
+            return false;
+        }
+        let [west, south, east, north] = grid.cell_bounds(key);
+        // Lower bounds on the great-circle distance to the cell's parallels
+        // and meridians, the latter taken where the cell is narrowest.
+        let to_parallel = (lat - south).min(north - lat) * KM_PER_DEGREE;
+        let dlng = (lng - west).min(east - lng).to_radians();
+        let narrowest = south.abs().max(north.abs()).to_radians().cos();
+        let to_meridian = EARTH_RADIUS_KM * narrowest * dlng.sin();
+        to_parallel.min(to_meridian) > max_km
+    }
+
+    pub(crate) fn distance_to_border(&self, lng: f64, lat: f64) -> Option<BorderDistance<'_>> {
+        let idx = self.find_item(lng, lat)?;
+        let item = &self.all[idx];
+
+        let mut best_km = f64::INFINITY;
+        let mut best = ((lng, lat), (lng, lat), (lng, lat));
+        item.for_each_edge(|a, b| {
+            if on_antimeridian_edge(a, b) {
+                return;
+            }
+            let c = closest_point_on_segment(lng, lat, a, b);
+            let d = haversine_km(lng, lat, c.0, c.1);
+            if d < best_km {
+                best_km = d;
+                best = (c, a, b);
+            }
+        });
+        if !best_km.is_finite() {
+            return None;
+        }
+
+        let (c, a, b) = best;
+        Some(BorderDistance {
+            name: item.name(),
+            distance_m: best_km * 1000.0,
+            across: self.zone_across(item.name(), (lng, lat), c, (a, b)),
+        })
+    }
+
+    /// Name of the zone just past border point `c` on `edge`, probing first
+    /// away from the query point `p` and then along both edge normals (the
+    /// only directions left when `p` sits on the edge itself).
+    fn zone_across(
+        &self,
+        own: &str,
+        p: (f64, f64),
+        c: (f64, f64),
+        edge: ((f64, f64), (f64, f64)),
+    ) -> &str {
+        let kx = c.1.to_radians().cos().max(1e-9);
+        let (ox, oy) = (wrap_lng(c.0 - p.0) * kx, c.1 - p.1);
+        let (ex, ey) = (wrap_lng(edge.1.0 - edge.0.0) * kx, edge.1.1 - edge.0.1);
+        let mut dirs: Vec<(f64, f64)> = vec![];
+        let olen = ox.hypot(oy);
+        if olen > 0.0 {
+            dirs.push((ox / olen, oy / olen));
+        }
+        let elen = ex.hypot(ey);
+        if elen > 0.0 {
+            dirs.push((-ey / elen, ex / elen));
+            dirs.push((ey / elen, -ex / elen));
+        }
+
+        for (dx, dy) in dirs {
+            let probe_lng = wrap_lng(c.0 + dx * ACROSS_PROBE_DEG / kx);
+            let probe_lat = (c.1 + dy * ACROSS_PROBE_DEG).clamp(-90.0, 90.0);
+            if let Some(name) = self
+                .get_tz_names(probe_lng, probe_lat)
+                .into_iter()
+                .find(|&name| name != own)
+            {
+                return name;
+            }
+        }
+        ""
+    }
+}
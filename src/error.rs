@@ -0,0 +1,227 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+/// Errors returned by the fallible `try_*` constructors.
+///
+/// The infallible constructors ([`Finder::new`](crate::Finder::new),
+/// [`DefaultFinder::new`](crate::DefaultFinder::new), …) keep their historical
+/// behavior of falling back to empty data; use the `try_*` variants when a
+/// corrupt dataset must be rejected instead of silently answering `""`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// A data file could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// The protobuf payload could not be decoded.
+    Decode(prost::DecodeError),
+    /// The polygon data and the preindex data come from different releases.
+    VersionMismatch {
+        /// Version of the polygon (topo) data.
+        finder: String,
+        /// Version of the preindex data.
+        fuzzy_finder: String,
+    },
+    /// A shared edge declares an id outside `0..shared_edges.len()`.
+    SharedEdgeIdOutOfRange { id: i32, len: usize },
+    /// A shared edge's polyline bytes are malformed.
+    MalformedSharedEdge { id: i32, reason: PolylineError },
+    /// A polygon ring is malformed.
+    MalformedRing {
+        location: RingLocation,
+        reason: RingError,
+    },
+    /// A grid cell references a timezone index past the end of the timezone list.
+    GridIndexOutOfRange {
+        lng: i32,
+        lat: i32,
+        tz_index: u32,
+        len: usize,
+    },
+    /// A grid cell lies outside `[-180, 180) x [-90, 90)`.
+    GridCellOutOfRange { lng: i32, lat: i32 },
+    /// The preindex contains more distinct timezone names than fit in a `u16`.
+    TooManyTimezones(usize),
+    /// A query longitude is NaN, infinite, or outside `[-180, 180]`.
+    InvalidLongitude(f64),
+    /// A query latitude is NaN, infinite, or outside `[-90, 90]`.
+    InvalidLatitude(f64),
+    /// Data loaded into a [`DefaultFinder`](crate::DefaultFinder), or
+    /// replacement data for a [`ReloadableFinder`](crate::ReloadableFinder),
+    /// contains no timezones.
+    EmptyDataset,
+    /// Replacement data for a [`ReloadableFinder`](crate::ReloadableFinder)
+    /// has the same data version as the data already loaded.
+    SameDataVersion(String),
+    /// [`FinderOptions::shared_edges`](crate::FinderOptions::shared_edges)
+    /// was combined with an index mode shared-edge rings cannot build.
+    SharedEdgesIndexMode,
+    /// A snapshot is truncated, or its header or cross-references are
+    /// inconsistent.
+    InvalidSnapshot(&'static str),
+    /// A snapshot was written in a format version this build cannot read.
+    UnsupportedSnapshotVersion(u32),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => write!(f, "failed to read {}: {source}", path.display()),
+            Self::Decode(err) => write!(f, "failed to decode timezone data: {err}"),
+            Self::VersionMismatch {
+                finder,
+                fuzzy_finder,
+            } => write!(
+                f,
+                "data version mismatch: polygons are {finder:?}, preindex is {fuzzy_finder:?}"
+            ),
+            Self::SharedEdgeIdOutOfRange { id, len } => {
+                write!(f, "shared edge id {id} out of range for {len} edges")
+            }
+            Self::MalformedSharedEdge { id, reason } => {
+                write!(f, "shared edge {id}: {reason}")
+            }
+            Self::MalformedRing { location, reason } => write!(f, "{location}: {reason}"),
+            Self::GridIndexOutOfRange {
+                lng,
+                lat,
+                tz_index,
+                len,
+            } => write!(
+                f,
+                "grid cell ({lng}, {lat}) references timezone index {tz_index} out of range for {len} timezones"
+            ),
+            Self::GridCellOutOfRange { lng, lat } => {
+                write!(f, "grid cell ({lng}, {lat}) lies outside the globe")
+            }
+            Self::TooManyTimezones(n) => {
+                write!(f, "{n} distinct timezone names exceed the u16 name table")
+            }
+            Self::InvalidLongitude(lng) => {
+                write!(f, "longitude {lng} is not a number in [-180, 180]")
+            }
+            Self::InvalidLatitude(lat) => write!(f, "latitude {lat} is not a number in [-90, 90]"),
+            Self::EmptyDataset => write!(f, "timezone data contains no timezones"),
+            Self::SameDataVersion(version) => {
+                write!(
+                    f,
+                    "replacement data has the loaded data version {version:?}"
+                )
+            }
+            Self::SharedEdgesIndexMode => write!(
+                f,
+                "shared edges support only Y stripes or no index, with the float raycast"
+            ),
+            Self::InvalidSnapshot(reason) => write!(f, "invalid snapshot: {reason}"),
+            Self::UnsupportedSnapshotVersion(version) => write!(
+                f,
+                "snapshot format version {version} is not supported (expected {})",
+                crate::SNAPSHOT_FORMAT_VERSION
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Decode(err) => Some(err),
+            Self::MalformedSharedEdge { reason, .. } => Some(reason),
+            Self::MalformedRing { reason, .. } => Some(reason),
+            _ => None,
+        }
+    }
+}
+
+impl From<prost::DecodeError> for Error {
+    fn from(err: prost::DecodeError) -> Self {
+        Self::Decode(err)
+    }
+}
+
+/// Why a polyline byte string failed to decode. Offsets are byte positions in
+/// the encoded string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum PolylineError {
+    /// The input ends in the middle of a value or of a `[lng, lat]` pair.
+    Truncated { offset: usize },
+    /// A value, or the running coordinate, does not fit a 1e5-scaled `i32`.
+    Overflow { offset: usize },
+    /// A byte outside the polyline alphabet (`63..=126`).
+    InvalidByte { offset: usize, byte: u8 },
+}
+
+impl fmt::Display for PolylineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated { offset } => write!(f, "polyline truncated in value at byte {offset}"),
+            Self::Overflow { offset } => write!(f, "polyline value at byte {offset} overflows i32"),
+            Self::InvalidByte { offset, byte } => {
+                write!(f, "invalid polyline byte {byte:#04x} at byte {offset}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for PolylineError {}
+
+/// Why a polygon ring failed to expand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RingError {
+    /// An inline segment holds a malformed polyline.
+    Polyline(PolylineError),
+    /// A segment references a shared edge that is negative, out of range, or
+    /// failed to decode.
+    DanglingSharedEdge(i32),
+    /// The first and last points of the expanded ring differ.
+    NotClosed,
+}
+
+impl fmt::Display for RingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Polyline(err) => err.fmt(f),
+            Self::DanglingSharedEdge(edge) => write!(f, "missing shared edge {edge}"),
+            Self::NotClosed => f.write_str("ring is not closed"),
+        }
+    }
+}
+
+impl std::error::Error for RingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Polyline(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Where in a `CompressedTopoTimezones` payload a malformed ring was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RingLocation {
+    /// Name of the timezone owning the ring.
+    pub timezone: String,
+    /// Index of the polygon within the timezone.
+    pub polygon: usize,
+    /// Index of the hole within the polygon, `None` for the exterior ring.
+    pub hole: Option<usize>,
+    /// Index of the segment within the ring, `None` for whole-ring problems.
+    pub segment: Option<usize>,
+}
+
+impl fmt::Display for RingLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "timezone {:?} polygon {}", self.timezone, self.polygon)?;
+        match self.hole {
+            Some(hole) => write!(f, " hole {hole}")?,
+            None => f.write_str(" exterior")?,
+        }
+        if let Some(segment) = self.segment {
+            write!(f, " segment {segment}")?;
+        }
+        Ok(())
+    }
+}
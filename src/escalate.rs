@@ -0,0 +1,173 @@
+//! Answering from simplified data and re-checking near borders against
+//! full-precision data.
+
+use crate::load::io_error;
+use crate::{Error, Finder, FinderOptions, pbgen};
+use prost::Message;
+use std::path::Path;
+
+/// Default escalation tolerance, in meters: the simplified borders stay
+/// within about 111 m of the full-precision ones (see the README's Accuracy
+/// section), plus a margin.
+const DEFAULT_TOLERANCE_M: f64 = 150.0;
+
+/// A [`Finder`] over topology-simplified data that escalates to
+/// full-precision data for points near a border.
+///
+/// Simplification only moves borders, so a point farther than the tolerance
+/// from every simplified edge gets the same answer from both datasets and is
+/// answered from the simplified one. Closer points are re-checked against
+/// the full-precision finder, whose polygons are built lazily
+/// ([`FinderOptions::lazy`]): only the zones around escalated points are
+/// ever materialized, keeping memory near that of the simplified data alone.
+/// Startup still decodes every full-precision ring once, so loading takes
+/// about as long as an eager load of both datasets.
+///
+/// ```rust,no_run
+/// use tzf_rs::PrecisionEscalatingFinder;
+///
+/// let finder = PrecisionEscalatingFinder::from_paths(
+///     "data/combined-with-oceans.reduce.topology.compress.topo.bin",
+///     "data/combined-with-oceans.compress.topo.bin",
+/// )
+/// .unwrap();
+/// println!("{}", finder.get_tz_name(116.3883, 39.9289));
+/// ```
+pub struct PrecisionEscalatingFinder {
+    simplified: Finder,
+    full: Finder,
+    tolerance_m: f64,
+}
+
+impl PrecisionEscalatingFinder {
+    /// Pair a finder over simplified data with one over full-precision
+    /// data. Build `full` with [`FinderOptions::lazy`] to load its polygons
+    /// only where queries escalate.
+    #[must_use]
+    pub fn new(simplified: Finder, full: Finder) -> Self {
+        Self {
+            simplified,
+            full,
+            tolerance_m: DEFAULT_TOLERANCE_M,
+        }
+    }
+
+    /// Builds both finders from encoded `CompressedTopoTimezones` payloads:
+    /// the simplified data eagerly with Y stripes, the full data lazily.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either payload fails to decode or validate.
+    pub fn from_bytes(simplified_topo: &[u8], full_topo: &[u8]) -> Result<Self, Error> {
+        let simplified = Finder::try_from_compressed_topo_with_options(
+            pbgen::CompressedTopoTimezones::decode(simplified_topo)?,
+            FinderOptions::y_stripes(),
+        )?;
+        let full = Finder::try_from_compressed_topo_with_options(
+            pbgen::CompressedTopoTimezones::decode(full_topo)?,
+            FinderOptions::y_stripes().lazy(true),
+        )?;
+        Ok(Self::new(simplified, full))
+    }
+
+    /// Reads both topo data files from disk; see
+    /// [`PrecisionEscalatingFinder::from_bytes`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if a file cannot be read, otherwise see
+    /// [`PrecisionEscalatingFinder::from_bytes`].
+    pub fn from_paths(
+        simplified_path: impl AsRef<Path>,
+        full_path: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        let (simplified_path, full_path) = (simplified_path.as_ref(), full_path.as_ref());
+        let simplified = std::fs::read(simplified_path).map_err(io_error(simplified_path))?;
+        let full = std::fs::read(full_path).map_err(io_error(full_path))?;
+        Self::from_bytes(&simplified, &full)
+    }
+
+    /// Builds from the embedded datasets of the `bundled` and `full`
+    /// features.
+    ///
+    /// ```rust
+    /// # #[cfg(all(feature = "bundled", feature = "full"))]
+    /// # {
+    /// use tzf_rs::PrecisionEscalatingFinder;
+    ///
+    /// let finder = PrecisionEscalatingFinder::try_new().unwrap();
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either embedded dataset fails to decode or
+    /// validate.
+    #[cfg(all(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(all(feature = "bundled", feature = "full"))))]
+    pub fn try_new() -> Result<Self, Error> {
+        Self::from_bytes(
+            &tzf_dist::load_topology_compress_topo(),
+            &tzf_dist_git::load_compress_topo(),
+        )
+    }
+
+    /// Escalate points within `tolerance_m` meters of a simplified border.
+    /// Defaults to 150 m.
+    #[must_use]
+    pub fn tolerance_m(self, tolerance_m: f64) -> Self {
+        Self {
+            tolerance_m,
+            ..self
+        }
+    }
+
+    /// Whether a query at the point is answered from the full-precision
+    /// data.
+    #[must_use]
+    pub fn escalates(&self, lng: f64, lat: f64) -> bool {
+        self.simplified.near_border(lng, lat, self.tolerance_m)
+    }
+
+    /// The finder answering from the point's dataset.
+    fn finder_at(&self, lng: f64, lat: f64) -> &Finder {
+        if self.escalates(lng, lat) {
+            &self.full
+        } else {
+            &self.simplified
+        }
+    }
+
+    /// ```rust
+    /// # #[cfg(all(feature = "bundled", feature = "full"))]
+    /// # {
+    /// use tzf_rs::PrecisionEscalatingFinder;
+    ///
+    /// let finder = PrecisionEscalatingFinder::try_new().unwrap();
+    /// assert_eq!("Europe/Kyiv", finder.get_tz_name(24.5212, 50.2506));
+    /// # }
+    /// ```
+    #[must_use]
+    pub fn get_tz_name(&self, lng: f64, lat: f64) -> &str {
+        self.finder_at(lng, lat).get_tz_name(lng, lat)
+    }
+
+    /// Every zone containing the point; see [`Finder::get_tz_names`].
+    #[must_use]
+    pub fn get_tz_names(&self, lng: f64, lat: f64) -> Vec<&str> {
+        self.finder_at(lng, lat).get_tz_names(lng, lat)
+    }
+
+    /// The finder over simplified data.
+    #[must_use]
+    pub fn simplified(&self) -> &Finder {
+        &self.simplified
+    }
+
+    /// The finder over full-precision data.
+    #[must_use]
+    pub fn full(&self) -> &Finder {
+        &self.full
+    }
+}
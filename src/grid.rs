@@ -0,0 +1,335 @@
+//! The grid candidate index, and building it from polygon geometry for data
+//! that does not embed one.
+
+use crate::distance::for_each_ring_edge;
+use crate::{PolygonRings, Zone, pbgen};
+use std::collections::{HashMap, HashSet};
+use std::ops::RangeInclusive;
+use std::sync::OnceLock;
+
+/// Cell size of the grid built for data without an embedded `GridIndex`;
+/// see [`FinderOptions::grid_resolution`](crate::FinderOptions::grid_resolution).
+///
+/// Finer cells hold fewer candidates and let more queries skip the
+/// point-in-polygon test, at the cost of a larger index and a longer build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GridResolution {
+    /// Build no grid; queries on such data scan every timezone.
+    Off,
+    /// 1°×1° cells, the layout of the embedded `GridIndex`.
+    #[default]
+    Degree,
+    /// 0.5°×0.5° cells.
+    HalfDegree,
+    /// 0.25°×0.25° cells.
+    QuarterDegree,
+}
+
+impl GridResolution {
+    /// Cells per degree, `None` for [`GridResolution::Off`].
+    pub(crate) fn cells_per_degree(self) -> Option<u8> {
+        match self {
+            Self::Off => None,
+            Self::Degree => Some(1),
+            Self::HalfDegree => Some(2),
+            Self::QuarterDegree => Some(4),
+        }
+    }
+}
+
+/// Candidate item indices by cell. A cell's key is
+/// `(floor(lng * n), floor(lat * n))` for `n` cells per degree.
+pub(crate) struct Grid {
+    pub(crate) cells_per_degree: u8,
+    pub(crate) cells: HashMap<(i16, i16), Vec<u32>>,
+    // Single-candidate cells lying entirely inside their candidate.
+    covered: HashSet<(i16, i16)>,
+    // Zone → the cells it covers, worked out on first use, for zones whose
+    // flags the grid was built without; `None` for zones flagged in
+    // `covered`.
+    deferred: Vec<Option<OnceLock<HashSet<(i16, i16)>>>>,
+}
+
+impl Grid {
+    /// A grid from `(key, candidates, covered)` cells. Flags of cells with
+    /// more than one candidate are ignored.
+    pub(crate) fn new(
+        cells_per_degree: u8,
+        cells: impl IntoIterator<Item = ((i16, i16), Vec<u32>, bool)>,
+    ) -> Self {
+        let mut grid = Self {
+            cells_per_degree,
+            cells: HashMap::new(),
+            covered: HashSet::new(),
+            deferred: Vec::new(),
+        };
+        for (key, candidates, covered) in cells {
+            if covered && candidates.len() == 1 {
+                grid.covered.insert(key);
+            }
+            grid.cells.insert(key, candidates);
+        }
+        grid
+    }
+
+    /// Whether the single-candidate cell `key` lies entirely inside its
+    /// candidate. `false` for other cells. A deferred candidate's coverage is
+    /// worked out from its polygons in `all` the first time it is asked for.
+    pub(crate) fn is_covered<Z: Zone>(&self, key: (i16, i16), all: &[Z]) -> bool {
+        let Some(&[idx]) = self.cells.get(&key).map(Vec::as_slice) else {
+            return false;
+        };
+        match self.deferred.get(idx as usize) {
+            Some(Some(cells)) => cells
+                .get_or_init(|| self.covered_cells(idx, &all[idx as usize].polygons()))
+                .contains(&key),
+            _ => self.covered.contains(&key),
+        }
+    }
+
+    /// Work out the coverage of `zones` on first use instead of trusting
+    /// their flags. Grids decoded without flags defer every zone, so loading
+    /// them clips no rings.
+    pub(crate) fn defer_coverage(&mut self, zones: impl IntoIterator<Item = u32>) {
+        for idx in zones {
+            let idx = idx as usize;
+            if self.deferred.len() <= idx {
+                self.deferred.resize_with(idx + 1, || None);
+            }
+            self.deferred[idx] = Some(OnceLock::new());
+        }
+    }
+
+    /// Whether zone `idx`'s coverage is worked out on first use.
+    pub(crate) fn is_deferred(&self, idx: u32) -> bool {
+        matches!(self.deferred.get(idx as usize), Some(Some(_)))
+    }
+
+    /// The single-candidate cells of zone `idx`, given as polygons in
+    /// degrees, that it covers entirely.
+    fn covered_cells(&self, idx: u32, polygons: &[PolygonRings]) -> HashSet<(i16, i16)> {
+        let mut hits = HashMap::new();
+        for rings in polygons {
+            mark_polygon(self.cells_per_degree, rings, &mut hits);
+        }
+        hits.into_iter()
+            .filter(|&(key, hit)| {
+                hit.center_inside
+                    && !hit.crosses_interior
+                    && self.cells.get(&key).is_some_and(|cell| cell[..] == [idx])
+            })
+            .map(|(key, _)| key)
+            .collect()
+    }
+
+    /// The key of the cell holding the point.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn key(&self, lng: f64, lat: f64) -> (i16, i16) {
+        let n = f64::from(self.cells_per_degree);
+        ((lng * n).floor() as i16, (lat * n).floor() as i16)
+    }
+
+    /// Cell edge length in degrees.
+    pub(crate) fn cell_size(&self) -> f64 {
+        1.0 / f64::from(self.cells_per_degree)
+    }
+
+    /// The cell's `[min_lng, min_lat, max_lng, max_lat]`, in degrees.
+    pub(crate) fn cell_bounds(&self, (x, y): (i16, i16)) -> [f64; 4] {
+        let size = self.cell_size();
+        let (lng, lat) = (f64::from(x) * size, f64::from(y) * size);
+        [lng, lat, lng + size, lat + size]
+    }
+
+    /// The number of cells around a parallel.
+    pub(crate) fn lng_cells(&self) -> i16 {
+        360 * i16::from(self.cells_per_degree)
+    }
+
+    /// The grid as an embeddable `GridIndex`, cells sorted by latitude then
+    /// longitude. Only 1°×1° grids can be embedded.
+    pub(crate) fn to_grid_index(&self, version: &str) -> Option<pbgen::GridIndex> {
+        if self.cells_per_degree != 1 {
+            return None;
+        }
+        let mut cells: Vec<pbgen::GridIndexCell> = self
+            .cells
+            .iter()
+            .map(|(&(lng, lat), indices)| pbgen::GridIndexCell {
+                lng: i32::from(lng),
+                lat: i32::from(lat),
+                tz_indices: indices.clone(),
+            })
+            .collect();
+        cells.sort_unstable_by_key(|cell| (cell.lat, cell.lng));
+        Some(pbgen::GridIndex {
+            cells,
+            version: version.to_string(),
+        })
+    }
+}
+
+/// How one zone meets one grid cell.
+#[derive(Default, Clone, Copy)]
+struct CellHit {
+    /// An edge of the zone crosses the open interior of the cell.
+    crosses_interior: bool,
+    /// The cell's center lies inside the zone.
+    center_inside: bool,
+}
+
+/// Cells `floor(lo)..=floor(hi)`, clamped to `min..=max`.
+#[allow(clippy::cast_possible_truncation)]
+fn cells(lo: f64, hi: f64, min: i16, max: i16) -> RangeInclusive<i16> {
+    let clamp = |v: f64| v.floor().clamp(f64::from(min), f64::from(max)) as i16;
+    clamp(lo)..=clamp(hi)
+}
+
+/// Clip the segment `a`–`b` to the closed rectangle
+/// `[min_x, min_y, max_x, max_y]` (Liang–Barsky). `None` when they do not
+/// meet, otherwise whether the segment enters the rectangle's interior
+/// rather than only touching its boundary.
+pub(crate) fn clip_segment(
+    a: (f64, f64),
+    b: (f64, f64),
+    [min_x, min_y, max_x, max_y]: [f64; 4],
+) -> Option<bool> {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let (mut t0, mut t1) = (0.0f64, 1.0f64);
+    for (p, q) in [
+        (-dx, a.0 - min_x),
+        (dx, max_x - a.0),
+        (-dy, a.1 - min_y),
+        (dy, max_y - a.1),
+    ] {
+        if p == 0.0 {
+            if q < 0.0 {
+                return None;
+            }
+        } else if p < 0.0 {
+            t0 = t0.max(q / p);
+        } else {
+            t1 = t1.min(q / p);
+        }
+    }
+    if t0 > t1 {
+        return None;
+    }
+    // The clipped part lies on the boundary exactly when its midpoint does.
+    let t = (t0 + t1) / 2.0;
+    let (x, y) = (a.0 + t * dx, a.1 + t * dy);
+    Some(min_x < x && x < max_x && min_y < y && y < max_y)
+}
+
+/// Record how polygon `rings` (exterior first) meets the cells it touches,
+/// on a grid of `cells_per_degree` cells per degree. Coordinates are scaled
+/// to cell units, so cell `(x, y)` spans `[x, x + 1] × [y, y + 1]`.
+fn mark_polygon(
+    cells_per_degree: u8,
+    rings: &PolygonRings,
+    hits: &mut HashMap<(i16, i16), CellHit>,
+) {
+    let n = i16::from(cells_per_degree);
+    let scale = f64::from(cells_per_degree);
+    let (lng_cells, lat_cells) = ((-180 * n, 180 * n - 1), (-90 * n, 90 * n - 1));
+    // Crossings of each row's center line, for the even-odd center test.
+    let mut rows: HashMap<i16, Vec<f64>> = HashMap::new();
+    for ring in rings {
+        let scaled = ring.iter().map(|&(lng, lat)| (lng * scale, lat * scale));
+        for_each_ring_edge(scaled, |a, b| {
+            let ys = cells(a.1.min(b.1), a.1.max(b.1), lat_cells.0, lat_cells.1);
+            for x in cells(a.0.min(b.0), a.0.max(b.0), lng_cells.0, lng_cells.1) {
+                for y in ys.clone() {
+                    let (x0, y0) = (f64::from(x), f64::from(y));
+                    if let Some(interior) = clip_segment(a, b, [x0, y0, x0 + 1.0, y0 + 1.0]) {
+                        hits.entry((x, y)).or_default().crosses_interior |= interior;
+                    }
+                }
+            }
+            for y in ys {
+                let center = f64::from(y) + 0.5;
+                if (a.1 > center) != (b.1 > center) {
+                    let x = a.0 + (center - a.1) * (b.0 - a.0) / (b.1 - a.1);
+                    rows.entry(y).or_default().push(x);
+                }
+            }
+        });
+    }
+    for (y, mut xs) in rows {
+        xs.sort_unstable_by(f64::total_cmp);
+        for span in xs.chunks_exact(2) {
+            // Cells whose center `x + 0.5` lies strictly inside the span.
+            let (first, last) = (span[0] - 0.5, span[1] - 0.5);
+            for x in cells(first, last, lng_cells.0, lng_cells.1) {
+                let center = f64::from(x) + 0.5;
+                if span[0] < center && center < span[1] {
+                    hits.entry((x, y)).or_default().center_inside = true;
+                }
+            }
+        }
+    }
+}
+
+/// Builds a [`Grid`] zone by zone, so callers can feed geometry as they
+/// decode it.
+pub(crate) struct GridBuilder {
+    cells_per_degree: u8,
+    zones: u32,
+    cells: HashMap<(i16, i16), Vec<(u32, bool)>>,
+}
+
+impl GridBuilder {
+    pub(crate) fn new(cells_per_degree: u8) -> Self {
+        Self {
+            cells_per_degree,
+            zones: 0,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// Add the next zone, given as polygons in degrees.
+    ///
+    /// The zone is a candidate of every cell its edges touch or whose center
+    /// it contains; the bounding box of each edge limits the cells clipped
+    /// against it.
+    pub(crate) fn add_zone(&mut self, polygons: &[PolygonRings]) {
+        let mut hits = HashMap::new();
+        for rings in polygons {
+            mark_polygon(self.cells_per_degree, rings, &mut hits);
+        }
+        for (key, hit) in hits {
+            let covers = hit.center_inside && !hit.crosses_interior;
+            self.cells
+                .entry(key)
+                .or_default()
+                .push((self.zones, covers));
+        }
+        self.zones += 1;
+    }
+
+    /// The finished grid, with coverage flags for its single-candidate
+    /// cells.
+    pub(crate) fn finish(self) -> Grid {
+        let cells = self.cells.into_iter().map(|(key, zones)| {
+            let covered = zones[0].1;
+            (
+                key,
+                zones.into_iter().map(|(idx, _)| idx).collect(),
+                covered,
+            )
+        });
+        Grid::new(self.cells_per_degree, cells)
+    }
+}
+
+/// Build the grid for zones given as polygons in degrees, in item order.
+pub(crate) fn build_grid(
+    cells_per_degree: u8,
+    zones: impl IntoIterator<Item = Vec<PolygonRings>>,
+) -> Grid {
+    let mut builder = GridBuilder::new(cells_per_degree);
+    for polygons in zones {
+        builder.add_zone(&polygons);
+    }
+    builder.finish()
+}
@@ -0,0 +1,111 @@
+//! Interned timezone ids for string-free hot paths.
+
+use crate::{FinderCore, Zone};
+use std::sync::Arc;
+
+/// A compact, interned timezone id.
+///
+/// Ids index a [`TimezoneNames`] table sorted lexicographically, so
+/// comparing ids orders zones by name. A [`Finder`](crate::Finder) issues
+/// ids from its own table; a [`FuzzyFinder`](crate::FuzzyFinder) issues
+/// them from its preindex names, or from a `Finder`'s table once given it
+/// with [`FuzzyFinder::with_names`](crate::FuzzyFinder::with_names), as
+/// [`DefaultFinder`](crate::DefaultFinder) does so that both tiers share one
+/// id space. Ids are only meaningful together with the table that issued
+/// them; resolve them with `name` and `id_of` on the finder or the table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimezoneId(u16);
+
+impl TimezoneId {
+    /// The raw id.
+    #[must_use]
+    pub const fn get(self) -> u16 {
+        self.0
+    }
+
+    /// The id as an index into the finder's sorted name table.
+    #[must_use]
+    pub const fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A sorted, deduplicated table of timezone names, which [`TimezoneId`]s
+/// index. Clones share the table.
+///
+/// ```rust
+/// use tzf_rs::{Finder, FuzzyFinder};
+///
+/// let finder = Finder::new();
+/// let fuzzy = FuzzyFinder::new().with_names(finder.names());
+/// let id = fuzzy.get_tz_id(139.4382, 36.4432).unwrap();
+/// assert_eq!(Some(id), finder.get_tz_id(139.4382, 36.4432));
+/// assert_eq!(Some("Asia/Tokyo"), finder.names().name(id));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TimezoneNames(Arc<[String]>);
+
+impl TimezoneNames {
+    /// Sort and deduplicate `names` into a table.
+    pub(crate) fn new(names: impl IntoIterator<Item = String>) -> Self {
+        let mut names: Vec<String> = names.into_iter().collect();
+        names.sort();
+        names.dedup();
+        Self(names.into())
+    }
+
+    /// The name an id stands for, or `None` if the id is out of range.
+    #[must_use]
+    pub fn name(&self, id: TimezoneId) -> Option<&str> {
+        self.0.get(id.index()).map(String::as_str)
+    }
+
+    /// The id of a name, or `None` if the table has no such name or it
+    /// ranks past `u16::MAX`.
+    #[must_use]
+    pub fn id_of(&self, name: &str) -> Option<TimezoneId> {
+        let rank = self.0.binary_search_by(|n| n.as_str().cmp(name)).ok()?;
+        u16::try_from(rank).ok().map(TimezoneId)
+    }
+
+    /// Number of names in the table.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the table holds no names.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The names in id order.
+    pub fn iter(&self) -> impl Iterator<Item = &str> {
+        self.0.iter().map(String::as_str)
+    }
+}
+
+/// Build the sorted name table and the per-item id list for items named by
+/// `item_names`, in item order. Names past `u16::MAX` get no id.
+pub(crate) fn build_id_tables<'a>(
+    item_names: impl Iterator<Item = &'a str> + Clone,
+) -> (TimezoneNames, Vec<Option<TimezoneId>>) {
+    let names = TimezoneNames::new(item_names.clone().map(str::to_string));
+    let item_ids = item_names.map(|name| names.id_of(name)).collect();
+    (names, item_ids)
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    pub(crate) fn get_tz_id(&self, lng: f64, lat: f64) -> Option<TimezoneId> {
+        self.item_ids[self.find_item(lng, lat)?]
+    }
+
+    pub(crate) fn name(&self, id: TimezoneId) -> Option<&str> {
+        self.names.name(id)
+    }
+
+    pub(crate) fn id_of(&self, name: &str) -> Option<TimezoneId> {
+        self.names.id_of(name)
+    }
+}
@@ -4,31 +4,203 @@
 use geometry_rs::{
     CoordStorage, I32Point, I32Polygon, I32RaycastMode, Point, Polygon, PolygonBuildOptions,
 };
+#[cfg(any(feature = "bundled", feature = "full"))]
+use prost::Message;
 #[cfg(feature = "export-geojson")]
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::f64::consts::PI;
+use std::sync::{Arc, OnceLock};
 use std::vec;
-#[cfg(all(feature = "bundled", feature = "full"))]
-compile_error!(
-    "features `bundled` and `full` are mutually exclusive; \
-     add `default-features = false` when enabling `full`"
-);
-
+// With both features the simplified `bundled` data backs the default
+// constructors, and the full data only the `*_full` ones and
+// `PrecisionEscalatingFinder`.
 #[cfg(feature = "bundled")]
 use tzf_dist::{load_preindex, load_topology_compress_topo};
 #[cfg(feature = "full")]
-use tzf_dist_git::{load_compress_topo, load_preindex, load_topology_compress_topo};
+use tzf_dist_git::load_compress_topo;
+#[cfg(all(feature = "full", not(feature = "bundled")))]
+use tzf_dist_git::{load_preindex, load_topology_compress_topo};
+
+/// Dispatch once at the top of each query; everything below the dispatch is
+/// monomorphized over the storage type, avoiding a per-polygon enum match.
+/// Defined ahead of the modules so they dispatch through it too.
+macro_rules! with_core {
+    ($finder:expr, $core:ident => $body:expr) => {
+        match &$finder.inner {
+            $crate::FinderKind::Float($core) => $body,
+            $crate::FinderKind::Scaled($core) => $body,
+            $crate::FinderKind::Snapshot($core) => $body,
+            $crate::FinderKind::Shared($core) => $body,
+        }
+    };
+}
+
+/// Like `with_core!`, wrapping each arm's result, another core over the
+/// same storage type, back into its variant.
+macro_rules! map_core {
+    ($finder:expr, $core:ident => $body:expr) => {
+        match &$finder.inner {
+            $crate::FinderKind::Float($core) => $crate::FinderKind::Float($body),
+            $crate::FinderKind::Scaled($core) => $crate::FinderKind::Scaled($body),
+            $crate::FinderKind::Snapshot($core) => $crate::FinderKind::Snapshot($body),
+            $crate::FinderKind::Shared($core) => $crate::FinderKind::Shared($body),
+        }
+    };
+}
+
+#[cfg(feature = "export-geojson")]
+mod antimeridian;
+mod batch;
+mod distance;
+mod error;
+mod escalate;
+mod grid;
+mod id;
+mod load;
+mod lookup;
 pub mod pbgen;
+mod quadtree;
+mod region;
+mod reload;
+mod shared;
+mod snapshot;
+mod subset;
+mod topo;
+mod trajectory;
+#[cfg(any(feature = "chrono-tz", feature = "jiff"))]
+mod tz;
 
+#[cfg(feature = "export-geojson")]
+pub use antimeridian::AntimeridianMode;
+pub use distance::BorderDistance;
+pub use error::{Error, PolylineError, RingError, RingLocation};
+pub use escalate::PrecisionEscalatingFinder;
+pub use grid::GridResolution;
+use grid::{Grid, GridBuilder};
+use id::build_id_tables;
+pub use id::{TimezoneId, TimezoneNames};
+pub use lookup::{LookupResult, LookupSource};
+use quadtree::{Leaf, QuadTree};
+pub use region::RegionZone;
+pub use reload::ReloadableFinder;
+pub use snapshot::SNAPSHOT_FORMAT_VERSION;
+pub use topo::CompressedTopoBuilder;
+pub use trajectory::ZoneSegment;
+#[cfg(feature = "jiff")]
+pub use tz::LocalOffset;
+
+/// Builds an item's polygons on first use; see [`FinderOptions::lazy`].
+type Materialize<T> = Arc<dyn Fn() -> Vec<Polygon<T>> + Send + Sync>;
+
+/// Clones share the polygons, so a subset finder (see
+/// [`Finder::subset_by_names`]) costs no geometry rebuild.
+#[derive(Clone)]
 struct Item<T: CoordStorage> {
-    polys: Vec<Polygon<T>>,
+    polys: Arc<OnceLock<Vec<Polygon<T>>>>,
+    // Exterior bounding box of each polygon, in degrees, on first use.
+    bounds: Arc<OnceLock<Vec<[f64; 4]>>>,
+    // `None` when the polygons were built up front.
+    materialize: Option<Materialize<T>>,
     name: String,
 }
 
 impl<T: CoordStorage> Item<T> {
+    fn new(name: String, polys: Vec<Polygon<T>>) -> Self {
+        Self {
+            polys: Arc::new(OnceLock::from(polys)),
+            bounds: Arc::new(OnceLock::new()),
+            materialize: None,
+            name,
+        }
+    }
+
+    fn new_lazy(name: String, materialize: Materialize<T>) -> Self {
+        Self {
+            polys: Arc::new(OnceLock::new()),
+            bounds: Arc::new(OnceLock::new()),
+            materialize: Some(materialize),
+            name,
+        }
+    }
+
+    /// The item's polygons, building them first in lazy mode.
+    fn polys(&self) -> &[Polygon<T>] {
+        self.polys
+            .get_or_init(|| self.materialize.as_ref().map_or_else(Vec::new, |f| f()))
+    }
+}
+
+/// Rings of one polygon in degrees: the exterior first, then its holes.
+type PolygonRings = Vec<Vec<(f64, f64)>>;
+
+/// One timezone's geometry as the query code sees it: polygons built in
+/// memory ([`Item`]) or read in place from a snapshot
+/// ([`snapshot::SnapshotZone`]).
+trait Zone {
+    fn name(&self) -> &str;
+
+    /// Whether any polygon contains the point, borders included.
+    fn contains_point(&self, p: &Point) -> bool;
+
+    /// Call `f` with every ring edge of every polygon, in degrees.
+    fn for_each_edge(&self, f: impl FnMut((f64, f64), (f64, f64)));
+
+    /// Every polygon's rings, in degrees.
+    fn polygons(&self) -> Vec<PolygonRings>;
+
+    /// Call `f` with each ring, in degrees, of every polygon whose exterior
+    /// bounding box overlaps `bbox`. Rings come polygon by polygon, exterior
+    /// first; the flag marks exteriors.
+    fn for_each_ring_in(
+        &self,
+        bbox: [f64; 4],
+        f: impl FnMut(bool, &mut dyn Iterator<Item = (f64, f64)>),
+    );
+
+    /// Every polygon's exterior bounding box as
+    /// `[min_lng, min_lat, max_lng, max_lat]`, in degrees.
+    fn bounds(&self) -> Vec<[f64; 4]>;
+
+    /// Call `f` with at least every ring edge whose bounding box overlaps
+    /// `bbox`, in degrees. Zones knowing their rings' bounding boxes skip
+    /// the rings missing it.
+    fn for_each_edge_in(&self, bbox: [f64; 4], mut f: impl FnMut((f64, f64), (f64, f64))) {
+        self.for_each_edge(|a, b| {
+            if boxes_overlap(bbox, ring_bounds([a, b])) {
+                f(a, b);
+            }
+        });
+    }
+}
+
+/// Whether two `[min_lng, min_lat, max_lng, max_lat]` boxes share a point.
+fn boxes_overlap(a: [f64; 4], b: [f64; 4]) -> bool {
+    a[0] <= b[2] && b[0] <= a[2] && a[1] <= b[3] && b[1] <= a[3]
+}
+
+/// Bounding box of a ring as `[min_lng, min_lat, max_lng, max_lat]`.
+fn ring_bounds(ring: impl IntoIterator<Item = (f64, f64)>) -> [f64; 4] {
+    ring.into_iter().fold(
+        [
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ],
+        |[min_x, min_y, max_x, max_y], (x, y)| {
+            [min_x.min(x), min_y.min(y), max_x.max(x), max_y.max(y)]
+        },
+    )
+}
+
+impl<T: CoordStorage> Zone for Item<T> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
     fn contains_point(&self, p: &Point) -> bool {
-        for poly in &self.polys {
+        for poly in self.polys() {
             // Timezone polygons tile the globe, so a query that lands exactly
             // on a shared border must belong to both neighbours rather than to
             // neither. The nautical zones make this easy to hit: their borders
@@ -40,34 +212,169 @@ impl<T: CoordStorage> Item<T> {
         }
         false
     }
+
+    fn for_each_edge(&self, mut f: impl FnMut((f64, f64), (f64, f64))) {
+        for poly in self.polys() {
+            polygon_edges(poly, &mut f);
+        }
+    }
+
+    fn for_each_edge_in(&self, bbox: [f64; 4], mut f: impl FnMut((f64, f64), (f64, f64))) {
+        // Holes lie inside their exterior, so its box bounds every ring.
+        let bounds = self.bounds.get_or_init(|| self.compute_bounds());
+        for (poly, &poly_bounds) in self.polys().iter().zip(bounds) {
+            if boxes_overlap(bbox, poly_bounds) {
+                polygon_edges(poly, &mut f);
+            }
+        }
+    }
+
+    fn for_each_ring_in(
+        &self,
+        bbox: [f64; 4],
+        mut f: impl FnMut(bool, &mut dyn Iterator<Item = (f64, f64)>),
+    ) {
+        let bounds = self.bounds.get_or_init(|| self.compute_bounds());
+        for (poly, &poly_bounds) in self.polys().iter().zip(bounds) {
+            if !boxes_overlap(bbox, poly_bounds) {
+                continue;
+            }
+            // Storage space → degrees; `scale` is 1.0 for float storage.
+            let scale = poly.scale();
+            f(
+                true,
+                &mut poly
+                    .exterior()
+                    .iter()
+                    .map(|p| (p.x.to_f64() / scale, p.y.to_f64() / scale)),
+            );
+            for hole in poly.holes() {
+                f(
+                    false,
+                    &mut hole
+                        .iter()
+                        .map(|p| (p.x.to_f64() / scale, p.y.to_f64() / scale)),
+                );
+            }
+        }
+    }
+
+    fn polygons(&self) -> Vec<PolygonRings> {
+        let mut ret = Vec::with_capacity(self.polys().len());
+        for poly in self.polys() {
+            // Storage space → degrees; `scale` is 1.0 for float storage.
+            let scale = poly.scale();
+            let mut rings: PolygonRings = vec![
+                poly.exterior()
+                    .iter()
+                    .map(|p| (p.x.to_f64() / scale, p.y.to_f64() / scale))
+                    .collect(),
+            ];
+            for hole in poly.holes() {
+                rings.push(
+                    hole.iter()
+                        .map(|p| (p.x.to_f64() / scale, p.y.to_f64() / scale))
+                        .collect(),
+                );
+            }
+            ret.push(rings);
+        }
+        ret
+    }
+
+    fn bounds(&self) -> Vec<[f64; 4]> {
+        self.bounds.get_or_init(|| self.compute_bounds()).clone()
+    }
 }
 
-/// Monomorphized finder internals. `T` is the polygon coordinate storage:
-/// `i32` (1e5-scaled) for compressed topo data, `f64` for user-supplied
-/// protobuf data.
-struct FinderCore<T: CoordStorage> {
-    all: Vec<Item<T>>,
-    data_version: String,
-    // grid maps (floor(lng), floor(lat)) → candidate item indices.
-    // Populated automatically when loading CompressedTopoTimezones that
-    // contains an embedded GridIndex.
-    grid: Option<HashMap<(i16, i16), Vec<u32>>>,
+impl<T: CoordStorage> Item<T> {
+    fn compute_bounds(&self) -> Vec<[f64; 4]> {
+        self.polys()
+            .iter()
+            .map(|poly| {
+                let scale = poly.scale();
+                ring_bounds(
+                    poly.exterior()
+                        .iter()
+                        .map(|p| (p.x.to_f64() / scale, p.y.to_f64() / scale)),
+                )
+            })
+            .collect()
+    }
 }
 
-enum FinderKind {
-    Float(FinderCore<f64>),
-    Scaled(FinderCore<i32>),
+/// Call `f` with every ring edge of `poly`, in degrees.
+fn polygon_edges<T: CoordStorage>(poly: &Polygon<T>, f: &mut impl FnMut((f64, f64), (f64, f64))) {
+    // Storage space → degrees; `scale` is 1.0 for float storage.
+    let scale = poly.scale();
+    distance::for_each_ring_edge(
+        poly.exterior()
+            .iter()
+            .map(|p| (p.x.to_f64() / scale, p.y.to_f64() / scale)),
+        &mut *f,
+    );
+    for hole in poly.holes() {
+        distance::for_each_ring_edge(
+            hole.iter()
+                .map(|p| (p.x.to_f64() / scale, p.y.to_f64() / scale)),
+            &mut *f,
+        );
+    }
 }
 
-/// Dispatch once at the top of each query; everything below the dispatch is
-/// monomorphized over the storage type, avoiding a per-polygon enum match.
-macro_rules! with_core {
-    ($finder:expr, $core:ident => $body:expr) => {
-        match &$finder.inner {
-            FinderKind::Float($core) => $body,
-            FinderKind::Scaled($core) => $body,
+/// Monomorphized finder internals. `Z` is the zone geometry: [`Item<i32>`]
+/// (1e5-scaled) for compressed topo data, [`Item<f64>`] for user-supplied
+/// protobuf data, [`snapshot::SnapshotZone`] for snapshots.
+struct FinderCore<Z: Zone> {
+    all: Vec<Z>,
+    data_version: String,
+    // Grid cell → candidate item indices. Decoded from an embedded
+    // GridIndex, or built from the polygons at the resolution chosen by
+    // `FinderOptions::grid_resolution` when the data has none. Cells missing
+    // from the grid hold no zone, so queries there find none; only finders
+    // without a grid scan every item.
+    grid: Option<Grid>,
+    // Dense grid cells subdivided further; see `FinderOptions::quadtree`.
+    tree: Option<QuadTree>,
+    // Sorted, deduplicated zone names; a `TimezoneId` indexes this table.
+    names: TimezoneNames,
+    // item index → id of its name; `None` past `u16::MAX` names.
+    item_ids: Vec<Option<TimezoneId>>,
+    // The options the zones were built with, so snapshots index the same
+    // rings.
+    options: FinderOptions,
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    fn new(all: Vec<Z>, data_version: String, grid: Option<Grid>) -> Self {
+        let (names, item_ids) = build_id_tables(all.iter().map(Z::name));
+        Self {
+            all,
+            data_version,
+            grid,
+            tree: None,
+            names,
+            item_ids,
+            options: FinderOptions::default(),
         }
-    };
+    }
+
+    /// Record the options the zones were built with, and build the quadtree
+    /// over the grid if they ask for one.
+    fn with_options(mut self, options: FinderOptions) -> Self {
+        if let (Some(grid), Some(max_candidates)) = (&self.grid, options.quadtree) {
+            self.tree = Some(QuadTree::build(&self.all, grid, max_candidates));
+        }
+        self.options = options;
+        self
+    }
+}
+
+enum FinderKind {
+    Float(FinderCore<Item<f64>>),
+    Scaled(FinderCore<Item<i32>>),
+    Snapshot(FinderCore<snapshot::SnapshotZone>),
+    Shared(FinderCore<shared::SharedZone>),
 }
 
 /// Finder works anywhere.
@@ -84,8 +391,9 @@ pub struct Finder {
 
 /// Minimum ring segment count for building a polygon acceleration index.
 ///
-/// Rings below this are scanned linearly. Only [`FinderOptions::YStripes`]
-/// consults this value; the `NoIndex*` variants build no index at all.
+/// Rings below this are scanned linearly. The indexed modes consult this
+/// value unless [`FinderOptions::min_index_segments`] overrides it; the
+/// `NoIndex*` variants build no index at all.
 ///
 /// 32 matches the Go implementation (`internal/geom.minIndexSegments`) rather
 /// than the geometry-rs default of 64, which tzf-rs previously inherited from
@@ -96,37 +404,114 @@ pub struct Finder {
 /// p50 over many iterations, resolves it more clearly (+14% p50 at 64).
 const DEFAULT_RTREE_MIN_SEGMENTS: usize = 32;
 
+// Rough index sizes per polygon segment, for [`FinderOptions::auto`] only:
+// an RTree stores a bounding box and id per segment plus its inner nodes,
+// Y stripes store a segment id per stripe the segment spans, and the
+// compressed quadtree packs segment ids into shared leaves.
+const RTREE_BYTES_PER_SEGMENT: usize = 24;
+const Y_STRIPES_BYTES_PER_SEGMENT: usize = 8;
+const COMPRESSED_QUAD_BYTES_PER_SEGMENT: usize = 3;
+
 /// Finder build options for polygon acceleration indexes.
 ///
 /// Compressed topo data (the tzf-dist default) always stores polygons as
-/// 1e5-scaled integer coordinates; the options only choose the acceleration
-/// index and the raycast flavor. The indexes operate directly in the scaled
-/// integer storage space, so [`FinderOptions::YStripes`] keeps the full
+/// 1e5-scaled integer coordinates; the options choose the acceleration
+/// index, the raycast flavor, and whether polygons are built up front or on
+/// first use ([`FinderOptions::lazy`]). The indexes operate directly in the
+/// scaled integer storage space, so [`FinderOptions::YStripes`] keeps the full
 /// memory savings of integer storage.
 ///
 /// Default:
-/// - [`FinderOptions::NoIndex`]
+/// - [`FinderOptions::NoIndex`], polygons built eagerly
+/// - a 1°×1° grid built for data without an embedded grid index
+///   ([`FinderOptions::grid_resolution`])
+///
+/// # Compatibility
+///
+/// Before 2.0 this was a `#[non_exhaustive]` enum. Its variant paths live on
+/// as associated constants, so `FinderOptions::YStripes` still works as a
+/// value and as a pattern, but they can no longer be brought into scope with
+/// `use`, and a pattern only matches options left otherwise at their
+/// defaults: `FinderOptions::YStripes.lazy(true)` does not match
+/// `FinderOptions::YStripes`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinderOptions {
+    index: IndexMode,
+    lazy_polygons: bool,
+    grid: GridResolution,
+    quadtree: Option<usize>,
+    shared_edges: bool,
+    min_index_segments: usize,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-#[non_exhaustive]
-pub enum FinderOptions {
+enum IndexMode {
+    #[default]
+    NoIndex,
+    YStripes,
+    NoIndexFloatRaycast,
+    NoIndexIntegerRaycast,
+    Indexes {
+        rtree: bool,
+        compressed_quad: bool,
+        y_stripes: bool,
+    },
+    Auto {
+        max_index_bytes: usize,
+    },
+}
+
+// `FinderOptions` used to be an enum; the constants keep its variant paths
+// (values and patterns) compiling.
+#[allow(non_upper_case_globals)]
+impl FinderOptions {
     /// Disable polygon acceleration indexes.
     ///
     /// For compressed topo data this is equivalent to
     /// [`FinderOptions::NoIndexFloatRaycast`].
-    #[default]
-    NoIndex,
+    pub const NoIndex: Self = Self::with_index(IndexMode::NoIndex);
     /// Use Y stripes index (recommended).
-    YStripes,
+    pub const YStripes: Self = Self::with_index(IndexMode::YStripes);
     /// Disable polygon acceleration indexes; segment endpoints are converted
     /// to `f64` in registers during raycasting.
-    NoIndexFloatRaycast,
+    pub const NoIndexFloatRaycast: Self = Self::with_index(IndexMode::NoIndexFloatRaycast);
     /// Disable polygon acceleration indexes and use an integer cross-product
     /// raycast, which snaps the query point to the 1e-5 grid (a semantic
     /// difference near polygon edges). Opt-in.
-    NoIndexIntegerRaycast,
+    pub const NoIndexIntegerRaycast: Self = Self::with_index(IndexMode::NoIndexIntegerRaycast);
+    /// Use geometry-rs's RTree segment index.
+    pub const RTree: Self = Self::with_index(IndexMode::Indexes {
+        rtree: true,
+        compressed_quad: false,
+        y_stripes: false,
+    });
+    /// Use geometry-rs's compressed quadtree segment index, the smallest of
+    /// the three.
+    pub const CompressedQuad: Self = Self::with_index(IndexMode::Indexes {
+        rtree: false,
+        compressed_quad: true,
+        y_stripes: false,
+    });
+}
+
+impl Default for FinderOptions {
+    fn default() -> Self {
+        Self::NoIndex
+    }
 }
 
 impl FinderOptions {
+    const fn with_index(index: IndexMode) -> Self {
+        Self {
+            index,
+            lazy_polygons: false,
+            grid: GridResolution::Degree,
+            quadtree: None,
+            shared_edges: false,
+            min_index_segments: DEFAULT_RTREE_MIN_SEGMENTS,
+        }
+    }
+
     /// Disable polygon acceleration indexes.
     #[must_use]
     pub fn no_index() -> Self {
@@ -149,29 +534,250 @@ impl FinderOptions {
         Self::NoIndexIntegerRaycast
     }
 
-    fn to_polygon_build_options(self) -> PolygonBuildOptions {
-        match self {
-            Self::YStripes => PolygonBuildOptions {
-                enable_rtree: false,
-                enable_compressed_quad: false,
-                enable_y_stripes: true,
-                rtree_min_segments: DEFAULT_RTREE_MIN_SEGMENTS,
+    /// Use geometry-rs's RTree segment index.
+    #[must_use]
+    pub fn rtree() -> Self {
+        Self::RTree
+    }
+
+    /// Use geometry-rs's compressed quadtree segment index.
+    #[must_use]
+    pub fn compressed_quad() -> Self {
+        Self::CompressedQuad
+    }
+
+    /// Pick each polygon's index from its segment count, spending at most
+    /// `max_index_bytes` of (estimated) index memory on any one polygon.
+    ///
+    /// Polygons below [`FinderOptions::min_index_segments`] get no index.
+    /// Larger ones get the fastest index that fits the budget, trying the
+    /// RTree, then Y stripes, then the compressed quadtree; a polygon too
+    /// large for all three is scanned linearly. The budget thus caps memory
+    /// on the few huge polygons while small and medium ones keep the fast
+    /// indexes.
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, FinderOptions};
+    ///
+    /// let finder = DefaultFinder::new_with_options(FinderOptions::auto(256 * 1024));
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// ```
+    #[must_use]
+    pub fn auto(max_index_bytes: usize) -> Self {
+        Self::with_index(IndexMode::Auto { max_index_bytes })
+    }
+
+    /// Choose the polygon indexes individually, mirroring geometry-rs's
+    /// `PolygonBuildOptions`. Enabling several builds each of them; lookups
+    /// use whichever geometry-rs prefers.
+    #[must_use]
+    pub fn polygon_indexes(self, rtree: bool, compressed_quad: bool, y_stripes: bool) -> Self {
+        Self {
+            index: IndexMode::Indexes {
+                rtree,
+                compressed_quad,
+                y_stripes,
             },
-            Self::NoIndex | Self::NoIndexFloatRaycast | Self::NoIndexIntegerRaycast => {
-                PolygonBuildOptions {
-                    enable_rtree: false,
-                    enable_compressed_quad: false,
-                    enable_y_stripes: false,
-                    rtree_min_segments: DEFAULT_RTREE_MIN_SEGMENTS,
+            ..self
+        }
+    }
+
+    /// Rings with fewer segments than this are scanned linearly instead of
+    /// getting an index. Defaults to 32.
+    #[must_use]
+    pub fn min_index_segments(self, min_index_segments: usize) -> Self {
+        Self {
+            min_index_segments,
+            ..self
+        }
+    }
+
+    /// Build each timezone's polygons (and their indexes) on its first
+    /// point-in-polygon test instead of up front.
+    ///
+    /// Lenient loads of data embedding a grid index, like the bundled data,
+    /// decode nothing up front: shared edges, rings and polygons are all
+    /// expanded when a query first reaches a zone, which also works out
+    /// which grid cells the zone covers. Startup still expands every ring
+    /// once to build a grid for data without one (unless
+    /// [`FinderOptions::grid_resolution`] is [`GridResolution::Off`]), and in
+    /// strict mode to validate it; those loads save the memory and build
+    /// time of polygons queries never reach, not the ring decoding.
+    /// Applies to compressed topo data; `from_pb` input is always eager.
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, FinderOptions};
+    ///
+    /// let finder = DefaultFinder::new_with_options(FinderOptions::y_stripes().lazy(true));
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// ```
+    #[must_use]
+    pub fn lazy(self, lazy_polygons: bool) -> Self {
+        Self {
+            lazy_polygons,
+            ..self
+        }
+    }
+
+    /// Whether polygons are built lazily; see [`FinderOptions::lazy`].
+    #[must_use]
+    pub fn is_lazy(&self) -> bool {
+        self.lazy_polygons
+    }
+
+    /// Cell size of the grid candidate index built for data that does not
+    /// embed one: `from_pb` input, and compressed topo data written without
+    /// a `GridIndex`. An embedded index is always used as is.
+    ///
+    /// Each timezone is a candidate of the cells its polygons intersect, and
+    /// cells covered by a single timezone skip the point-in-polygon test.
+    /// Without a grid every query scans every timezone.
+    ///
+    /// ```rust
+    /// use tzf_rs::pbgen::{Point, Polygon, Timezone, Timezones};
+    /// use tzf_rs::{Finder, FinderOptions, GridResolution};
+    ///
+    /// let square = [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0)];
+    /// let tzs = Timezones {
+    ///     timezones: vec![Timezone {
+    ///         polygons: vec![Polygon {
+    ///             points: square.iter().map(|&(lng, lat)| Point { lng, lat }).collect(),
+    ///             holes: Vec::new(),
+    ///         }],
+    ///         name: "Square".to_string(),
+    ///     }],
+    ///     reduced: false,
+    ///     version: "custom".to_string(),
+    /// };
+    /// let options = FinderOptions::default().grid_resolution(GridResolution::QuarterDegree);
+    /// let finder = Finder::from_pb_with_options(tzs, options);
+    /// assert_eq!("Square", finder.get_tz_name(1.3, 0.6));
+    /// ```
+    #[must_use]
+    pub fn grid_resolution(self, grid: GridResolution) -> Self {
+        Self { grid, ..self }
+    }
+
+    /// Subdivide grid cells with more than `max_candidates` candidate
+    /// timezones into an adaptive quadtree, splitting each quadrant again
+    /// until it holds at most `max_candidates` (down to 1/256 of a cell).
+    ///
+    /// Dense border regions then need fewer raycasts per query, and
+    /// quadrants lying entirely inside one timezone answer without any, like
+    /// single-candidate grid cells. Not built by default. Building tests
+    /// candidate polygons against each quadrant, so it adds to construction
+    /// time and builds the polygons of lazy zones in dense cells. Snapshots
+    /// and subsets keep the grid only.
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, FinderOptions};
+    ///
+    /// let finder = DefaultFinder::new_with_options(FinderOptions::y_stripes().quadtree(2));
+    /// assert_eq!("Europe/Belgrade", finder.get_tz_name(20.4489, 44.7866));
+    /// ```
+    #[must_use]
+    pub fn quadtree(self, max_candidates: usize) -> Self {
+        Self {
+            quadtree: Some(max_candidates),
+            ..self
+        }
+    }
+
+    /// Keep each shared border of compressed topo data stored once, with
+    /// rings referencing it, instead of expanding a copy into every ring
+    /// that uses it.
+    ///
+    /// Points on a border between two zones are then held once rather than
+    /// twice, which is most of the bundled data. Shared edges are decoded
+    /// up front; with [`FinderOptions::lazy`] only each ring's Y-stripes
+    /// index is deferred to its first test. Applies to compressed topo data
+    /// only.
+    ///
+    /// Shared-edge rings can only build Y stripes and use the float
+    /// raycast, so this works with [`FinderOptions::YStripes`],
+    /// [`FinderOptions::NoIndex`], [`FinderOptions::NoIndexFloatRaycast`]
+    /// and [`FinderOptions::polygon_indexes`] enabling Y stripes alone.
+    /// With any other index mode the `try_*` constructors return
+    /// [`Error::SharedEdgesIndexMode`], and the others keep the chosen index
+    /// by expanding every ring as if this were off.
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, FinderOptions};
+    ///
+    /// let finder = DefaultFinder::new_with_options(FinderOptions::y_stripes().shared_edges(true));
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// ```
+    #[must_use]
+    pub fn shared_edges(self, shared_edges: bool) -> Self {
+        Self {
+            shared_edges,
+            ..self
+        }
+    }
+
+    /// Build options for a polygon with `segments` segments over all its
+    /// rings.
+    fn polygon_build_options(self, segments: usize) -> PolygonBuildOptions {
+        let (enable_rtree, enable_compressed_quad, enable_y_stripes) = match self.index {
+            IndexMode::YStripes => (false, false, true),
+            IndexMode::NoIndex
+            | IndexMode::NoIndexFloatRaycast
+            | IndexMode::NoIndexIntegerRaycast => (false, false, false),
+            IndexMode::Indexes {
+                rtree,
+                compressed_quad,
+                y_stripes,
+            } => (rtree, compressed_quad, y_stripes),
+            IndexMode::Auto { .. } if segments < self.min_index_segments => (false, false, false),
+            IndexMode::Auto { max_index_bytes } => {
+                let fits =
+                    |per_segment: usize| segments.saturating_mul(per_segment) <= max_index_bytes;
+                if fits(RTREE_BYTES_PER_SEGMENT) {
+                    (true, false, false)
+                } else if fits(Y_STRIPES_BYTES_PER_SEGMENT) {
+                    (false, false, true)
+                } else {
+                    (false, fits(COMPRESSED_QUAD_BYTES_PER_SEGMENT), false)
                 }
             }
+        };
+        PolygonBuildOptions {
+            enable_rtree,
+            enable_compressed_quad,
+            enable_y_stripes,
+            rtree_min_segments: self.min_index_segments,
+        }
+    }
+
+    /// Whether shared-edge rings can serve the index mode; see
+    /// [`FinderOptions::shared_edges`].
+    fn supports_shared_edges(self) -> bool {
+        match self.index {
+            IndexMode::NoIndex | IndexMode::NoIndexFloatRaycast | IndexMode::YStripes => true,
+            IndexMode::Indexes {
+                rtree,
+                compressed_quad,
+                ..
+            } => !rtree && !compressed_quad,
+            IndexMode::NoIndexIntegerRaycast | IndexMode::Auto { .. } => false,
         }
     }
 
+    /// Whether a ring of `segments` edges gets an acceleration index.
+    fn indexes(self, segments: usize) -> bool {
+        let build = self.polygon_build_options(segments);
+        (build.enable_rtree || build.enable_compressed_quad || build.enable_y_stripes)
+            && segments >= build.rtree_min_segments
+    }
+
     fn i32_raycast_mode(self) -> I32RaycastMode {
-        match self {
-            Self::NoIndexIntegerRaycast => I32RaycastMode::Integer,
-            Self::NoIndex | Self::NoIndexFloatRaycast | Self::YStripes => I32RaycastMode::Float,
+        match self.index {
+            IndexMode::NoIndexIntegerRaycast => I32RaycastMode::Integer,
+            IndexMode::NoIndex
+            | IndexMode::NoIndexFloatRaycast
+            | IndexMode::YStripes
+            | IndexMode::Indexes { .. }
+            | IndexMode::Auto { .. } => I32RaycastMode::Float,
         }
     }
 }
@@ -179,35 +785,73 @@ impl FinderOptions {
 /// Decode a Google Polyline encoded byte slice into a list of Points.
 ///
 /// The go-polyline library encodes coordinates as [lng, lat] pairs with 1e5 precision.
-#[allow(clippy::cast_possible_truncation)]
-fn decode_polyline(encoded: &[u8]) -> Vec<I32Point> {
+fn decode_polyline(encoded: &[u8]) -> Result<Vec<I32Point>, PolylineError> {
     let mut points = Vec::new();
     let mut index = 0;
     let mut lng: i64 = 0;
     let mut lat: i64 = 0;
 
     while index < encoded.len() {
-        let (dlng, next) = polyline_decode_value(encoded, index);
+        let start = index;
+        let (dlng, next) = polyline_decode_value(encoded, index)?;
         index = next;
-        let (dlat, next) = polyline_decode_value(encoded, index);
+        let (dlat, next) = polyline_decode_value(encoded, index)?;
         index = next;
+        // Each delta is below 2^35, and the running sums are checked against
+        // i32 after every step, so the i64 additions cannot overflow.
         lng += dlng;
         lat += dlat;
-        points.push(I32Point {
-            x: i32::try_from(lng).expect("polyline longitude exceeds i32"),
-            y: i32::try_from(lat).expect("polyline latitude exceeds i32"),
-        });
+        match (i32::try_from(lng), i32::try_from(lat)) {
+            (Ok(x), Ok(y)) => points.push(I32Point { x, y }),
+            _ => return Err(PolylineError::Overflow { offset: start }),
+        }
     }
-    points
+    Ok(points)
 }
 
-fn polyline_decode_value(encoded: &[u8], start: usize) -> (i64, usize) {
+/// Encode points as a Google Polyline byte string, the inverse of
+/// [`decode_polyline`].
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn encode_polyline(points: &[I32Point]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(points.len() * 4);
+    let (mut lng, mut lat) = (0i64, 0i64);
+    for p in points {
+        for delta in [i64::from(p.x) - lng, i64::from(p.y) - lat] {
+            let mut value = if delta < 0 { !(delta << 1) } else { delta << 1 };
+            while value >= 0x20 {
+                out.push(((value & 0x1F | 0x20) + 63) as u8);
+                value >>= 5;
+            }
+            out.push((value + 63) as u8);
+        }
+        (lng, lat) = (i64::from(p.x), i64::from(p.y));
+    }
+    out
+}
+
+/// Longest varint a 1e5-scaled `i32` coordinate delta can need: the zigzag
+/// encoded delta of two `i32` values fits in 34 bits, i.e. seven 5-bit chunks.
+const POLYLINE_MAX_CHUNKS: u32 = 7;
+
+fn polyline_decode_value(encoded: &[u8], start: usize) -> Result<(i64, usize), PolylineError> {
     let mut result: i64 = 0;
     let mut shift = 0;
     let mut index = start;
 
     loop {
-        let byte = (encoded[index] as i64) - 63;
+        let Some(&raw) = encoded.get(index) else {
+            return Err(PolylineError::Truncated { offset: start });
+        };
+        if !(63..=126).contains(&raw) {
+            return Err(PolylineError::InvalidByte {
+                offset: index,
+                byte: raw,
+            });
+        }
+        if shift == 5 * POLYLINE_MAX_CHUNKS {
+            return Err(PolylineError::Overflow { offset: start });
+        }
+        let byte = i64::from(raw) - 63;
         index += 1;
         result |= (byte & 0x1F) << shift;
         shift += 5;
@@ -221,72 +865,347 @@ fn polyline_decode_value(encoded: &[u8], start: usize) -> (i64, usize) {
     } else {
         result >> 1
     };
-    (value, index)
+    Ok((value, index))
+}
+
+/// How the compressed topo builder reacts to malformed input.
+#[derive(Clone, Copy)]
+enum TopoDecodeMode {
+    /// Stop at the first problem and report it.
+    Strict,
+    /// Drop the broken shared edge, ring or grid entry and keep going, so the
+    /// infallible constructors never panic on untrusted data.
+    Lenient,
 }
 
-fn expand_compressed_ring(
+impl TopoDecodeMode {
+    /// `Err` in strict mode; `Ok` in lenient mode, telling the caller to skip
+    /// the offending piece.
+    fn reject(self, err: Error) -> Result<(), Error> {
+        match self {
+            Self::Strict => Err(err),
+            Self::Lenient => Ok(()),
+        }
+    }
+}
+
+/// Decode the shared-edge library. Edges that failed to decode in lenient
+/// mode are left as `None`, so rings referencing them are dropped as dangling.
+fn decode_shared_edges(
+    shared_edges: &[pbgen::CompressedSharedEdge],
+    mode: TopoDecodeMode,
+) -> Result<Vec<Option<Vec<I32Point>>>, Error> {
+    let len = shared_edges.len();
+    let mut edges: Vec<Option<Vec<I32Point>>> = vec![None; len];
+    for edge in shared_edges {
+        let Some(slot) = usize::try_from(edge.id)
+            .ok()
+            .and_then(|id| edges.get_mut(id))
+        else {
+            mode.reject(Error::SharedEdgeIdOutOfRange { id: edge.id, len })?;
+            continue;
+        };
+        match decode_polyline(&edge.points) {
+            Ok(points) => *slot = Some(points),
+            Err(reason) => mode.reject(Error::MalformedSharedEdge {
+                id: edge.id,
+                reason,
+            })?,
+        }
+    }
+    Ok(edges)
+}
+
+/// Expand a ring from its segments, resolving shared edges by id through
+/// `lookup`. On failure, returns the index of the offending segment and the
+/// reason.
+fn expand_compressed_ring<E: AsRef<[I32Point]>>(
     segs: &[pbgen::CompressedRingSegment],
-    edges: &[Vec<I32Point>],
-) -> Vec<I32Point> {
+    lookup: impl Fn(usize) -> Option<E>,
+) -> Result<Vec<I32Point>, (Option<usize>, RingError)> {
+    let edge = |idx: i32, seg: usize| {
+        usize::try_from(idx)
+            .ok()
+            .and_then(&lookup)
+            .ok_or((Some(seg), RingError::DanglingSharedEdge(idx)))
+    };
+
     let mut pts = Vec::new();
-    for seg in segs {
+    for (i, seg) in segs.iter().enumerate() {
         match &seg.content {
             Some(pbgen::compressed_ring_segment::Content::Inline(inline)) => {
-                pts.extend(decode_polyline(&inline.points));
+                let decoded = decode_polyline(&inline.points)
+                    .map_err(|reason| (Some(i), RingError::Polyline(reason)))?;
+                pts.extend(decoded);
             }
             Some(pbgen::compressed_ring_segment::Content::EdgeForward(idx)) => {
-                pts.extend_from_slice(&edges[*idx as usize]);
+                pts.extend_from_slice(edge(*idx, i)?.as_ref());
             }
             Some(pbgen::compressed_ring_segment::Content::EdgeReversed(idx)) => {
-                pts.extend(edges[*idx as usize].iter().rev().copied());
+                pts.extend(edge(*idx, i)?.as_ref().iter().rev().copied());
             }
             None => {}
         }
     }
-    pts
+    Ok(pts)
 }
 
-impl<T: CoordStorage> FinderCore<T> {
-    fn get_tz_name(&self, lng: f64, lat: f64) -> &str {
-        if let Some(ref grid) = self.grid {
-            let key = (lng.floor() as i16, lat.floor() as i16);
-            let indices = match grid.get(&key) {
-                Some(v) => v,
-                None => return "",
-            };
-            // Single-candidate short-circuit: skip PIP when there is only one
-            // candidate and we are away from antimeridian / pole edges.
-            if indices.len() == 1 && (-179.0..179.0).contains(&lng) && (-89.0..89.0).contains(&lat)
-            {
-                return &self.all[indices[0] as usize].name;
+/// A polygon's expanded exterior ring and holes.
+type ExpandedPolygon = (Vec<I32Point>, Vec<Vec<I32Point>>);
+
+/// Expand every ring of a timezone. In lenient mode broken rings are dropped
+/// (a broken exterior drops its whole polygon), so this never fails.
+fn expand_topo_timezone<E: AsRef<[I32Point]>>(
+    tz: &pbgen::CompressedTopoTimezone,
+    lookup: impl Fn(usize) -> Option<E>,
+    mode: TopoDecodeMode,
+) -> Result<Vec<ExpandedPolygon>, Error> {
+    resolve_topo_timezone(tz, mode, |segs| {
+        let ring = expand_compressed_ring(segs, &lookup)?;
+        // The lenient path has always accepted open rings; only strict
+        // mode insists on closure.
+        if matches!(mode, TopoDecodeMode::Strict) && !ring_is_closed(&ring) {
+            return Err((None, RingError::NotClosed));
+        }
+        Ok(ring)
+    })
+}
+
+/// Resolve every ring of a timezone with `resolve`, as exterior and holes
+/// per polygon, locating failures for [`Error::MalformedRing`]. In lenient
+/// mode broken rings are dropped (a broken exterior drops its whole
+/// polygon).
+fn resolve_topo_timezone<R>(
+    tz: &pbgen::CompressedTopoTimezone,
+    mode: TopoDecodeMode,
+    mut resolve: impl FnMut(&[pbgen::CompressedRingSegment]) -> Result<R, (Option<usize>, RingError)>,
+) -> Result<Vec<(R, Vec<R>)>, Error> {
+    let mut polys = Vec::with_capacity(tz.polygons.len());
+    for (polygon, poly) in tz.polygons.iter().enumerate() {
+        let malformed = |hole, (segment, reason)| Error::MalformedRing {
+            location: RingLocation {
+                timezone: tz.name.clone(),
+                polygon,
+                hole,
+                segment,
+            },
+            reason,
+        };
+        let exterior = match resolve(&poly.exterior) {
+            Ok(exterior) => exterior,
+            Err(err) => {
+                mode.reject(malformed(None, err))?;
+                continue;
             }
-            let p = geometry_rs::Point { x: lng, y: lat };
-            for &idx in indices {
-                if self.all[idx as usize].contains_point(&p) {
-                    return &self.all[idx as usize].name;
+        };
+        let mut interior = Vec::with_capacity(poly.holes.len());
+        for (hole, ring) in poly.holes.iter().enumerate() {
+            match resolve(&ring.exterior) {
+                Ok(ring) => interior.push(ring),
+                Err(err) => mode.reject(malformed(Some(hole), err))?,
+            }
+        }
+        polys.push((exterior, interior));
+    }
+    Ok(polys)
+}
+
+fn build_topo_polygons(rings: Vec<ExpandedPolygon>, options: FinderOptions) -> Vec<I32Polygon> {
+    let raycast_mode = options.i32_raycast_mode();
+    // The acceleration indexes operate directly in the 1e5-scaled integer
+    // storage space, so enabling them no longer requires falling back to
+    // float storage.
+    rings
+        .into_iter()
+        .map(|(exterior, interior)| {
+            let segments = exterior.len() + interior.iter().map(Vec::len).sum::<usize>();
+            let build_options = options.polygon_build_options(segments);
+            I32Polygon::new_with_options(exterior, interior, 1e5, raycast_mode, Some(build_options))
+        })
+        .collect()
+}
+
+/// Expanded rings in degrees, exterior first, for building a grid.
+fn expanded_to_degrees(polygons: &[ExpandedPolygon]) -> Vec<PolygonRings> {
+    let degrees = |ring: &Vec<I32Point>| -> Vec<(f64, f64)> {
+        ring.iter()
+            .map(|p| (f64::from(p.x) / 1e5, f64::from(p.y) / 1e5))
+            .collect()
+    };
+    polygons
+        .iter()
+        .map(|(exterior, holes)| {
+            std::iter::once(exterior)
+                .chain(holes)
+                .map(degrees)
+                .collect()
+        })
+        .collect()
+}
+
+/// Raw polyline bytes of each shared edge, by id, for lazy decoding. Edges
+/// with out-of-range ids are dropped, as in [`decode_shared_edges`].
+fn shared_edge_pool(shared_edges: Vec<pbgen::CompressedSharedEdge>) -> Vec<Option<Vec<u8>>> {
+    let mut pool: Vec<Option<Vec<u8>>> = vec![None; shared_edges.len()];
+    for edge in shared_edges {
+        if let Some(slot) = usize::try_from(edge.id)
+            .ok()
+            .and_then(|id| pool.get_mut(id))
+        {
+            *slot = Some(edge.points);
+        }
+    }
+    pool
+}
+
+fn ring_is_closed(pts: &[I32Point]) -> bool {
+    match (pts.first(), pts.last()) {
+        (Some(first), Some(last)) => first.x == last.x && first.y == last.y,
+        _ => false,
+    }
+}
+
+/// Decode the embedded grid index, checking every candidate against the
+/// timezone count so queries can index `FinderCore::all` without bounds
+/// failures.
+///
+/// The index carries no coverage flags, so every zone's are worked out on
+/// first use; see [`Grid::defer_coverage`].
+#[allow(clippy::cast_possible_truncation)]
+fn decode_grid_index(
+    gi: pbgen::GridIndex,
+    tz_count: usize,
+    mode: TopoDecodeMode,
+) -> Result<Grid, Error> {
+    let mut cells = Vec::with_capacity(gi.cells.len());
+    for mut cell in gi.cells {
+        // One cell per degree; anything else would wrap in the `i16` key.
+        if !(-180..180).contains(&cell.lng) || !(-90..90).contains(&cell.lat) {
+            mode.reject(Error::GridCellOutOfRange {
+                lng: cell.lng,
+                lat: cell.lat,
+            })?;
+            continue;
+        }
+        if let Some(&tz_index) = cell
+            .tz_indices
+            .iter()
+            .find(|&&idx| !usize::try_from(idx).is_ok_and(|idx| idx < tz_count))
+        {
+            mode.reject(Error::GridIndexOutOfRange {
+                lng: cell.lng,
+                lat: cell.lat,
+                tz_index,
+                len: tz_count,
+            })?;
+            cell.tz_indices
+                .retain(|&idx| usize::try_from(idx).is_ok_and(|idx| idx < tz_count));
+        }
+        cells.push(((cell.lng as i16, cell.lat as i16), cell.tz_indices, false));
+    }
+    let mut grid = Grid::new(1, cells);
+    grid.defer_coverage(0..tz_count as u32);
+    Ok(grid)
+}
+
+/// Whether `lng` lies on the antimeridian. Zones crossing it are stored
+/// split there, so queries on it check both the -180° and the 180° side.
+fn on_antimeridian(lng: f64) -> bool {
+    lng.abs() == 180.0
+}
+
+/// The items that may contain a query point.
+enum Candidates<'a> {
+    /// A grid cell lying entirely inside this item.
+    Inside(usize),
+    /// A quadtree leaf lying entirely inside this item.
+    InsideLeaf(usize),
+    /// The candidates of the point's grid cell or quadtree leaf.
+    Items(&'a [u32]),
+    /// Every item: there is no grid.
+    All,
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    fn candidates(&self, lng: f64, lat: f64) -> Candidates<'_> {
+        let Some(grid) = &self.grid else {
+            return Candidates::All;
+        };
+        match self
+            .tree
+            .as_ref()
+            .and_then(|tree| tree.leaf(grid, lng, lat))
+        {
+            Some(Leaf::Inside(idx)) => Candidates::InsideLeaf(idx),
+            Some(Leaf::Candidates(indices)) => Candidates::Items(indices),
+            None => {
+                let key = grid.key(lng, lat);
+                match grid.cells.get(&key).map(Vec::as_slice) {
+                    Some(&[idx]) if grid.is_covered(key, &self.all) => {
+                        Candidates::Inside(idx as usize)
+                    }
+                    Some(indices) => Candidates::Items(indices),
+                    None => Candidates::Items(&[]),
                 }
             }
-            return "";
         }
+    }
+
+    fn get_tz_name(&self, lng: f64, lat: f64) -> &str {
+        self.find_item(lng, lat)
+            .map_or("", |idx| self.all[idx].name())
+    }
+
+    /// Index of the first item containing the point, as [`Finder::get_tz_name`]
+    /// reports it.
+    fn find_item(&self, lng: f64, lat: f64) -> Option<usize> {
+        if on_antimeridian(lng) {
+            return self
+                .find_item_at(-180.0, lat)
+                .or_else(|| self.find_item_at(180.0, lat));
+        }
+        self.find_item_at(lng, lat)
+    }
+
+    fn find_item_at(&self, lng: f64, lat: f64) -> Option<usize> {
         let p = geometry_rs::Point { x: lng, y: lat };
-        for item in &self.all {
-            if item.contains_point(&p) {
-                return &item.name;
-            }
+        match self.candidates(lng, lat) {
+            Candidates::Inside(idx) | Candidates::InsideLeaf(idx) => Some(idx),
+            Candidates::Items(indices) => indices
+                .iter()
+                .map(|&idx| idx as usize)
+                .find(|&idx| self.all[idx].contains_point(&p)),
+            Candidates::All => self.all.iter().position(|item| item.contains_point(&p)),
         }
-        ""
     }
 
     fn get_tz_names(&self, lng: f64, lat: f64) -> Vec<&str> {
+        if on_antimeridian(lng) {
+            let mut ret = self.get_tz_names_at(-180.0, lat);
+            for name in self.get_tz_names_at(180.0, lat) {
+                if !ret.contains(&name) {
+                    ret.push(name);
+                }
+            }
+            return ret;
+        }
+        self.get_tz_names_at(lng, lat)
+    }
+
+    fn get_tz_names_at(&self, lng: f64, lat: f64) -> Vec<&str> {
         let mut ret: Vec<&str> = vec![];
-        if let Some(ref grid) = self.grid {
-            let key = (lng.floor() as i16, lat.floor() as i16);
-            if let Some(indices) = grid.get(&key) {
-                let p = geometry_rs::Point { x: lng, y: lat };
-                for &idx in indices {
-                    if self.all[idx as usize].contains_point(&p) {
-                        ret.push(&self.all[idx as usize].name);
-                    }
+        let indices = match self.candidates(lng, lat) {
+            Candidates::Inside(idx) | Candidates::InsideLeaf(idx) => {
+                return vec![self.all[idx].name()];
+            }
+            Candidates::Items(indices) => Some(indices),
+            Candidates::All => None,
+        };
+        if let Some(indices) = indices {
+            let p = geometry_rs::Point { x: lng, y: lat };
+            for &idx in indices {
+                if self.all[idx as usize].contains_point(&p) {
+                    ret.push(self.all[idx as usize].name());
                 }
             }
             return ret;
@@ -294,7 +1213,7 @@ impl<T: CoordStorage> FinderCore<T> {
         let p = geometry_rs::Point { x: lng, y: lat };
         for item in &self.all {
             if item.contains_point(&p) {
-                ret.push(&item.name);
+                ret.push(item.name());
             }
         }
         ret
@@ -303,14 +1222,14 @@ impl<T: CoordStorage> FinderCore<T> {
     fn timezonenames(&self) -> Vec<&str> {
         let mut ret: Vec<&str> = vec![];
         for item in &self.all {
-            ret.push(&item.name);
+            ret.push(item.name());
         }
         ret
     }
 }
 
 impl Finder {
-    fn from_pb_with_polygon_options(tzs: pbgen::Timezones, options: PolygonBuildOptions) -> Self {
+    fn from_pb_with_polygon_options(tzs: pbgen::Timezones, finder_options: FinderOptions) -> Self {
         let mut all: Vec<Item<f64>> = vec![];
         for tz in &tzs.timezones {
             let mut polys: Vec<Polygon> = vec![];
@@ -337,139 +1256,337 @@ impl Finder {
                     interior.push(holeextr);
                 }
 
+                let segments = exterior.len() + interior.iter().map(Vec::len).sum::<usize>();
+                let options = finder_options.polygon_build_options(segments);
                 polys.push(geometry_rs::Polygon::new(exterior, interior, Some(options)));
             }
 
-            all.push(Item {
-                name: tz.name.to_string(),
-                polys,
-            });
+            all.push(Item::new(tz.name.to_string(), polys));
         }
+        let grid = finder_options
+            .grid
+            .cells_per_degree()
+            .map(|n| grid::build_grid(n, all.iter().map(Zone::polygons)));
+        let core = FinderCore::new(all, tzs.version, grid).with_options(finder_options);
         Self {
-            inner: FinderKind::Float(FinderCore {
-                all,
-                data_version: tzs.version,
-                grid: None,
-            }),
+            inner: FinderKind::Float(core),
         }
     }
 
     fn from_compressed_topo_with_polygon_options(
         tzs: pbgen::CompressedTopoTimezones,
-        options: PolygonBuildOptions,
-        raycast_mode: I32RaycastMode,
-    ) -> Self {
-        let mut edges: Vec<Vec<I32Point>> = vec![Vec::new(); tzs.shared_edges.len()];
-        for edge in &tzs.shared_edges {
-            edges[edge.id as usize] = decode_polyline(&edge.points);
-        }
-
-        let grid = tzs.grid_index.map(|gi| {
-            let mut m = HashMap::with_capacity(gi.cells.len());
-            for cell in gi.cells {
-                m.insert((cell.lng as i16, cell.lat as i16), cell.tz_indices);
+        options: FinderOptions,
+        mode: TopoDecodeMode,
+    ) -> Result<Self, Error> {
+        if options.shared_edges {
+            if options.supports_shared_edges() {
+                return Self::from_compressed_topo_shared(tzs, options, mode);
             }
-            m
-        });
-
-        let mut all: Vec<Item<i32>> = vec![];
-        for tz in &tzs.timezones {
-            let mut polys: Vec<I32Polygon> = vec![];
-            for poly in &tz.polygons {
-                let exterior = expand_compressed_ring(&poly.exterior, &edges);
-                let interior: Vec<Vec<I32Point>> = poly
-                    .holes
-                    .iter()
-                    .map(|hole| expand_compressed_ring(&hole.exterior, &edges))
-                    .collect();
-                // The acceleration indexes operate directly in the 1e5-scaled
-                // integer storage space, so enabling them no longer requires
-                // falling back to float storage.
-                polys.push(I32Polygon::new_with_options(
-                    exterior,
-                    interior,
-                    1e5,
-                    raycast_mode,
-                    Some(options),
-                ));
+            mode.reject(Error::SharedEdgesIndexMode)?;
+        }
+        let lazy = options.is_lazy();
+        let embedded = match tzs.grid_index {
+            Some(gi) => Some(decode_grid_index(gi, tzs.timezones.len(), mode)?),
+            None => None,
+        };
+        // Without an embedded index, one is built from the rings as they are
+        // decoded.
+        let mut builder = match embedded {
+            Some(_) => None,
+            None => options.grid.cells_per_degree().map(GridBuilder::new),
+        };
+        // A grid being built needs every ring at startup; an embedded one
+        // works out coverage on first use, so lazy lenient builds with it
+        // expand rings on demand.
+        let expand_now = matches!(mode, TopoDecodeMode::Strict) || builder.is_some();
+        // Lazy builds expanding no rings now decode shared edges on demand.
+        let edges = if lazy && !expand_now {
+            Vec::new()
+        } else {
+            decode_shared_edges(&tzs.shared_edges, mode)?
+        };
+        let decoded = |id: usize| edges.get(id)?.as_deref();
+        let pool = lazy.then(|| Arc::new(shared_edge_pool(tzs.shared_edges)));
+
+        let mut all: Vec<Item<i32>> = Vec::with_capacity(tzs.timezones.len());
+        for tz in tzs.timezones {
+            // Feed the zone to the grid being built.
+            let mut index = |rings: &[ExpandedPolygon]| {
+                if let Some(builder) = &mut builder {
+                    builder.add_zone(&expanded_to_degrees(rings));
+                }
+            };
+            let Some(pool) = &pool else {
+                let rings = expand_topo_timezone(&tz, decoded, mode)?;
+                index(&rings);
+                all.push(Item::new(tz.name, build_topo_polygons(rings, options)));
+                continue;
+            };
+            if expand_now {
+                // Strict builds also report broken rings now rather than on
+                // first use.
+                let rings = expand_topo_timezone(&tz, decoded, mode)?;
+                index(&rings);
             }
-            all.push(Item {
-                name: tz.name.clone(),
-                polys,
-            });
+            let pool = Arc::clone(pool);
+            let name = tz.name.clone();
+            all.push(Item::new_lazy(
+                name,
+                Arc::new(move || {
+                    let lookup = |id: usize| decode_polyline(pool.get(id)?.as_deref()?).ok();
+                    // Lenient expansion drops broken rings and cannot fail.
+                    let rings = expand_topo_timezone(&tz, lookup, TopoDecodeMode::Lenient)
+                        .unwrap_or_default();
+                    build_topo_polygons(rings, options)
+                }),
+            ));
         }
-        Self {
-            inner: FinderKind::Scaled(FinderCore {
-                all,
-                data_version: tzs.version,
-                grid,
-            }),
+        let grid = embedded.or_else(|| builder.map(GridBuilder::finish));
+        let core = FinderCore::new(all, tzs.version, grid).with_options(options);
+        Ok(Self {
+            inner: FinderKind::Scaled(core),
+        })
+    }
+
+    /// Create a Finder from `CompressedTopoTimezones` protobuf data.
+    ///
+    /// This is the preferred constructor when using tzf-dist data.
+    #[must_use]
+    pub fn from_compressed_topo(tzs: pbgen::CompressedTopoTimezones) -> Self {
+        Self::from_compressed_topo_with_options(tzs, FinderOptions::default())
+    }
+
+    /// Create a Finder from `CompressedTopoTimezones` with explicit polygon build options.
+    ///
+    /// Malformed input never panics: broken shared edges, rings and grid
+    /// entries are dropped. Use
+    /// [`Finder::try_from_compressed_topo_with_options`] to reject such data
+    /// instead.
+    #[must_use]
+    pub fn from_compressed_topo_with_options(
+        tzs: pbgen::CompressedTopoTimezones,
+        options: FinderOptions,
+    ) -> Self {
+        match Self::from_compressed_topo_with_polygon_options(tzs, options, TopoDecodeMode::Lenient)
+        {
+            Ok(finder) => finder,
+            Err(err) => unreachable!("lenient topo decoding reported {err}"),
         }
     }
 
-    /// Create a Finder from `CompressedTopoTimezones` protobuf data.
-    ///
-    /// This is the preferred constructor when using tzf-dist data.
+    /// Fallible counterpart of [`Finder::from_compressed_topo`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a shared edge, ring segment or grid cell references
+    /// data outside the payload, if a polyline is malformed, or if a ring is
+    /// not closed. Ring errors carry the timezone, polygon and segment.
+    pub fn try_from_compressed_topo(tzs: pbgen::CompressedTopoTimezones) -> Result<Self, Error> {
+        Self::try_from_compressed_topo_with_options(tzs, FinderOptions::default())
+    }
+
+    /// Fallible counterpart of [`Finder::from_compressed_topo_with_options`].
+    ///
+    /// # Errors
+    ///
+    /// See [`Finder::try_from_compressed_topo`].
+    pub fn try_from_compressed_topo_with_options(
+        tzs: pbgen::CompressedTopoTimezones,
+        options: FinderOptions,
+    ) -> Result<Self, Error> {
+        Self::from_compressed_topo_with_polygon_options(tzs, options, TopoDecodeMode::Strict)
+    }
+
+    /// `from_pb` is used when you can use your own timezone data, as long as
+    /// it's compatible with Proto's desc.
+    ///
+    /// # Arguments
+    ///
+    /// * `tzs` - Timezones data.
+    ///
+    /// # Returns
+    ///
+    /// * `Finder` - A Finder instance.
+    #[must_use]
+    pub fn from_pb(tzs: pbgen::Timezones) -> Self {
+        Self::from_pb_with_options(tzs, FinderOptions::default())
+    }
+
+    /// Create a finder from protobuf data with explicit polygon build options.
+    #[must_use]
+    pub fn from_pb_with_options(tzs: pbgen::Timezones, options: FinderOptions) -> Self {
+        Self::from_pb_with_polygon_options(tzs, options)
+    }
+
+    /// Example:
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// ```
+    #[must_use]
+    pub fn get_tz_name(&self, lng: f64, lat: f64) -> &str {
+        with_core!(self, core => core.get_tz_name(lng, lat))
+    }
+
+    /// Checked variant of [`Finder::get_tz_name`].
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// assert_eq!("Asia/Shanghai", finder.try_get_tz_name(116.3883, 39.9289).unwrap());
+    /// assert!(finder.try_get_tz_name(f64::NAN, 39.9289).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLongitude`] or [`Error::InvalidLatitude`] if a
+    /// coordinate is NaN, infinite or out of range.
+    pub fn try_get_tz_name(&self, lng: f64, lat: f64) -> Result<&str, Error> {
+        let (lng, lat) = check_coordinates(lng, lat, false)?;
+        Ok(self.get_tz_name(lng, lat))
+    }
+
+    /// Like [`Finder::try_get_tz_name`], but finite longitudes outside
+    /// `[-180, 180]` are wrapped instead of rejected, so `190.0` is
+    /// looked up as `-170.0`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLongitude`] or [`Error::InvalidLatitude`] if a
+    /// coordinate is NaN or infinite, or the latitude is out of range.
+    pub fn try_get_tz_name_wrapped(&self, lng: f64, lat: f64) -> Result<&str, Error> {
+        let (lng, lat) = check_coordinates(lng, lat, true)?;
+        Ok(self.get_tz_name(lng, lat))
+    }
+
+    /// ```rust
+    /// use tzf_rs::Finder;
+    /// let finder = Finder::new();
+    /// println!("{:?}", finder.get_tz_names(116.3883, 39.9289));
+    /// ```
     #[must_use]
-    pub fn from_compressed_topo(tzs: pbgen::CompressedTopoTimezones) -> Self {
-        Self::from_compressed_topo_with_options(tzs, FinderOptions::default())
+    pub fn get_tz_names(&self, lng: f64, lat: f64) -> Vec<&str> {
+        with_core!(self, core => core.get_tz_names(lng, lat))
     }
 
-    /// Create a Finder from `CompressedTopoTimezones` with explicit polygon build options.
+    /// Like [`Finder::get_tz_name`], but when the point falls in no polygon,
+    /// such as a sliver between simplified polygons or a coordinate snapped
+    /// just offshore, fall back to the closest polygon within
+    /// `max_distance_km`.
+    ///
+    /// Returns the zone name and the great-circle distance to it in
+    /// kilometers (`0.0` when the point is inside a polygon), or `None` when
+    /// nothing lies within `max_distance_km`. The embedded grid index limits
+    /// the search to cells near the point.
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// assert_eq!(
+    ///     Some(("Asia/Shanghai", 0.0)),
+    ///     finder.get_tz_name_nearest(116.3883, 39.9289, 10.0)
+    /// );
+    /// ```
     #[must_use]
-    pub fn from_compressed_topo_with_options(
-        tzs: pbgen::CompressedTopoTimezones,
-        options: FinderOptions,
-    ) -> Self {
-        Self::from_compressed_topo_with_polygon_options(
-            tzs,
-            options.to_polygon_build_options(),
-            options.i32_raycast_mode(),
-        )
+    pub fn get_tz_name_nearest(
+        &self,
+        lng: f64,
+        lat: f64,
+        max_distance_km: f64,
+    ) -> Option<(&str, f64)> {
+        with_core!(self, core => core.get_tz_name_nearest(lng, lat, max_distance_km))
     }
 
-    /// `from_pb` is used when you can use your own timezone data, as long as
-    /// it's compatible with Proto's desc.
+    /// Like [`Finder::get_tz_name`], but also reports how the answer was
+    /// found and every zone that claims the point.
     ///
-    /// # Arguments
+    /// ```rust
+    /// use tzf_rs::{Finder, LookupSource};
     ///
-    /// * `tzs` - Timezones data.
+    /// let finder = Finder::new();
+    /// let result = finder.lookup(7.5, 54.5);
+    /// assert_eq!(LookupSource::Raycast, result.source);
+    /// assert!(result.on_shared_edge);
+    /// assert_eq!(["Etc/GMT-1", "Etc/GMT"], result.candidates[..]);
+    /// ```
+    #[must_use]
+    pub fn lookup(&self, lng: f64, lat: f64) -> LookupResult<'_> {
+        with_core!(self, core => core.lookup(lng, lat))
+    }
+
+    /// Distance from the point to the nearest edge of the timezone polygon
+    /// containing it, and the zone across that edge.
     ///
-    /// # Returns
+    /// Useful for confidence scoring: a GPS fix a few meters from a border
+    /// may belong to either side. Returns `None` when the point falls in no
+    /// polygon.
     ///
-    /// * `Finder` - A Finder instance.
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let border = finder.distance_to_border(7.4999, 54.5).unwrap();
+    /// assert_eq!("Etc/GMT", border.name);
+    /// assert_eq!("Etc/GMT-1", border.across);
+    /// assert!(border.distance_m < 20.0);
+    /// ```
     #[must_use]
-    pub fn from_pb(tzs: pbgen::Timezones) -> Self {
-        Self::from_pb_with_options(tzs, FinderOptions::default())
+    pub fn distance_to_border(&self, lng: f64, lat: f64) -> Option<BorderDistance<'_>> {
+        with_core!(self, core => core.distance_to_border(lng, lat))
     }
 
-    /// Create a finder from protobuf data with explicit polygon build options.
-    #[must_use]
-    pub fn from_pb_with_options(tzs: pbgen::Timezones, options: FinderOptions) -> Self {
-        Self::from_pb_with_polygon_options(tzs, options.to_polygon_build_options())
+    /// Whether a polygon edge passes within `max_m` meters of the point.
+    fn near_border(&self, lng: f64, lat: f64, max_m: f64) -> bool {
+        with_core!(self, core => core.near_border(lng, lat, max_m / 1000.0))
     }
 
-    /// Example:
+    /// Like [`Finder::get_tz_name`], but returns a compact [`TimezoneId`]
+    /// instead of a string, for hot paths that group or count by zone.
     ///
     /// ```rust
     /// use tzf_rs::Finder;
     ///
     /// let finder = Finder::new();
-    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// let id = finder.get_tz_id(116.3883, 39.9289).unwrap();
+    /// assert_eq!(Some("Asia/Shanghai"), finder.name(id));
     /// ```
     #[must_use]
-    pub fn get_tz_name(&self, lng: f64, lat: f64) -> &str {
-        with_core!(self, core => core.get_tz_name(lng, lat))
+    pub fn get_tz_id(&self, lng: f64, lat: f64) -> Option<TimezoneId> {
+        with_core!(self, core => core.get_tz_id(lng, lat))
     }
 
+    /// Resolves a [`TimezoneId`] issued by this finder back to its name.
+    #[must_use]
+    pub fn name(&self, id: TimezoneId) -> Option<&str> {
+        with_core!(self, core => core.name(id))
+    }
+
+    /// The [`TimezoneId`] of a zone name, or `None` if the data has no such
+    /// zone.
+    ///
     /// ```rust
     /// use tzf_rs::Finder;
+    ///
     /// let finder = Finder::new();
-    /// println!("{:?}", finder.get_tz_names(116.3883, 39.9289));
+    /// let id = finder.id_of("Asia/Tokyo").unwrap();
+    /// assert_eq!(Some(id), finder.get_tz_id(139.4382, 36.4432));
+    /// assert_eq!(None, finder.id_of("Mars/Olympus_Mons"));
     /// ```
     #[must_use]
-    pub fn get_tz_names(&self, lng: f64, lat: f64) -> Vec<&str> {
-        with_core!(self, core => core.get_tz_names(lng, lat))
+    pub fn id_of(&self, name: &str) -> Option<TimezoneId> {
+        with_core!(self, core => core.id_of(name))
+    }
+
+    /// The name table this finder's [`TimezoneId`]s index, to share with a
+    /// [`FuzzyFinder`] through [`FuzzyFinder::with_names`].
+    #[must_use]
+    pub fn names(&self) -> &TimezoneNames {
+        with_core!(self, core => &core.names)
     }
 
     /// Example:
@@ -508,13 +1625,36 @@ impl Finder {
     /// let finder = Finder::new();
     /// ```
     #[must_use]
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Fallible counterpart of [`Finder::new`].
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::try_new().unwrap();
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the embedded data fails to decode or validate.
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
+    pub fn try_new() -> Result<Self, Error> {
+        let tzs = pbgen::CompressedTopoTimezones::decode(&load_topology_compress_topo()[..])?;
+        Self::try_from_compressed_topo(tzs)
+    }
+
     /// Convert the Finder's data to GeoJSON format.
     ///
     /// Returns a `BoundaryFile` (FeatureCollection) containing all timezone polygons.
+    /// Zones crossing the antimeridian come out as separate polygons on
+    /// either side; see [`BoundaryFile::with_antimeridian`] for other forms.
     ///
     /// # Example
     ///
@@ -563,34 +1703,26 @@ impl Finder {
 }
 
 #[cfg(feature = "export-geojson")]
-impl<T: CoordStorage> FinderCore<T> {
+impl<Z: Zone> FinderCore<Z> {
     /// Helper method to convert an Item to a FeatureItem.
-    fn item_to_feature(&self, item: &Item<T>) -> FeatureItem {
+    fn item_to_feature(&self, item: &Z) -> FeatureItem {
         // Convert internal Item to pbgen::Timezone format
+        let point = |&(lng, lat): &(f64, f64)| pbgen::Point {
+            lng: lng as f32,
+            lat: lat as f32,
+        };
         let mut pbpolys = Vec::new();
-        for poly in &item.polys {
-            // Storage space → degrees; `scale` is 1.0 for float storage.
-            let scale = poly.scale();
+        for rings in item.polygons() {
+            let mut rings = rings.iter();
             let mut pbpoly = pbgen::Polygon {
-                points: Vec::new(),
+                points: rings
+                    .next()
+                    .map_or_else(Vec::new, |ring| ring.iter().map(point).collect()),
                 holes: Vec::new(),
             };
-
-            pbpoly
-                .points
-                .extend(poly.exterior().iter().map(|point| pbgen::Point {
-                    lng: (point.x.to_f64() / scale) as f32,
-                    lat: (point.y.to_f64() / scale) as f32,
-                }));
-            for hole in poly.holes() {
+            for hole in rings {
                 pbpoly.holes.push(pbgen::Polygon {
-                    points: hole
-                        .iter()
-                        .map(|point| pbgen::Point {
-                            lng: (point.x.to_f64() / scale) as f32,
-                            lat: (point.y.to_f64() / scale) as f32,
-                        })
-                        .collect(),
+                    points: hole.iter().map(point).collect(),
                     holes: Vec::new(),
                 });
             }
@@ -600,7 +1732,7 @@ impl<T: CoordStorage> FinderCore<T> {
 
         let pbtz = pbgen::Timezone {
             polygons: pbpolys,
-            name: item.name.clone(),
+            name: item.name().to_string(),
         };
 
         revert_item(&pbtz)
@@ -625,7 +1757,7 @@ impl<T: CoordStorage> FinderCore<T> {
             features: Vec::new(),
         };
         for item in &self.all {
-            if item.name == timezone_name {
+            if item.name() == timezone_name {
                 output.features.push(self.item_to_feature(item));
             }
         }
@@ -647,6 +1779,8 @@ impl<T: CoordStorage> FinderCore<T> {
 ///
 /// let finder = Finder::default();
 /// ```
+#[cfg(any(feature = "bundled", feature = "full"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
 impl Default for Finder {
     fn default() -> Self {
         let file_bytes = load_topology_compress_topo();
@@ -656,11 +1790,29 @@ impl Default for Finder {
     }
 }
 
+/// Validate a query coordinate for the `try_get_tz_name*` methods, wrapping
+/// out-of-range longitudes into `[-180, 180)` when `wrap_lng` is set.
+fn check_coordinates(lng: f64, lat: f64, wrap_lng: bool) -> Result<(f64, f64), Error> {
+    if !(-90.0..=90.0).contains(&lat) {
+        return Err(Error::InvalidLatitude(lat));
+    }
+    if (-180.0..=180.0).contains(&lng) {
+        return Ok((lng, lat));
+    }
+    if wrap_lng && lng.is_finite() {
+        return Ok(((lng + 180.0).rem_euclid(360.0) - 180.0, lat));
+    }
+    Err(Error::InvalidLongitude(lng))
+}
+
 /// deg2num is used to convert longitude, latitude to [Slippy map tilenames]
 /// under specific zoom level.
 ///
 /// [Slippy map tilenames]: https://wiki.openstreetmap.org/wiki/Slippy_map_tilenames
 ///
+/// Longitude 180 maps to the same column as -180; other longitudes are not
+/// wrapped.
+///
 /// Example:
 ///
 /// ```rust
@@ -677,6 +1829,9 @@ impl Default for Finder {
 pub fn deg2num(lng: f64, lat: f64, zoom: i64) -> (i64, i64) {
     let n = (1i64 << zoom) as f64;
     let lat_rad = lat.to_radians();
+    // 180° is the same meridian as -180°, so it shares the first column
+    // instead of indexing one past the last.
+    let lng = if lng == 180.0 { -180.0 } else { lng };
     let xtile = (lng / 360.0 + 0.5) * n;
     let ytile = (1.0 - lat_rad.tan().asinh() / PI) / 2.0 * n;
 
@@ -857,10 +2012,16 @@ pub struct FuzzyFinder {
     // Sorted timezone name table; tiles reference names by index, so index
     // order matches lexical order.
     names: Vec<String>,
+    // The table `TimezoneId`s index, and tile name index → id in it. The
+    // preindex names themselves until `with_names` swaps in another table.
+    id_names: TimezoneNames,
+    ids: Vec<Option<TimezoneId>>,
     all: HashMap<u64, TileEntry>, // K: packed <x,y,z>
     data_version: String,
 }
 
+#[cfg(any(feature = "bundled", feature = "full"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
 impl Default for FuzzyFinder {
     /// Creates a new, empty `FuzzyFinder`.
     ///
@@ -881,6 +2042,16 @@ impl FuzzyFinder {
     /// Panics if the input contains more than `u16::MAX` distinct timezone names.
     #[must_use]
     pub fn from_pb(tzs: pbgen::PreindexTimezones) -> Self {
+        Self::try_from_pb(tzs).expect("more than u16::MAX timezone names")
+    }
+
+    /// Fallible counterpart of [`FuzzyFinder::from_pb`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::TooManyTimezones`] if the input contains more than
+    /// `u16::MAX` distinct timezone names.
+    pub fn try_from_pb(tzs: pbgen::PreindexTimezones) -> Result<Self, Error> {
         // First pass: build a sorted name table so indices compare in the
         // same order as the names themselves.
         let mut names: Vec<String> = tzs.keys.iter().map(|item| item.name.clone()).collect();
@@ -889,13 +2060,9 @@ impl FuzzyFinder {
         let name_idx: HashMap<&str, u16> = names
             .iter()
             .enumerate()
-            .map(|(i, name)| {
-                (
-                    name.as_str(),
-                    u16::try_from(i).expect("more than u16::MAX timezone names"),
-                )
-            })
-            .collect();
+            .map(|(i, name)| u16::try_from(i).map(|i| (name.as_str(), i)))
+            .collect::<Result<_, _>>()
+            .map_err(|_| Error::TooManyTimezones(names.len()))?;
 
         // Second pass: populate tiles with name indices.
         let mut all: HashMap<u64, TileEntry> = HashMap::new();
@@ -915,13 +2082,71 @@ impl FuzzyFinder {
             }
         }
 
-        Self {
+        let id_names = TimezoneNames::new(names.iter().cloned());
+        let ids = names.iter().map(|name| id_names.id_of(name)).collect();
+        Ok(Self {
             min_zoom: i64::from(tzs.agg_zoom),
             max_zoom: i64::from(tzs.idx_zoom),
             names,
+            id_names,
+            ids,
             all,
             data_version: tzs.version,
-        }
+        })
+    }
+
+    /// Issue [`TimezoneId`]s from `names` instead of the preindex's own
+    /// names, so they agree with the [`Finder`] owning the table. Preindex
+    /// zones missing from `names` get no id.
+    ///
+    /// ```rust
+    /// use tzf_rs::{Finder, FuzzyFinder};
+    ///
+    /// let finder = Finder::new();
+    /// let fuzzy = FuzzyFinder::new().with_names(finder.names());
+    /// assert_eq!(fuzzy.id_of("Asia/Shanghai"), finder.id_of("Asia/Shanghai"));
+    /// ```
+    #[must_use]
+    pub fn with_names(mut self, names: &TimezoneNames) -> Self {
+        self.ids = self.names.iter().map(|name| names.id_of(name)).collect();
+        self.id_names = names.clone();
+        self
+    }
+
+    /// Like [`FuzzyFinder::get_tz_name`], but returns a compact
+    /// [`TimezoneId`]; see [`FuzzyFinder::with_names`] for the table it
+    /// indexes.
+    ///
+    /// ```rust
+    /// use tzf_rs::FuzzyFinder;
+    ///
+    /// let finder = FuzzyFinder::new();
+    /// let id = finder.get_tz_id(139.4382, 36.4432).unwrap();
+    /// assert_eq!(Some("Asia/Tokyo"), finder.name(id));
+    /// ```
+    #[must_use]
+    pub fn get_tz_id(&self, lng: f64, lat: f64) -> Option<TimezoneId> {
+        let (_, indices) = self.lookup_tile(lng, lat)?;
+        self.ids[usize::from(*indices.first()?)]
+    }
+
+    /// Resolves a [`TimezoneId`] issued by this finder back to its name.
+    #[must_use]
+    pub fn name(&self, id: TimezoneId) -> Option<&str> {
+        self.id_names.name(id)
+    }
+
+    /// The [`TimezoneId`] of a zone name, or `None` if the id table has no
+    /// such zone.
+    #[must_use]
+    pub fn id_of(&self, name: &str) -> Option<TimezoneId> {
+        self.id_names.id_of(name)
+    }
+
+    /// The name table this finder's [`TimezoneId`]s index.
+    #[must_use]
+    pub fn names(&self) -> &TimezoneNames {
+        &self.id_names
     }
 
     /// Retrieves the time zone name for the given longitude and latitude.
@@ -940,29 +2165,66 @@ impl FuzzyFinder {
     /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
     /// ```
     ///
-    /// # Panics
-    ///
-    /// - Panics if `lng` or `lat` is out of range.
-    /// - Panics if `lng` or `lat` is not a number.
+    /// Coordinates are not checked: NaN or out-of-range input never panics
+    /// but may answer `""` or the zone of an unrelated tile. Use
+    /// [`FuzzyFinder::try_get_tz_name`] for untrusted input.
     #[must_use]
     pub fn get_tz_name(&self, lng: f64, lat: f64) -> &str {
+        self.lookup_tile(lng, lat)
+            .and_then(|(_, indices)| indices.first())
+            .map_or("", |&idx| &self.names[usize::from(idx)])
+    }
+
+    /// Checked variant of [`FuzzyFinder::get_tz_name`].
+    ///
+    /// ```rust
+    /// use tzf_rs::FuzzyFinder;
+    ///
+    /// let finder = FuzzyFinder::new();
+    /// assert_eq!("Asia/Shanghai", finder.try_get_tz_name(116.3883, 39.9289).unwrap());
+    /// assert!(finder.try_get_tz_name(116.3883, 91.0).is_err());
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLongitude`] or [`Error::InvalidLatitude`] if a
+    /// coordinate is NaN, infinite or out of range.
+    pub fn try_get_tz_name(&self, lng: f64, lat: f64) -> Result<&str, Error> {
+        let (lng, lat) = check_coordinates(lng, lat, false)?;
+        Ok(self.get_tz_name(lng, lat))
+    }
+
+    /// Like [`FuzzyFinder::try_get_tz_name`], but finite longitudes outside
+    /// `[-180, 180]` are wrapped instead of rejected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLongitude`] or [`Error::InvalidLatitude`] if a
+    /// coordinate is NaN or infinite, or the latitude is out of range.
+    pub fn try_get_tz_name_wrapped(&self, lng: f64, lat: f64) -> Result<&str, Error> {
+        let (lng, lat) = check_coordinates(lng, lat, true)?;
+        Ok(self.get_tz_name(lng, lat))
+    }
+
+    /// The coarsest preindex tile covering the point, with its zoom level and
+    /// name indices.
+    fn lookup_tile(&self, lng: f64, lat: f64) -> Option<(i64, &[u16])> {
         if self.max_zoom <= self.min_zoom {
-            return "";
+            return None;
         }
         // Compute tile coords once at the highest zoom, then right-shift for coarser levels.
         let top_zoom = self.max_zoom - 1;
         let (high_x, high_y) = deg2num(lng, lat, top_zoom);
         for zoom in self.min_zoom..self.max_zoom {
             let shift = (top_zoom - zoom) as u32;
-            if let Some(&idx) = self
-                .all
-                .get(&pack_tile_key(high_x >> shift, high_y >> shift, zoom))
-                .and_then(|entry| entry.indices().first())
+            if let Some(entry) =
+                self.all
+                    .get(&pack_tile_key(high_x >> shift, high_y >> shift, zoom))
             {
-                return &self.names[usize::from(idx)];
+                return Some((zoom, entry.indices()));
             }
         }
-        ""
+        None
     }
 
     pub fn get_tz_names(&self, lng: f64, lat: f64) -> Vec<&str> {
@@ -1013,10 +2275,30 @@ impl FuzzyFinder {
     /// let finder = FuzzyFinder::default();
     /// ```
     #[must_use]
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Fallible counterpart of [`FuzzyFinder::new`].
+    ///
+    /// ```rust
+    /// use tzf_rs::FuzzyFinder;
+    ///
+    /// let finder = FuzzyFinder::try_new().unwrap();
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the embedded preindex fails to decode.
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
+    pub fn try_new() -> Result<Self, Error> {
+        Self::try_from_pb(pbgen::PreindexTimezones::decode(&load_preindex()[..])?)
+    }
+
     /// Convert the FuzzyFinder's preindex data to GeoJSON format.
     ///
     /// This method generates polygons for each tile in the preindex,
@@ -1176,6 +2458,8 @@ pub struct DefaultFinder {
     pub fuzzy_finder: FuzzyFinder,
 }
 
+#[cfg(any(feature = "bundled", feature = "full"))]
+#[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
 impl Default for DefaultFinder {
     /// Creates a new, empty `DefaultFinder`.
     ///
@@ -1191,7 +2475,7 @@ impl Default for DefaultFinder {
         let tzs = pbgen::CompressedTopoTimezones::try_from(topo_bytes).unwrap_or_default();
         let finder = Finder::from_compressed_topo_with_options(tzs, options);
 
-        let fuzzy_finder = FuzzyFinder::default();
+        let fuzzy_finder = FuzzyFinder::default().with_names(finder.names());
 
         Self {
             finder,
@@ -1205,13 +2489,72 @@ impl DefaultFinder {
     ///
     /// The selected options are applied to the internal `Finder`.
     #[must_use]
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
     pub fn new_with_options(options: FinderOptions) -> Self {
         let topo_bytes = load_topology_compress_topo();
         let tzs = pbgen::CompressedTopoTimezones::try_from(topo_bytes).unwrap_or_default();
+        let finder = Finder::from_compressed_topo_with_options(tzs, options);
+        let fuzzy_finder = FuzzyFinder::default().with_names(finder.names());
         Self {
-            finder: Finder::from_compressed_topo_with_options(tzs, options),
-            fuzzy_finder: FuzzyFinder::default(),
+            finder,
+            fuzzy_finder,
+        }
+    }
+
+    /// Fallible counterpart of [`DefaultFinder::new`].
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    /// let finder = DefaultFinder::try_new().unwrap();
+    /// assert_eq!("Asia/Shanghai", finder.get_tz_name(116.3883, 39.9289));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either embedded dataset fails to decode or
+    /// validate, or if the two datasets come from different releases.
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
+    pub fn try_new() -> Result<Self, Error> {
+        Self::try_new_with_options(FinderOptions::y_stripes())
+    }
+
+    /// Fallible counterpart of [`DefaultFinder::new_with_options`].
+    ///
+    /// # Errors
+    ///
+    /// See [`DefaultFinder::try_new`].
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
+    pub fn try_new_with_options(options: FinderOptions) -> Result<Self, Error> {
+        let tzs = pbgen::CompressedTopoTimezones::decode(&load_topology_compress_topo()[..])?;
+        Self::try_from_parts(
+            Finder::try_from_compressed_topo_with_options(tzs, options)?,
+            FuzzyFinder::try_new()?,
+        )
+    }
+
+    /// Pair a [`Finder`] with a [`FuzzyFinder`], rejecting data from
+    /// different releases: a stale preindex would answer with zones the
+    /// polygons no longer agree with. Data without timezones is rejected
+    /// too: it would answer `""` everywhere. The preindex issues ids from
+    /// the `Finder`'s name table.
+    fn try_from_parts(finder: Finder, fuzzy_finder: FuzzyFinder) -> Result<Self, Error> {
+        if finder.timezonenames().is_empty() {
+            return Err(Error::EmptyDataset);
+        }
+        if finder.data_version() != fuzzy_finder.data_version() {
+            return Err(Error::VersionMismatch {
+                finder: finder.data_version().to_string(),
+                fuzzy_finder: fuzzy_finder.data_version().to_string(),
+            });
         }
+        let fuzzy_finder = fuzzy_finder.with_names(finder.names());
+        Ok(Self {
+            finder,
+            fuzzy_finder,
+        })
     }
 
     /// Use lossless data to create a new `DefaultFinder`.
@@ -1249,12 +2592,40 @@ impl DefaultFinder {
     pub fn new_full_with_options(options: FinderOptions) -> Self {
         let tzs =
             pbgen::CompressedTopoTimezones::try_from(load_compress_topo()).unwrap_or_default();
+        let finder = Finder::from_compressed_topo_with_options(tzs, options);
+        let fuzzy_finder = FuzzyFinder::default().with_names(finder.names());
         Self {
-            finder: Finder::from_compressed_topo_with_options(tzs, options),
-            fuzzy_finder: FuzzyFinder::default(),
+            finder,
+            fuzzy_finder,
         }
     }
 
+    /// Fallible counterpart of [`DefaultFinder::new_full`].
+    ///
+    /// # Errors
+    ///
+    /// See [`DefaultFinder::try_new`].
+    #[cfg(feature = "full")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "full")))]
+    pub fn try_new_full() -> Result<Self, Error> {
+        Self::try_new_full_with_options(FinderOptions::y_stripes())
+    }
+
+    /// Fallible counterpart of [`DefaultFinder::new_full_with_options`].
+    ///
+    /// # Errors
+    ///
+    /// See [`DefaultFinder::try_new`].
+    #[cfg(feature = "full")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "full")))]
+    pub fn try_new_full_with_options(options: FinderOptions) -> Result<Self, Error> {
+        let tzs = pbgen::CompressedTopoTimezones::decode(&load_compress_topo()[..])?;
+        Self::try_from_parts(
+            Finder::try_from_compressed_topo_with_options(tzs, options)?,
+            FuzzyFinder::try_new()?,
+        )
+    }
+
     /// ```rust
     /// use tzf_rs::DefaultFinder;
     /// let finder = DefaultFinder::new();
@@ -1269,6 +2640,94 @@ impl DefaultFinder {
         self.finder.get_tz_name(lng, lat)
     }
 
+    /// Checked variant of [`DefaultFinder::get_tz_name`].
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, Error};
+    ///
+    /// let finder = DefaultFinder::new();
+    /// assert_eq!("Asia/Shanghai", finder.try_get_tz_name(116.3883, 39.9289).unwrap());
+    /// assert!(matches!(
+    ///     finder.try_get_tz_name(1e9, 39.9289),
+    ///     Err(Error::InvalidLongitude(_))
+    /// ));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLongitude`] or [`Error::InvalidLatitude`] if a
+    /// coordinate is NaN, infinite or out of range.
+    pub fn try_get_tz_name(&self, lng: f64, lat: f64) -> Result<&str, Error> {
+        let (lng, lat) = check_coordinates(lng, lat, false)?;
+        Ok(self.get_tz_name(lng, lat))
+    }
+
+    /// Like [`DefaultFinder::try_get_tz_name`], but finite longitudes outside
+    /// `[-180, 180]` are wrapped instead of rejected.
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// assert_eq!(
+    ///     finder.try_get_tz_name_wrapped(116.3883 + 360.0, 39.9289).unwrap(),
+    ///     "Asia/Shanghai"
+    /// );
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidLongitude`] or [`Error::InvalidLatitude`] if a
+    /// coordinate is NaN or infinite, or the latitude is out of range.
+    pub fn try_get_tz_name_wrapped(&self, lng: f64, lat: f64) -> Result<&str, Error> {
+        let (lng, lat) = check_coordinates(lng, lat, true)?;
+        Ok(self.get_tz_name(lng, lat))
+    }
+
+    /// Like [`DefaultFinder::get_tz_name`], but also reports which tier
+    /// answered: a [`FuzzyFinder`] preindex tile, the grid single-candidate
+    /// shortcut, or a polygon raycast.
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, LookupSource};
+    ///
+    /// let finder = DefaultFinder::new();
+    /// let result = finder.lookup(116.3883, 39.9289);
+    /// assert_eq!("Asia/Shanghai", result.name);
+    /// assert!(matches!(result.source, LookupSource::Preindex { .. }));
+    /// ```
+    #[must_use]
+    pub fn lookup(&self, lng: f64, lat: f64) -> LookupResult<'_> {
+        if let Some(result) = self.fuzzy_finder.lookup(lng, lat) {
+            return result;
+        }
+        self.finder.lookup(lng, lat)
+    }
+
+    /// Nearest-timezone fallback; see [`Finder::get_tz_name_nearest`].
+    ///
+    /// A [`FuzzyFinder`] hit is returned with distance `0.0`.
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    /// let finder = DefaultFinder::new();
+    /// let (name, distance_km) = finder.get_tz_name_nearest(116.3883, 39.9289, 10.0).unwrap();
+    /// assert_eq!(("Asia/Shanghai", 0.0), (name, distance_km));
+    /// ```
+    #[must_use]
+    pub fn get_tz_name_nearest(
+        &self,
+        lng: f64,
+        lat: f64,
+        max_distance_km: f64,
+    ) -> Option<(&str, f64)> {
+        let fuzzy = self.fuzzy_finder.get_tz_name(lng, lat);
+        if !fuzzy.is_empty() {
+            return Some((fuzzy, 0.0));
+        }
+        self.finder.get_tz_name_nearest(lng, lat, max_distance_km)
+    }
+
     /// ```rust
     /// use tzf_rs::DefaultFinder;
     /// let finder = DefaultFinder::new();
@@ -1279,6 +2738,50 @@ impl DefaultFinder {
         self.finder.get_tz_names(lng, lat)
     }
 
+    /// Distance to the border of the containing zone; see
+    /// [`Finder::distance_to_border`].
+    #[must_use]
+    pub fn distance_to_border(&self, lng: f64, lat: f64) -> Option<BorderDistance<'_>> {
+        self.finder.distance_to_border(lng, lat)
+    }
+
+    /// Like [`DefaultFinder::get_tz_name`], but returns a compact
+    /// [`TimezoneId`].
+    ///
+    /// Preindex hits are mapped into the [`Finder`]'s name table, so ids are
+    /// comparable whichever tier answered; resolve them with
+    /// [`DefaultFinder::name`].
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// let beijing = finder.get_tz_id(116.3883, 39.9289).unwrap();
+    /// let shanghai = finder.get_tz_id(121.4737, 31.2304).unwrap();
+    /// assert_eq!(beijing, shanghai);
+    /// assert_eq!(Some("Asia/Shanghai"), finder.name(beijing));
+    /// ```
+    #[must_use]
+    pub fn get_tz_id(&self, lng: f64, lat: f64) -> Option<TimezoneId> {
+        let fuzzy = self.fuzzy_finder.get_tz_name(lng, lat);
+        if !fuzzy.is_empty() {
+            return self.finder.id_of(fuzzy);
+        }
+        self.finder.get_tz_id(lng, lat)
+    }
+
+    /// Resolves a [`TimezoneId`] issued by this finder back to its name.
+    #[must_use]
+    pub fn name(&self, id: TimezoneId) -> Option<&str> {
+        self.finder.name(id)
+    }
+
+    /// The [`TimezoneId`] of a zone name; see [`Finder::id_of`].
+    #[must_use]
+    pub fn id_of(&self, name: &str) -> Option<TimezoneId> {
+        self.finder.id_of(name)
+    }
+
     /// Returns all time zone names as a `Vec<&str>`.
     ///
     /// ```rust
@@ -1313,6 +2816,8 @@ impl DefaultFinder {
     /// let finder = DefaultFinder::new();
     /// ```
     #[must_use]
+    #[cfg(any(feature = "bundled", feature = "full"))]
+    #[cfg_attr(docsrs, doc(cfg(any(feature = "bundled", feature = "full"))))]
     pub fn new() -> Self {
         Self::default()
     }
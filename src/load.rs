@@ -0,0 +1,144 @@
+//! Loading timezone data from files at runtime instead of the embedded
+//! `tzf-dist` data.
+
+use crate::{DefaultFinder, Error, Finder, FinderOptions, FuzzyFinder, pbgen};
+use prost::Message;
+use std::path::Path;
+
+pub(crate) fn io_error(path: &Path) -> impl FnOnce(std::io::Error) -> Error + '_ {
+    |source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    }
+}
+
+impl DefaultFinder {
+    /// Builds a `DefaultFinder` from encoded data held in memory: a
+    /// `CompressedTopoTimezones` payload (as in
+    /// `combined-with-oceans.reduce.topology.compress.topo.bin`) and a
+    /// `PreindexTimezones` payload (as in `combined-with-oceans.preindex.bin`).
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, Error};
+    ///
+    /// // Empty payloads decode to data without a single timezone.
+    /// assert!(matches!(
+    ///     DefaultFinder::from_bytes(&[], &[]),
+    ///     Err(Error::EmptyDataset)
+    /// ));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if either payload fails to decode or validate, if
+    /// the topo data holds no timezones ([`Error::EmptyDataset`]), or if the
+    /// two come from different releases.
+    pub fn from_bytes(topo: &[u8], preindex: &[u8]) -> Result<Self, Error> {
+        Self::from_bytes_with_options(topo, preindex, FinderOptions::y_stripes())
+    }
+
+    /// Like [`DefaultFinder::from_bytes`], with explicit polygon build options.
+    ///
+    /// # Errors
+    ///
+    /// See [`DefaultFinder::from_bytes`].
+    pub fn from_bytes_with_options(
+        topo: &[u8],
+        preindex: &[u8],
+        options: FinderOptions,
+    ) -> Result<Self, Error> {
+        let tzs = pbgen::CompressedTopoTimezones::decode(topo)?;
+        Self::try_from_parts(
+            Finder::try_from_compressed_topo_with_options(tzs, options)?,
+            FuzzyFinder::try_from_pb(pbgen::PreindexTimezones::decode(preindex)?)?,
+        )
+    }
+
+    /// Reads the topo and preindex data files from disk; see
+    /// [`DefaultFinder::from_bytes`].
+    ///
+    /// This works without the `bundled` feature, so a binary built with
+    /// `default-features = false` can ship dataset updates separately.
+    ///
+    /// ```rust,no_run
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::from_paths(
+    ///     "data/combined-with-oceans.reduce.topology.compress.topo.bin",
+    ///     "data/combined-with-oceans.preindex.bin",
+    /// )
+    /// .unwrap();
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if a file cannot be read, otherwise see
+    /// [`DefaultFinder::from_bytes`].
+    pub fn from_paths(
+        topo_path: impl AsRef<Path>,
+        preindex_path: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        Self::from_paths_with_options(topo_path, preindex_path, FinderOptions::y_stripes())
+    }
+
+    /// Like [`DefaultFinder::from_paths`], with explicit polygon build options.
+    ///
+    /// # Errors
+    ///
+    /// See [`DefaultFinder::from_paths`].
+    pub fn from_paths_with_options(
+        topo_path: impl AsRef<Path>,
+        preindex_path: impl AsRef<Path>,
+        options: FinderOptions,
+    ) -> Result<Self, Error> {
+        let (topo_path, preindex_path) = (topo_path.as_ref(), preindex_path.as_ref());
+        let topo = std::fs::read(topo_path).map_err(io_error(topo_path))?;
+        let preindex = std::fs::read(preindex_path).map_err(io_error(preindex_path))?;
+        Self::from_bytes_with_options(&topo, &preindex, options)
+    }
+
+    /// Like [`DefaultFinder::from_paths`], but decodes straight from
+    /// memory-mapped files instead of reading them into heap buffers first,
+    /// which avoids holding a second copy of the encoded data while the
+    /// finder is built. The maps are released before this returns.
+    ///
+    /// # Errors
+    ///
+    /// See [`DefaultFinder::from_paths`].
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub fn from_mmap_paths(
+        topo_path: impl AsRef<Path>,
+        preindex_path: impl AsRef<Path>,
+    ) -> Result<Self, Error> {
+        Self::from_mmap_paths_with_options(topo_path, preindex_path, FinderOptions::y_stripes())
+    }
+
+    /// Like [`DefaultFinder::from_mmap_paths`], with explicit polygon build
+    /// options.
+    ///
+    /// # Errors
+    ///
+    /// See [`DefaultFinder::from_paths`].
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub fn from_mmap_paths_with_options(
+        topo_path: impl AsRef<Path>,
+        preindex_path: impl AsRef<Path>,
+        options: FinderOptions,
+    ) -> Result<Self, Error> {
+        let (topo_path, preindex_path) = (topo_path.as_ref(), preindex_path.as_ref());
+        let topo = map_file(topo_path)?;
+        let preindex = map_file(preindex_path)?;
+        Self::from_bytes_with_options(&topo, &preindex, options)
+    }
+}
+
+#[cfg(feature = "mmap")]
+fn map_file(path: &Path) -> Result<memmap2::Mmap, Error> {
+    let file = std::fs::File::open(path).map_err(io_error(path))?;
+    // SAFETY: the map only lives for the duration of decoding, which copies
+    // everything it keeps. Truncating the file concurrently can still fault;
+    // callers own the data directory, as with any mmap.
+    unsafe { memmap2::Mmap::map(&file) }.map_err(io_error(path))
+}
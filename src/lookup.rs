@@ -0,0 +1,91 @@
+//! Query results that record which finder tier answered.
+
+use crate::{Candidates, FinderCore, FuzzyFinder, Zone, on_antimeridian};
+
+/// Which tier of the lookup pipeline produced a [`LookupResult`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LookupSource {
+    /// A [`FuzzyFinder`] preindex tile at the given zoom level.
+    Preindex { zoom: i64 },
+    /// The grid cell lies entirely inside a single zone, so no raycast was
+    /// needed.
+    GridSingleCandidate,
+    /// The quadtree leaf subdividing the grid cell lies entirely inside a
+    /// single zone, so no raycast was needed.
+    QuadtreeInside,
+    /// Point-in-polygon tests against the candidate zones.
+    Raycast,
+    /// No tier found a zone.
+    NotFound,
+}
+
+/// A timezone lookup together with how it was answered.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LookupResult<'a> {
+    /// The zone [`get_tz_name`](crate::DefaultFinder::get_tz_name) would
+    /// return, or `""` when nothing was found.
+    pub name: &'a str,
+    /// The tier that answered.
+    pub source: LookupSource,
+    /// Whether more than one polygon claims the point, i.e. it lies on a
+    /// shared border. Always `false` for [`LookupSource::Preindex`],
+    /// [`LookupSource::GridSingleCandidate`] and
+    /// [`LookupSource::QuadtreeInside`], which never reach polygon edges.
+    pub on_shared_edge: bool,
+    /// Every zone claiming the point, `name` first: the zones of the
+    /// preindex tile, the zone covering the grid cell or quadtree leaf, or
+    /// every polygon containing the point.
+    pub candidates: Vec<&'a str>,
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    pub(crate) fn lookup(&self, lng: f64, lat: f64) -> LookupResult<'_> {
+        // On the antimeridian, both sides are raycast so their claims merge.
+        let inside = match self.candidates(lng, lat) {
+            _ if on_antimeridian(lng) => None,
+            Candidates::Inside(idx) => Some((idx, LookupSource::GridSingleCandidate)),
+            Candidates::InsideLeaf(idx) => Some((idx, LookupSource::QuadtreeInside)),
+            _ => None,
+        };
+        if let Some((idx, source)) = inside {
+            let name = self.all[idx].name();
+            return LookupResult {
+                name,
+                source,
+                on_shared_edge: false,
+                candidates: vec![name],
+            };
+        }
+
+        let candidates = self.get_tz_names(lng, lat);
+        LookupResult {
+            name: candidates.first().copied().unwrap_or(""),
+            source: if candidates.is_empty() {
+                LookupSource::NotFound
+            } else {
+                LookupSource::Raycast
+            },
+            on_shared_edge: candidates.len() > 1,
+            candidates,
+        }
+    }
+}
+
+impl FuzzyFinder {
+    /// The preindex tier of [`DefaultFinder::lookup`](crate::DefaultFinder::lookup);
+    /// `None` on a miss.
+    pub(crate) fn lookup(&self, lng: f64, lat: f64) -> Option<LookupResult<'_>> {
+        let (zoom, indices) = self.lookup_tile(lng, lat)?;
+        let candidates: Vec<&str> = indices
+            .iter()
+            .map(|&idx| self.names[usize::from(idx)].as_str())
+            .collect();
+        Some(LookupResult {
+            name: candidates.first().copied()?,
+            source: LookupSource::Preindex { zoom },
+            on_shared_edge: false,
+            candidates,
+        })
+    }
+}
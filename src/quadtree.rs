@@ -0,0 +1,248 @@
+//! Adaptive quadtree refinement of dense grid cells.
+//!
+//! Grid cells along busy borders hold many candidates, and every query there
+//! raycasts them in turn. The quadtree splits such cells into quadrants until
+//! each leaf holds at most a given number of candidates, and marks leaves
+//! lying entirely inside one zone so queries there skip the raycast.
+
+use crate::Zone;
+use crate::grid::{Grid, clip_segment};
+use geometry_rs::Point;
+use std::collections::HashMap;
+
+/// Deepest subdivision below a grid cell: a 1° cell splits down to 1/256°,
+/// a few hundred meters.
+const MAX_DEPTH: u8 = 8;
+
+type Edge = ((f64, f64), (f64, f64));
+
+/// A zone meeting a node: its edges touching the node's rectangle and
+/// whether it covers the rectangle entirely.
+struct NodeZone {
+    index: u32,
+    edges: Vec<Edge>,
+    covers: bool,
+}
+
+enum Node {
+    /// The four children start at this node index, in the order south-west,
+    /// south-east, north-west, north-east.
+    Split(u32),
+    Leaf {
+        candidates: Box<[u32]>,
+        /// The single candidate, when it covers the whole leaf.
+        inside: Option<u32>,
+    },
+}
+
+/// The leaf holding a query point.
+pub(crate) enum Leaf<'a> {
+    /// The leaf lies entirely inside this item.
+    Inside(usize),
+    /// Items that may contain the point; possibly none.
+    Candidates(&'a [u32]),
+}
+
+/// Subdivided grid cells; cells without a root use the grid as is.
+pub(crate) struct QuadTree {
+    roots: HashMap<(i16, i16), u32>,
+    nodes: Vec<Node>,
+}
+
+impl QuadTree {
+    /// Subdivide every grid cell with more than `max_candidates` candidates
+    /// until each leaf holds at most that many, or [`MAX_DEPTH`] is reached.
+    ///
+    /// Builds the polygons of lazy zones in dense cells, which the coverage
+    /// tests need.
+    pub(crate) fn build<Z: Zone>(all: &[Z], grid: &Grid, max_candidates: usize) -> Self {
+        let mut tree = Self {
+            roots: HashMap::new(),
+            nodes: Vec::new(),
+        };
+        // Dense cell → its candidates, each with the edges touching the cell.
+        let mut dense: HashMap<(i16, i16), Vec<NodeZone>> = HashMap::new();
+        // Item → the dense cells listing it, with its position there.
+        let mut uses: Vec<HashMap<(i16, i16), usize>> =
+            (0..all.len()).map(|_| HashMap::new()).collect();
+        for (&key, candidates) in &grid.cells {
+            if candidates.len() <= max_candidates {
+                continue;
+            }
+            for (pos, &index) in candidates.iter().enumerate() {
+                uses[index as usize].insert(key, pos);
+            }
+            let zones = candidates
+                .iter()
+                .map(|&index| NodeZone {
+                    index,
+                    edges: Vec::new(),
+                    covers: false,
+                })
+                .collect();
+            dense.insert(key, zones);
+        }
+        for (index, cells) in uses.iter().enumerate() {
+            if cells.is_empty() {
+                continue;
+            }
+            all[index].for_each_edge(|a, b| {
+                let (min, max) = (
+                    grid.key(a.0.min(b.0), a.1.min(b.1)),
+                    grid.key(a.0.max(b.0), a.1.max(b.1)),
+                );
+                for x in min.0..=max.0 {
+                    for y in min.1..=max.1 {
+                        if let Some(&pos) = cells.get(&(x, y)) {
+                            dense.get_mut(&(x, y)).expect("dense cell")[pos]
+                                .edges
+                                .push((a, b));
+                        }
+                    }
+                }
+            });
+        }
+
+        for (key, zones) in dense {
+            let root = tree.reserve();
+            let node = tree.split(all, grid.cell_bounds(key), &zones, 0, max_candidates);
+            tree.nodes[root as usize] = node;
+            tree.roots.insert(key, root);
+        }
+        tree
+    }
+
+    /// Append a placeholder node, returning its index.
+    #[allow(clippy::cast_possible_truncation)]
+    fn reserve(&mut self) -> u32 {
+        self.nodes.push(Node::Leaf {
+            candidates: Box::new([]),
+            inside: None,
+        });
+        (self.nodes.len() - 1) as u32
+    }
+
+    fn node<Z: Zone>(
+        &mut self,
+        all: &[Z],
+        rect: [f64; 4],
+        zones: &[NodeZone],
+        depth: u8,
+        max_candidates: usize,
+    ) -> Node {
+        if zones.len() == 1 && zones[0].covers {
+            let index = zones[0].index;
+            return Node::Leaf {
+                candidates: Box::new([index]),
+                inside: Some(index),
+            };
+        }
+        if zones.len() <= max_candidates || depth == MAX_DEPTH {
+            return Node::Leaf {
+                candidates: zones.iter().map(|zone| zone.index).collect(),
+                inside: None,
+            };
+        }
+        self.split(all, rect, zones, depth, max_candidates)
+    }
+
+    /// Split `rect` into quadrants, classifying each zone against them.
+    fn split<Z: Zone>(
+        &mut self,
+        all: &[Z],
+        [min_x, min_y, max_x, max_y]: [f64; 4],
+        zones: &[NodeZone],
+        depth: u8,
+        max_candidates: usize,
+    ) -> Node {
+        let (mid_x, mid_y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+        let quadrants = [
+            [min_x, min_y, mid_x, mid_y],
+            [mid_x, min_y, max_x, mid_y],
+            [min_x, mid_y, mid_x, max_y],
+            [mid_x, mid_y, max_x, max_y],
+        ];
+        let first = self.reserve();
+        for _ in 1..4 {
+            self.reserve();
+        }
+        for (i, rect) in quadrants.into_iter().enumerate() {
+            let children: Vec<NodeZone> = zones
+                .iter()
+                .filter_map(|zone| classify(all, zone, rect))
+                .collect();
+            let node = self.node(all, rect, &children, depth + 1, max_candidates);
+            self.nodes[first as usize + i] = node;
+        }
+        Node::Split(first)
+    }
+
+    /// The leaf holding the point, `None` when its grid cell is not
+    /// subdivided.
+    pub(crate) fn leaf(&self, grid: &Grid, lng: f64, lat: f64) -> Option<Leaf<'_>> {
+        let key = grid.key(lng, lat);
+        let mut node = *self.roots.get(&key)?;
+        let [mut min_x, mut min_y, mut max_x, mut max_y] = grid.cell_bounds(key);
+        loop {
+            match &self.nodes[node as usize] {
+                Node::Split(first) => {
+                    let (mid_x, mid_y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+                    let east = lng >= mid_x;
+                    let north = lat >= mid_y;
+                    if east {
+                        min_x = mid_x;
+                    } else {
+                        max_x = mid_x;
+                    }
+                    if north {
+                        min_y = mid_y;
+                    } else {
+                        max_y = mid_y;
+                    }
+                    node = first + u32::from(east) + 2 * u32::from(north);
+                }
+                Node::Leaf {
+                    inside: Some(index),
+                    ..
+                } => return Some(Leaf::Inside(*index as usize)),
+                Node::Leaf { candidates, .. } => return Some(Leaf::Candidates(candidates)),
+            }
+        }
+    }
+}
+
+/// How `zone` meets the quadrant `rect`: `None` when it misses it,
+/// otherwise its edges there and whether it covers it.
+fn classify<Z: Zone>(all: &[Z], zone: &NodeZone, rect: [f64; 4]) -> Option<NodeZone> {
+    let mut crosses_interior = false;
+    let edges: Vec<Edge> = zone
+        .edges
+        .iter()
+        .filter(|&&(a, b)| match clip_segment(a, b, rect) {
+            Some(interior) => {
+                crosses_interior |= interior;
+                true
+            }
+            None => false,
+        })
+        .copied()
+        .collect();
+    if crosses_interior {
+        return Some(NodeZone {
+            index: zone.index,
+            edges,
+            covers: false,
+        });
+    }
+    // No edge enters the quadrant, so the zone holds all of it or none.
+    let center = Point {
+        x: (rect[0] + rect[2]) / 2.0,
+        y: (rect[1] + rect[3]) / 2.0,
+    };
+    let covers = all[zone.index as usize].contains_point(&center);
+    (covers || !edges.is_empty()).then_some(NodeZone {
+        index: zone.index,
+        edges,
+        covers,
+    })
+}
@@ -0,0 +1,415 @@
+//! Zones overlapping a query region, and how much of it each covers.
+//!
+//! The overlap area comes from Green's theorem: the boundary of `A ∩ B` is
+//! the part of `A`'s boundary inside `B` plus the part of `B`'s boundary
+//! inside `A`, so cutting each boundary where it crosses the other and
+//! summing the shoelace terms of the kept pieces gives the area without
+//! building the intersection polygon. Boundary pieces the two share count
+//! once where both run the same way (the interiors lie on the same side)
+//! and not at all where they run opposite ways.
+
+use crate::distance::for_each_ring_edge;
+use crate::{Finder, FinderCore, Zone};
+
+/// A zone overlapping a query region; see
+/// [`Finder::zones_intersecting_polygon_with_fractions`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegionZone<'a> {
+    /// Timezone name.
+    pub name: &'a str,
+    /// Share of the query region's area inside the zone, in `(0, 1]`.
+    pub fraction: f64,
+}
+
+type Pt = (f64, f64);
+
+/// Distance, in degrees, under which a point counts as lying on a segment:
+/// about 0.1 mm.
+const ON_EDGE_DEG: f64 = 1e-9;
+
+/// Shares below this are rounding noise along borders the query follows.
+const MIN_FRACTION: f64 = 1e-9;
+
+fn cross(a: Pt, b: Pt) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+fn sub(a: Pt, b: Pt) -> Pt {
+    (a.0 - b.0, a.1 - b.1)
+}
+
+fn lerp(a: Pt, b: Pt, t: f64) -> Pt {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// Lambert cylindrical equal-area projection, so that areas compare fairly
+/// across latitudes.
+fn project(p: Pt) -> Pt {
+    (p.0.to_radians(), p.1.to_radians().sin())
+}
+
+/// Twice the shoelace term of the edge `a`–`b`, in projected space.
+fn twice_area_term(a: Pt, b: Pt) -> f64 {
+    cross(project(a), project(b))
+}
+
+fn bboxes_overlap(a: [f64; 4], b: [f64; 4]) -> bool {
+    a[0] <= b[2] + ON_EDGE_DEG
+        && b[0] <= a[2] + ON_EDGE_DEG
+        && a[1] <= b[3] + ON_EDGE_DEG
+        && b[1] <= a[3] + ON_EDGE_DEG
+}
+
+fn edge_bbox(a: Pt, b: Pt) -> [f64; 4] {
+    [a.0.min(b.0), a.1.min(b.1), a.0.max(b.0), a.1.max(b.1)]
+}
+
+/// A polygon as oriented edges: exterior counterclockwise, holes clockwise,
+/// so the interior always lies to the left. Zone polygons keep only the
+/// edges a query region reads; see [`Shape::push_ring`].
+struct Shape {
+    edges: Vec<(Pt, Pt)>,
+    bbox: [f64; 4],
+}
+
+impl Shape {
+    /// Rings in degrees, exterior first.
+    fn new(rings: impl IntoIterator<Item = Vec<Pt>>) -> Self {
+        let mut shape = Self::empty();
+        let everywhere = [
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+            f64::INFINITY,
+            f64::INFINITY,
+        ];
+        for (i, ring) in rings.into_iter().enumerate() {
+            shape.push_ring(ring, i > 0, everywhere);
+        }
+        shape
+    }
+
+    fn empty() -> Self {
+        Self {
+            edges: Vec::new(),
+            bbox: [
+                f64::INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::NEG_INFINITY,
+            ],
+        }
+    }
+
+    /// Add a ring in degrees, oriented as a hole or an exterior. Only the
+    /// edges meeting `window` stretched east are kept: those are all an
+    /// overlap with a region inside `window` reads, since its edges cut or
+    /// touch them and its containment rays run east. The bounding box and
+    /// the orientation still cover the whole ring.
+    fn push_ring(&mut self, ring: impl IntoIterator<Item = Pt>, hole: bool, window: [f64; 4]) {
+        let keep = [window[0], window[1], f64::INFINITY, window[3]];
+        let start = self.edges.len();
+        let mut twice = 0.0;
+        let bbox = &mut self.bbox;
+        let edges = &mut self.edges;
+        for_each_ring_edge(ring, |a, b| {
+            *bbox = [
+                bbox[0].min(a.0),
+                bbox[1].min(a.1),
+                bbox[2].max(a.0),
+                bbox[3].max(a.1),
+            ];
+            if a != b {
+                twice += twice_area_term(a, b);
+                if bboxes_overlap(edge_bbox(a, b), keep) {
+                    edges.push((a, b));
+                }
+            }
+        });
+        if (twice < 0.0) != hole {
+            for edge in &mut self.edges[start..] {
+                *edge = (edge.1, edge.0);
+            }
+        }
+    }
+
+    fn area(&self) -> f64 {
+        let twice: f64 = self.edges.iter().map(|&(a, b)| twice_area_term(a, b)).sum();
+        twice / 2.0
+    }
+
+    /// Even-odd containment over all rings.
+    fn contains(&self, p: Pt) -> bool {
+        let mut inside = false;
+        for &(a, b) in &self.edges {
+            if (a.1 > p.1) != (b.1 > p.1) && p.0 < a.0 + (p.1 - a.1) * (b.0 - a.0) / (b.1 - a.1) {
+                inside = !inside;
+            }
+        }
+        inside
+    }
+
+    /// When `p` lies on an edge, whether that edge runs along `dir`.
+    fn on_edge(&self, p: Pt, dir: Pt) -> Option<bool> {
+        self.edges.iter().find_map(|&(a, b)| {
+            let ab = sub(b, a);
+            let t = ((p.0 - a.0) * ab.0 + (p.1 - a.1) * ab.1) / (ab.0 * ab.0 + ab.1 * ab.1);
+            let c = lerp(a, b, t.clamp(0.0, 1.0));
+            let near = (p.0 - c.0).hypot(p.1 - c.1) <= ON_EDGE_DEG;
+            near.then(|| ab.0 * dir.0 + ab.1 * dir.1 > 0.0)
+        })
+    }
+
+    /// Twice the area of `self ∩ other`. Shared boundary pieces running the
+    /// same way are counted from `self`'s side only.
+    fn twice_overlap(&self, other: &Self) -> f64 {
+        self.twice_boundary_inside(other, true) + other.twice_boundary_inside(self, false)
+    }
+
+    /// Twice the shoelace sum over the pieces of this shape's boundary
+    /// inside `other`, including shared pieces running the same way when
+    /// `keep_shared` is set.
+    fn twice_boundary_inside(&self, other: &Self, keep_shared: bool) -> f64 {
+        let mut twice = 0.0;
+        for &(p, q) in &self.edges {
+            let bbox = edge_bbox(p, q);
+            if !bboxes_overlap(bbox, other.bbox) {
+                continue;
+            }
+            // Cut the edge wherever it meets `other`'s boundary.
+            let mut cuts = vec![0.0, 1.0];
+            for &(c, d) in &other.edges {
+                if bboxes_overlap(bbox, edge_bbox(c, d)) {
+                    crossings(p, q, c, d, &mut cuts);
+                }
+            }
+            cuts.sort_by(f64::total_cmp);
+            cuts.dedup();
+            let dir = sub(q, p);
+            for w in cuts.windows(2) {
+                let (from, to) = (lerp(p, q, w[0]), lerp(p, q, w[1]));
+                if from == to {
+                    continue;
+                }
+                let mid = lerp(p, q, (w[0] + w[1]) / 2.0);
+                let keep = match other.on_edge(mid, dir) {
+                    Some(same_way) => keep_shared && same_way,
+                    None => other.contains(mid),
+                };
+                if keep {
+                    twice += twice_area_term(from, to);
+                }
+            }
+        }
+        twice
+    }
+}
+
+/// Push the parameters along `p`–`q` where it meets `c`–`d`: the crossing
+/// point, or the ends of a collinear overlap.
+fn crossings(p: Pt, q: Pt, c: Pt, d: Pt, cuts: &mut Vec<f64>) {
+    let (r, s, cp) = (sub(q, p), sub(d, c), sub(c, p));
+    let (r_len, s_len) = (r.0.hypot(r.1), s.0.hypot(s.1));
+    let denom = cross(r, s);
+    if denom.abs() > 1e-12 * r_len * s_len {
+        let t = cross(cp, s) / denom;
+        let u = cross(cp, r) / denom;
+        let (t_eps, u_eps) = (ON_EDGE_DEG / r_len, ON_EDGE_DEG / s_len);
+        if (-t_eps..=1.0 + t_eps).contains(&t) && (-u_eps..=1.0 + u_eps).contains(&u) {
+            cuts.push(t.clamp(0.0, 1.0));
+        }
+    } else if cross(cp, r).abs() <= ON_EDGE_DEG * r_len {
+        for end in [c, d] {
+            let t = ((end.0 - p.0) * r.0 + (end.1 - p.1) * r.1) / (r_len * r_len);
+            if (0.0..=1.0).contains(&t) {
+                cuts.push(t);
+            }
+        }
+    }
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    /// Item indices whose grid cells meet the bounding box, or every item
+    /// when there is no grid. Cells missing from the grid hold no zone.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn candidates_in_bbox(&self, bbox: [f64; 4]) -> Vec<usize> {
+        let Some(ref grid) = self.grid else {
+            return (0..self.all.len()).collect();
+        };
+        let n = f64::from(grid.cells_per_degree);
+        let half = grid.lng_cells() / 2;
+        let (south, north) = (f64::from(-half / 2), f64::from(half / 2 - 1));
+        let (west, east) = (f64::from(-half), f64::from(half - 1));
+        let cell = |v: f64, min: f64, max: f64| (v * n).floor().clamp(min, max) as i16;
+
+        let mut ret: Vec<usize> = vec![];
+        for cell_lat in cell(bbox[1], south, north)..=cell(bbox[3], south, north) {
+            for cell_lng in cell(bbox[0], west, east)..=cell(bbox[2], west, east) {
+                if let Some(indices) = grid.cells.get(&(cell_lng, cell_lat)) {
+                    ret.extend(indices.iter().map(|&idx| idx as usize));
+                }
+            }
+        }
+        ret.sort_unstable();
+        ret.dedup();
+        ret
+    }
+
+    fn zones_in_region(&self, region: &Shape) -> Vec<RegionZone<'_>> {
+        let total = region.area();
+        if total.is_nan() || total <= 0.0 {
+            return vec![];
+        }
+        let mut ret = vec![];
+        for idx in self.candidates_in_bbox(region.bbox) {
+            let zone = &self.all[idx];
+            let mut twice = 0.0;
+            let mut polygon: Option<Shape> = None;
+            zone.for_each_ring_in(region.bbox, |exterior, ring| {
+                if exterior {
+                    twice += polygon.take().map_or(0.0, |p| region.twice_overlap(&p));
+                }
+                polygon
+                    .get_or_insert_with(Shape::empty)
+                    .push_ring(ring, !exterior, region.bbox);
+            });
+            twice += polygon.map_or(0.0, |p| region.twice_overlap(&p));
+            let fraction = (twice / 2.0 / total).min(1.0);
+            if fraction > MIN_FRACTION {
+                ret.push(RegionZone {
+                    name: zone.name(),
+                    fraction,
+                });
+            }
+        }
+        sort_by_share(&mut ret);
+        ret
+    }
+}
+
+/// Largest share first, ties by name.
+fn sort_by_share(zones: &mut [RegionZone<'_>]) {
+    zones.sort_by(|a, b| b.fraction.total_cmp(&a.fraction).then(a.name.cmp(b.name)));
+}
+
+fn ring_points(ring: &[[f64; 2]]) -> Vec<Pt> {
+    ring.iter().map(|&[lng, lat]| (lng, lat)).collect()
+}
+
+impl Finder {
+    /// Timezones overlapping the bounding box, largest share first. A box
+    /// with `min_lng > max_lng` crosses the antimeridian, as in
+    /// [`Finder::subset_by_bbox`].
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// assert_eq!(["Asia/Shanghai"], finder.zones_in_bbox(116.0, 39.5, 116.8, 40.2)[..]);
+    /// ```
+    #[must_use]
+    pub fn zones_in_bbox(
+        &self,
+        min_lng: f64,
+        min_lat: f64,
+        max_lng: f64,
+        max_lat: f64,
+    ) -> Vec<&str> {
+        self.zones_in_bbox_with_fractions(min_lng, min_lat, max_lng, max_lat)
+            .into_iter()
+            .map(|zone| zone.name)
+            .collect()
+    }
+
+    /// Like [`Finder::zones_in_bbox`], with the share of the box's area
+    /// each timezone covers.
+    #[must_use]
+    pub fn zones_in_bbox_with_fractions(
+        &self,
+        min_lng: f64,
+        min_lat: f64,
+        max_lng: f64,
+        max_lat: f64,
+    ) -> Vec<RegionZone<'_>> {
+        let rect = |west: f64, east: f64| {
+            Shape::new([vec![
+                (west, min_lat),
+                (east, min_lat),
+                (east, max_lat),
+                (west, max_lat),
+            ]])
+        };
+        if min_lng <= max_lng {
+            return self.zones_in_region(&rect(min_lng, max_lng));
+        }
+        // Measure both sides of the antimeridian, weighing each by its area.
+        let sides = [rect(min_lng, 180.0), rect(-180.0, max_lng)];
+        let total: f64 = sides.iter().map(Shape::area).sum();
+        if total.is_nan() || total <= 0.0 {
+            return vec![];
+        }
+        let mut ret: Vec<RegionZone<'_>> = vec![];
+        for side in &sides {
+            let weight = side.area() / total;
+            for zone in self.zones_in_region(side) {
+                let fraction = zone.fraction * weight;
+                match ret.iter_mut().find(|other| other.name == zone.name) {
+                    Some(other) => other.fraction = (other.fraction + fraction).min(1.0),
+                    None => ret.push(RegionZone {
+                        name: zone.name,
+                        fraction,
+                    }),
+                }
+            }
+        }
+        sort_by_share(&mut ret);
+        ret
+    }
+
+    /// Timezones overlapping the polygon with `exterior` and `holes`, given
+    /// as `[lng, lat]` rings, largest share first.
+    ///
+    /// Only overlaps with positive area count: a region merely touching a
+    /// zone along its border does not include it, and a region without
+    /// area includes nothing. Rings may be open or closed and wound either
+    /// way, but must not cross the antimeridian; split such regions and
+    /// query each part.
+    #[must_use]
+    pub fn zones_intersecting_polygon(
+        &self,
+        exterior: &[[f64; 2]],
+        holes: &[Vec<[f64; 2]>],
+    ) -> Vec<&str> {
+        self.zones_intersecting_polygon_with_fractions(exterior, holes)
+            .into_iter()
+            .map(|zone| zone.name)
+            .collect()
+    }
+
+    /// Like [`Finder::zones_intersecting_polygon`], with the share of the
+    /// polygon's area each timezone covers. Areas are measured in an
+    /// equal-area projection, so shares are comparable at any latitude.
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let square = [[116.0, 39.5], [116.8, 39.5], [116.8, 40.2], [116.0, 40.2]];
+    /// let zones = finder.zones_intersecting_polygon_with_fractions(&square, &[]);
+    /// assert_eq!("Asia/Shanghai", zones[0].name);
+    /// assert!((zones[0].fraction - 1.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn zones_intersecting_polygon_with_fractions(
+        &self,
+        exterior: &[[f64; 2]],
+        holes: &[Vec<[f64; 2]>],
+    ) -> Vec<RegionZone<'_>> {
+        let rings =
+            std::iter::once(ring_points(exterior)).chain(holes.iter().map(|h| ring_points(h)));
+        self.zones_in_region(&Shape::new(rings))
+    }
+
+    fn zones_in_region(&self, region: &Shape) -> Vec<RegionZone<'_>> {
+        with_core!(self, core => core.zones_in_region(region))
+    }
+}
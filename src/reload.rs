@@ -0,0 +1,128 @@
+//! A finder handle whose data can be replaced while it is being queried.
+
+use crate::{DefaultFinder, Error, FinderOptions};
+use arc_swap::{ArcSwap, Guard};
+use std::path::Path;
+use std::sync::Arc;
+
+/// A shared [`DefaultFinder`] that can be swapped for newer data without
+/// restarting a long-running service.
+///
+/// Queries run against a snapshot taken with [`ReloadableFinder::load`]; a
+/// reload only replaces the pointer, so in-flight queries finish on the old
+/// data, which is freed once the last snapshot is dropped. The current
+/// [`Arc`] lives in an atomic pointer, so neither loading a snapshot nor
+/// reloading takes a lock, and a panicking caller cannot leave it poisoned.
+///
+/// ```rust
+/// use tzf_rs::{DefaultFinder, ReloadableFinder};
+///
+/// let finder = ReloadableFinder::new(DefaultFinder::new());
+/// let snapshot = finder.load();
+/// assert_eq!("Asia/Shanghai", snapshot.get_tz_name(116.3883, 39.9289));
+/// ```
+pub struct ReloadableFinder {
+    current: ArcSwap<DefaultFinder>,
+}
+
+impl ReloadableFinder {
+    #[must_use]
+    pub fn new(finder: DefaultFinder) -> Self {
+        Self {
+            current: ArcSwap::from_pointee(finder),
+        }
+    }
+
+    /// The finder currently in service. Hold on to the snapshot for the
+    /// duration of a request so every lookup in it sees the same data.
+    #[must_use]
+    pub fn load(&self) -> Arc<DefaultFinder> {
+        self.current.load_full()
+    }
+
+    /// Data version of the finder currently in service.
+    #[must_use]
+    pub fn data_version(&self) -> String {
+        self.load().data_version().to_string()
+    }
+
+    /// Replaces the finder in service with `finder`, returning the previous
+    /// one.
+    ///
+    /// ```rust
+    /// use tzf_rs::{DefaultFinder, Error, ReloadableFinder};
+    ///
+    /// let finder = ReloadableFinder::new(DefaultFinder::new());
+    /// // Same release as the one loaded: rejected, nothing changes.
+    /// assert!(matches!(
+    ///     finder.reload(DefaultFinder::new()),
+    ///     Err(Error::SameDataVersion(_))
+    /// ));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Leaves the current finder in place and returns
+    /// [`Error::EmptyDataset`] if `finder` has no timezones, or
+    /// [`Error::SameDataVersion`] if its data version equals the current one.
+    pub fn reload(&self, finder: DefaultFinder) -> Result<Arc<DefaultFinder>, Error> {
+        if finder.timezonenames().is_empty() {
+            return Err(Error::EmptyDataset);
+        }
+        let finder = Arc::new(finder);
+        let mut current = self.current.load_full();
+        loop {
+            if current.data_version() == finder.data_version() {
+                return Err(Error::SameDataVersion(finder.data_version().to_string()));
+            }
+            // Only swap if no concurrent reload got in since the version
+            // check; otherwise check again against the winner.
+            let previous = self.current.compare_and_swap(&current, Arc::clone(&finder));
+            if Arc::ptr_eq(&previous, &current) {
+                return Ok(current);
+            }
+            current = Guard::into_inner(previous);
+        }
+    }
+
+    /// Loads data files with [`DefaultFinder::from_paths`] and swaps them in
+    /// with [`ReloadableFinder::reload`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the load error, or the validation error from
+    /// [`ReloadableFinder::reload`]; either way the current finder stays in
+    /// service.
+    pub fn reload_from_paths(
+        &self,
+        topo_path: impl AsRef<Path>,
+        preindex_path: impl AsRef<Path>,
+    ) -> Result<Arc<DefaultFinder>, Error> {
+        self.reload(DefaultFinder::from_paths(topo_path, preindex_path)?)
+    }
+
+    /// Like [`ReloadableFinder::reload_from_paths`], with explicit polygon
+    /// build options.
+    ///
+    /// # Errors
+    ///
+    /// See [`ReloadableFinder::reload_from_paths`].
+    pub fn reload_from_paths_with_options(
+        &self,
+        topo_path: impl AsRef<Path>,
+        preindex_path: impl AsRef<Path>,
+        options: FinderOptions,
+    ) -> Result<Arc<DefaultFinder>, Error> {
+        self.reload(DefaultFinder::from_paths_with_options(
+            topo_path,
+            preindex_path,
+            options,
+        )?)
+    }
+}
+
+impl From<DefaultFinder> for ReloadableFinder {
+    fn from(finder: DefaultFinder) -> Self {
+        Self::new(finder)
+    }
+}
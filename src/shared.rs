@@ -0,0 +1,458 @@
+//! Polygons whose rings reference a pool of shared edges instead of owning
+//! expanded copies; see [`FinderOptions::shared_edges`].
+//!
+//! The compressed topo format stores each border between two zones once,
+//! and expanding it copies those points into both neighbouring rings. Here a
+//! ring is a list of edges in one [`EdgePool`], each walked forward or
+//! reversed, and the raycast and its Y-stripes index read the pool's
+//! segments in place. The even-odd test does not care which way an edge is
+//! walked, so a stripe lists pool segments rather than ring positions.
+
+use crate::distance::for_each_ring_edge;
+use crate::snapshot::{EDGES_PER_STRIPE, MAX_STRIPES, RingHit, SCALE, test_edge};
+use crate::{
+    Error, Finder, FinderCore, FinderKind, FinderOptions, PolygonRings, RingError, TopoDecodeMode,
+    Zone, boxes_overlap, decode_grid_index, decode_polyline, decode_shared_edges, grid, pbgen,
+    resolve_topo_timezone,
+};
+use geometry_rs::{I32Point, Point};
+use pbgen::compressed_ring_segment::Content;
+use std::sync::{Arc, OnceLock};
+
+/// Marks a ring part that walks its edge backwards.
+const REVERSED: u32 = 1 << 31;
+/// Marks a stripe entry naming one of the ring's joins rather than a pool
+/// segment.
+const JOIN: u32 = 1 << 31;
+
+type Scaled = (i32, i32);
+
+/// Every edge's points back to back: the shared edges first, under their
+/// ids, then the inline runs of individual rings.
+pub(crate) struct EdgePool {
+    points: Vec<Scaled>,
+    // Edge `i` spans `points[starts[i]..starts[i + 1]]`.
+    starts: Vec<u32>,
+}
+
+impl EdgePool {
+    fn new() -> Self {
+        Self {
+            points: Vec::new(),
+            starts: vec![0],
+        }
+    }
+
+    /// Append an edge, returning its id.
+    #[allow(clippy::cast_possible_truncation)]
+    fn push(&mut self, points: Vec<I32Point>) -> u32 {
+        self.points.extend(points.into_iter().map(|p| (p.x, p.y)));
+        let end = u32::try_from(self.points.len())
+            .ok()
+            .filter(|&end| end < JOIN)
+            .expect("edge pool exceeds 2^31 points");
+        self.starts.push(end);
+        (self.starts.len() - 2) as u32
+    }
+
+    /// The pool points of a part's edge, in stored order.
+    fn edge(&self, part: u32) -> &[Scaled] {
+        let id = (part & !REVERSED) as usize;
+        &self.points[self.starts[id] as usize..self.starts[id + 1] as usize]
+    }
+
+    /// The points of a ring part in walking order.
+    fn walk(&self, part: u32) -> impl Iterator<Item = Scaled> + '_ {
+        let edge = self.edge(part);
+        let reversed = part & REVERSED != 0;
+        (0..edge.len()).map(move |i| edge[if reversed { edge.len() - 1 - i } else { i }])
+    }
+
+    /// First and last point of a ring part in walking order; `None` for an
+    /// empty edge.
+    fn ends(&self, part: u32) -> Option<(Scaled, Scaled)> {
+        let edge = self.edge(part);
+        let (&first, &last) = (edge.first()?, edge.last()?);
+        Some(if part & REVERSED == 0 {
+            (first, last)
+        } else {
+            (last, first)
+        })
+    }
+
+    /// Whether the ring made of `parts` ends where it starts, as strict
+    /// decoding requires of expanded rings.
+    fn is_closed(&self, parts: &[u32]) -> bool {
+        let mut ends = parts.iter().filter_map(|&part| self.ends(part));
+        let first = ends.next();
+        let last = ends.last().or(first);
+        matches!((first, last), (Some((start, _)), Some((_, end))) if start == end)
+    }
+
+    /// Pool segment `id` joins points `id` and `id + 1` of one edge.
+    fn segment(&self, id: u32) -> (Scaled, Scaled) {
+        (self.points[id as usize], self.points[id as usize + 1])
+    }
+}
+
+/// A ring as references into an [`EdgePool`].
+struct SharedRing {
+    // Pool edges in walking order, `REVERSED` set for backward walks.
+    parts: Box<[u32]>,
+    // Segments from each part's end to the next part's start, and from the
+    // last part back to the first, where those points differ.
+    joins: Box<[(Scaled, Scaled)]>,
+    // `[min_x, min_y, max_x, max_y]`, scaled.
+    bbox: [i32; 4],
+    stripes: OnceLock<Option<Stripes>>,
+}
+
+/// Y-stripes index of one ring.
+struct Stripes {
+    min_y: i32,
+    height: i64,
+    // Stripe `i` lists `segments[offsets[i]..offsets[i + 1]]`.
+    offsets: Box<[u32]>,
+    // Pool segment ids, or `JOIN | j` for the ring's join `j`.
+    segments: Box<[u32]>,
+}
+
+impl SharedRing {
+    fn new(pool: &EdgePool, parts: Vec<u32>) -> Self {
+        let mut bbox = [i32::MAX, i32::MAX, i32::MIN, i32::MIN];
+        let mut joins = Vec::new();
+        let (mut first, mut last) = (None, None);
+        for &part in &parts {
+            let Some((start, end)) = pool.ends(part) else {
+                continue;
+            };
+            for &(x, y) in pool.edge(part) {
+                bbox = [
+                    bbox[0].min(x),
+                    bbox[1].min(y),
+                    bbox[2].max(x),
+                    bbox[3].max(y),
+                ];
+            }
+            if let Some(last) = last.filter(|&last| last != start) {
+                joins.push((last, start));
+            }
+            first.get_or_insert(start);
+            last = Some(end);
+        }
+        match (first, last) {
+            (Some(first), Some(last)) if first != last => joins.push((last, first)),
+            _ => {}
+        }
+        Self {
+            parts: parts.into_boxed_slice(),
+            joins: joins.into_boxed_slice(),
+            bbox,
+            stripes: OnceLock::new(),
+        }
+    }
+
+    /// Ids of every segment of the ring: pool segments, then joins.
+    #[allow(clippy::cast_possible_truncation)]
+    fn segments<'a>(&'a self, pool: &'a EdgePool) -> impl Iterator<Item = u32> + 'a {
+        let runs = self.parts.iter().flat_map(move |&part| {
+            let id = (part & !REVERSED) as usize;
+            let (start, end) = (pool.starts[id], pool.starts[id + 1]);
+            start..end.max(start + 1) - 1
+        });
+        runs.chain((0..self.joins.len() as u32).map(|j| JOIN | j))
+    }
+
+    fn segment(&self, pool: &EdgePool, id: u32) -> (Scaled, Scaled) {
+        if id & JOIN == 0 {
+            pool.segment(id)
+        } else {
+            self.joins[(id & !JOIN) as usize]
+        }
+    }
+
+    /// The ring's Y-stripes index, built on first use.
+    fn stripes(&self, pool: &EdgePool, options: FinderOptions) -> Option<&Stripes> {
+        self.stripes
+            .get_or_init(|| self.build_stripes(pool, options))
+            .as_ref()
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    /// `None` where `options` gives a ring of this size no index.
+    fn build_stripes(&self, pool: &EdgePool, options: FinderOptions) -> Option<Stripes> {
+        let n = self.segments(pool).count();
+        if !options.indexes(n) {
+            return None;
+        }
+        let [_, min_y, _, max_y] = self.bbox;
+        let span = i64::from(max_y) - i64::from(min_y);
+        let count = (n / EDGES_PER_STRIPE).clamp(1, MAX_STRIPES);
+        // `count * height > span`, so every y in the ring maps to a stripe.
+        let height = span / count as i64 + 1;
+        let stripe_of = |y: i32| ((i64::from(y) - i64::from(min_y)) / height) as usize;
+        let mut stripes: Vec<Vec<u32>> = vec![Vec::new(); count];
+        for id in self.segments(pool) {
+            let ((_, ay), (_, by)) = self.segment(pool, id);
+            for stripe in &mut stripes[stripe_of(ay.min(by))..=stripe_of(ay.max(by))] {
+                stripe.push(id);
+            }
+        }
+        let mut offsets = Vec::with_capacity(count + 1);
+        let mut offset = 0;
+        offsets.push(offset);
+        for stripe in &stripes {
+            offset += stripe.len() as u32;
+            offsets.push(offset);
+        }
+        Some(Stripes {
+            min_y,
+            height,
+            offsets: offsets.into_boxed_slice(),
+            segments: stripes.concat().into_boxed_slice(),
+        })
+    }
+
+    /// Locate the scaled point `(x, y)` relative to the ring: even-odd
+    /// raycast over the segments of its Y stripe, or all its segments when
+    /// it has no stripes.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn hit(&self, pool: &EdgePool, options: FinderOptions, x: f64, y: f64) -> RingHit {
+        let [min_x, min_y, max_x, max_y] = self.bbox.map(f64::from);
+        if x < min_x || x > max_x || y < min_y || y > max_y {
+            return RingHit::Outside;
+        }
+
+        let mut inside = false;
+        let mut test = |id: u32| {
+            let ((ax, ay), (bx, by)) = self.segment(pool, id);
+            let (a, b) = (
+                (f64::from(ax), f64::from(ay)),
+                (f64::from(bx), f64::from(by)),
+            );
+            test_edge(a, b, x, y, &mut inside)
+        };
+        let on_edge = match self.stripes(pool, options) {
+            None => self.segments(pool).any(&mut test),
+            Some(stripes) => {
+                let stripe = (((y - f64::from(stripes.min_y)) / stripes.height as f64) as usize)
+                    .min(stripes.offsets.len() - 2);
+                let range = stripes.offsets[stripe] as usize..stripes.offsets[stripe + 1] as usize;
+                stripes.segments[range].iter().any(|&id| test(id))
+            }
+        };
+        match (on_edge, inside) {
+            (true, _) => RingHit::OnEdge,
+            (false, true) => RingHit::Inside,
+            (false, false) => RingHit::Outside,
+        }
+    }
+}
+
+/// A zone whose rings reference a shared [`EdgePool`].
+#[derive(Clone)]
+pub(crate) struct SharedZone {
+    name: String,
+    // Rings per polygon, exterior first.
+    polygons: Arc<[Box<[SharedRing]>]>,
+    pool: Arc<EdgePool>,
+    options: FinderOptions,
+}
+
+impl SharedZone {
+    /// Ring `ring`'s points in degrees.
+    fn ring_degrees<'a>(&'a self, ring: &'a SharedRing) -> impl Iterator<Item = (f64, f64)> + 'a {
+        ring.parts
+            .iter()
+            .flat_map(|&part| self.pool.walk(part))
+            .map(|(x, y)| (f64::from(x) / SCALE, f64::from(y) / SCALE))
+    }
+
+    /// Whether the polygon with `rings` contains the scaled point, borders
+    /// included.
+    fn polygon_contains(&self, rings: &[SharedRing], x: f64, y: f64) -> bool {
+        let hit = |ring: &SharedRing| ring.hit(&self.pool, self.options, x, y);
+        let mut rings = rings.iter();
+        match rings.next().map(hit) {
+            Some(RingHit::Inside) => rings.all(|hole| hit(hole) != RingHit::Inside),
+            Some(RingHit::OnEdge) => true,
+            Some(RingHit::Outside) | None => false,
+        }
+    }
+
+    /// Build the Y-stripes index of every ring now rather than on first use.
+    fn build_stripes(&self) {
+        for ring in self.polygons.iter().flatten() {
+            ring.stripes(&self.pool, self.options);
+        }
+    }
+}
+
+impl Zone for SharedZone {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn contains_point(&self, p: &Point) -> bool {
+        let (x, y) = (p.x * SCALE, p.y * SCALE);
+        self.polygons
+            .iter()
+            .any(|rings| self.polygon_contains(rings, x, y))
+    }
+
+    fn for_each_edge(&self, mut f: impl FnMut((f64, f64), (f64, f64))) {
+        for ring in self.polygons.iter().flatten() {
+            for_each_ring_edge(self.ring_degrees(ring), &mut f);
+        }
+    }
+
+    fn for_each_edge_in(&self, bbox: [f64; 4], mut f: impl FnMut((f64, f64), (f64, f64))) {
+        for ring in self.polygons.iter().flatten() {
+            if boxes_overlap(bbox, ring.bbox.map(|v| f64::from(v) / SCALE)) {
+                for_each_ring_edge(self.ring_degrees(ring), &mut f);
+            }
+        }
+    }
+
+    fn for_each_ring_in(
+        &self,
+        bbox: [f64; 4],
+        mut f: impl FnMut(bool, &mut dyn Iterator<Item = (f64, f64)>),
+    ) {
+        for rings in &self.polygons {
+            let Some(exterior) = rings.first() else {
+                continue;
+            };
+            if boxes_overlap(bbox, exterior.bbox.map(|v| f64::from(v) / SCALE)) {
+                for (i, ring) in rings.iter().enumerate() {
+                    f(i == 0, &mut self.ring_degrees(ring));
+                }
+            }
+        }
+    }
+
+    fn polygons(&self) -> Vec<PolygonRings> {
+        self.polygons
+            .iter()
+            .map(|rings| {
+                rings
+                    .iter()
+                    .map(|ring| self.ring_degrees(ring).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn bounds(&self) -> Vec<[f64; 4]> {
+        self.polygons
+            .iter()
+            .filter_map(|rings| rings.first())
+            .map(|exterior| exterior.bbox.map(|v| f64::from(v) / SCALE))
+            .collect()
+    }
+}
+
+/// Resolve a ring's segments to pool parts, appending inline runs to the
+/// pool. `present[id]` tells whether shared edge `id` decoded.
+fn resolve_parts(
+    pool: &mut EdgePool,
+    present: &[bool],
+    segs: &[pbgen::CompressedRingSegment],
+) -> Result<Vec<u32>, (Option<usize>, RingError)> {
+    let mut parts = Vec::with_capacity(segs.len());
+    for (i, seg) in segs.iter().enumerate() {
+        let shared = |idx: i32| {
+            u32::try_from(idx)
+                .ok()
+                .filter(|&id| present.get(id as usize) == Some(&true))
+                .ok_or((Some(i), RingError::DanglingSharedEdge(idx)))
+        };
+        match &seg.content {
+            Some(Content::Inline(inline)) => {
+                let points = decode_polyline(&inline.points)
+                    .map_err(|reason| (Some(i), RingError::Polyline(reason)))?;
+                parts.push(pool.push(points));
+            }
+            Some(Content::EdgeForward(idx)) => parts.push(shared(*idx)?),
+            Some(Content::EdgeReversed(idx)) => parts.push(shared(*idx)? | REVERSED),
+            None => {}
+        }
+    }
+    Ok(parts)
+}
+
+impl Finder {
+    /// Build a finder over [`SharedZone`]s; see
+    /// [`FinderOptions::shared_edges`].
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn from_compressed_topo_shared(
+        tzs: pbgen::CompressedTopoTimezones,
+        options: FinderOptions,
+        mode: TopoDecodeMode,
+    ) -> Result<Self, Error> {
+        let embedded = match tzs.grid_index {
+            Some(gi) => Some(decode_grid_index(gi, tzs.timezones.len(), mode)?),
+            None => None,
+        };
+
+        // Shared edges keep their ids; ones that failed to decode stay empty
+        // and rings referencing them count as dangling.
+        let mut pool = EdgePool::new();
+        let decoded = decode_shared_edges(&tzs.shared_edges, mode)?;
+        let present: Vec<bool> = decoded.iter().map(Option::is_some).collect();
+        for edge in decoded {
+            pool.push(edge.unwrap_or_default());
+        }
+        let strict = matches!(mode, TopoDecodeMode::Strict);
+        let mut resolved = Vec::with_capacity(tzs.timezones.len());
+        for tz in &tzs.timezones {
+            let polygons = resolve_topo_timezone(tz, mode, |segs| {
+                let parts = resolve_parts(&mut pool, &present, segs)?;
+                if strict && !pool.is_closed(&parts) {
+                    return Err((None, RingError::NotClosed));
+                }
+                Ok(parts)
+            })?;
+            resolved.push(polygons);
+        }
+
+        let pool = Arc::new(pool);
+        let all: Vec<SharedZone> = tzs
+            .timezones
+            .into_iter()
+            .zip(resolved)
+            .map(|(tz, polygons)| SharedZone {
+                name: tz.name,
+                polygons: polygons
+                    .into_iter()
+                    .map(|(exterior, holes)| {
+                        std::iter::once(exterior)
+                            .chain(holes)
+                            .map(|parts| SharedRing::new(&pool, parts))
+                            .collect()
+                    })
+                    .collect(),
+                pool: Arc::clone(&pool),
+                options,
+            })
+            .collect();
+        if !options.is_lazy() {
+            for zone in &all {
+                zone.build_stripes();
+            }
+        }
+
+        let grid = embedded.or_else(|| {
+            let n = options.grid.cells_per_degree()?;
+            Some(grid::build_grid(n, all.iter().map(Zone::polygons)))
+        });
+        let core = FinderCore::new(all, tzs.version, grid).with_options(options);
+        Ok(Self {
+            inner: FinderKind::Shared(core),
+        })
+    }
+}
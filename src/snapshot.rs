@@ -0,0 +1,880 @@
+//! Versioned binary snapshots of a [`Finder`], queried in place.
+//!
+//! A snapshot holds the expanded 1e5-scaled rings, a Y-stripes index for each
+//! ring the finder's [`FinderOptions`] index, and the grid with its coverage
+//! flags, so loading one skips protobuf decoding and index building.
+//! Queries read the rings straight from the snapshot buffer, which may be a
+//! memory-mapped file; only the zone names and grid cells are copied out.
+//!
+//! # Layout
+//!
+//! All integers are little-endian. A 184-byte header:
+//!
+//! | bytes    | field                                              |
+//! | -------- | -------------------------------------------------- |
+//! | 0..8     | magic `TZFSNAP\0`                                  |
+//! | 8..12    | format version ([`SNAPSHOT_FORMAT_VERSION`])       |
+//! | 12..16   | flags; bit 0 set when the grid is present          |
+//! | 16..20   | byte length of the data version string             |
+//! | 20..24   | grid cells per degree: 1, 2 or 4                   |
+//! | 24..28   | polygon index mode of the writing finder           |
+//! | 28..32   | index mode parameter                               |
+//! | 32..36   | minimum segments of an indexed ring                |
+//! | 36..40   | zero                                               |
+//! | 40..184  | nine `(offset: u64, byte_len: u64)` section slots   |
+//!
+//! The index mode is 0 for [`FinderOptions::NoIndex`], 1 for
+//! [`FinderOptions::YStripes`], 2 and 3 for the float and integer raycasts,
+//! 4 for [`FinderOptions::polygon_indexes`] with the RTree, compressed
+//! quadtree and Y stripes flags in parameter bits 0 to 2, and 5 for
+//! [`FinderOptions::auto`] with the byte budget as parameter. Whichever mode
+//! indexes a ring, the snapshot stores Y stripes for it, the one index it can
+//! query in place; the mode is kept so a loaded finder writes the same
+//! snapshot again.
+//!
+//! followed by the sections, each starting at a multiple of 8 bytes, so a
+//! page-aligned mapping can be viewed as typed slices in place:
+//!
+//! 1. strings: the data version, then zone names (UTF-8)
+//! 2. zones: `name_offset, name_len, polygon_start, polygon_count` (`u32`)
+//! 3. polygons: `ring_start, ring_count` (`u32`); the first ring is the
+//!    exterior, the rest are holes
+//! 4. rings: `point_start, point_count, stripe_start, stripe_count` (`u32`),
+//!    `min_x, min_y, max_x, max_y` (`i32`), `stripe_height, 0` (`u32`)
+//! 5. points: `x, y` (`i32`, degrees × 1e5)
+//! 6. stripe offsets (`u32`): a ring with `stripe_count` stripes owns
+//!    `stripe_count + 1` entries from `stripe_start`, bounding its runs in
+//! 7. stripe edges (`u32`): edge `i` of a ring joins its points `i` and
+//!    `(i + 1) % point_count`
+//! 8. grid cells: `floor(lng * n), floor(lat * n)` (`i32`) for `n` cells per
+//!    degree, `candidate_start, candidate_count, flags, 0` (`u32`); flag bit
+//!    0 is set when the cell's single candidate covers it entirely
+//! 9. grid candidates (`u32` zone indices)
+
+use crate::distance::for_each_ring_edge;
+use crate::grid::Grid;
+use crate::{
+    Error, Finder, FinderCore, FinderKind, FinderOptions, IndexMode, PolygonRings, Zone,
+    boxes_overlap,
+};
+use geometry_rs::Point;
+use std::io::{self, Write};
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Version of the snapshot layout written by [`Finder::write_snapshot`].
+pub const SNAPSHOT_FORMAT_VERSION: u32 = 2;
+
+const MAGIC: [u8; 8] = *b"TZFSNAP\0";
+const SECTION_COUNT: usize = 9;
+const SLOTS_START: usize = 40;
+const HEADER_LEN: usize = SLOTS_START + SECTION_COUNT * 16;
+const ALIGN: usize = 8;
+const FLAG_GRID: u32 = 1;
+const CELL_COVERED: u32 = 1;
+
+/// Snapshot coordinates are degrees × `SCALE`, like compressed topo data.
+pub(crate) const SCALE: f64 = 1e5;
+
+/// Target edge count per Y stripe; rings the finder's options leave
+/// unindexed get no stripes and are scanned linearly.
+pub(crate) const EDGES_PER_STRIPE: usize = 4;
+pub(crate) const MAX_STRIPES: usize = 4096;
+
+#[derive(Clone, Copy)]
+enum Section {
+    Strings,
+    Zones,
+    Polygons,
+    Rings,
+    Points,
+    StripeOffsets,
+    StripeEdges,
+    GridCells,
+    GridCandidates,
+}
+
+impl Section {
+    const ALL: [Self; SECTION_COUNT] = [
+        Self::Strings,
+        Self::Zones,
+        Self::Polygons,
+        Self::Rings,
+        Self::Points,
+        Self::StripeOffsets,
+        Self::StripeEdges,
+        Self::GridCells,
+        Self::GridCandidates,
+    ];
+
+    /// Record size in 32-bit words; strings are bytes.
+    fn record_words(self) -> usize {
+        match self {
+            Self::Strings => 0,
+            Self::Zones => 4,
+            Self::GridCells => 6,
+            Self::Polygons | Self::Points => 2,
+            Self::Rings => 10,
+            Self::StripeOffsets | Self::StripeEdges | Self::GridCandidates => 1,
+        }
+    }
+}
+
+/// Word offsets of the fields of a ring record.
+mod ring {
+    pub const POINT_START: usize = 0;
+    pub const POINT_COUNT: usize = 1;
+    pub const STRIPE_START: usize = 2;
+    pub const STRIPE_COUNT: usize = 3;
+    pub const MIN_X: usize = 4;
+    pub const MIN_Y: usize = 5;
+    pub const MAX_X: usize = 6;
+    pub const MAX_Y: usize = 7;
+    pub const STRIPE_HEIGHT: usize = 8;
+}
+
+fn read_u32(bytes: &[u8], at: usize) -> u32 {
+    let mut word = [0; 4];
+    word.copy_from_slice(&bytes[at..at + 4]);
+    u32::from_le_bytes(word)
+}
+
+fn read_u64(bytes: &[u8], at: usize) -> u64 {
+    let mut word = [0; 8];
+    word.copy_from_slice(&bytes[at..at + 8]);
+    u64::from_le_bytes(word)
+}
+
+/// The index mode as its header `(mode, parameter)` words.
+fn encode_index(index: IndexMode) -> (u32, u32) {
+    match index {
+        IndexMode::NoIndex => (0, 0),
+        IndexMode::YStripes => (1, 0),
+        IndexMode::NoIndexFloatRaycast => (2, 0),
+        IndexMode::NoIndexIntegerRaycast => (3, 0),
+        IndexMode::Indexes {
+            rtree,
+            compressed_quad,
+            y_stripes,
+        } => (
+            4,
+            u32::from(rtree) | (u32::from(compressed_quad) << 1) | (u32::from(y_stripes) << 2),
+        ),
+        IndexMode::Auto { max_index_bytes } => {
+            (5, u32::try_from(max_index_bytes).unwrap_or(u32::MAX))
+        }
+    }
+}
+
+/// Inverse of [`encode_index`]; `None` for unknown modes.
+fn decode_index(mode: u32, parameter: u32) -> Option<IndexMode> {
+    Some(match (mode, parameter) {
+        (0, 0) => IndexMode::NoIndex,
+        (1, 0) => IndexMode::YStripes,
+        (2, 0) => IndexMode::NoIndexFloatRaycast,
+        (3, 0) => IndexMode::NoIndexIntegerRaycast,
+        (4, 0..=7) => IndexMode::Indexes {
+            rtree: parameter & 1 != 0,
+            compressed_quad: parameter & 2 != 0,
+            y_stripes: parameter & 4 != 0,
+        },
+        (5, _) => IndexMode::Auto {
+            max_index_bytes: parameter as usize,
+        },
+        _ => return None,
+    })
+}
+
+/// A validated snapshot buffer.
+pub(crate) struct SnapshotData {
+    buffer: Box<dyn AsRef<[u8]> + Send + Sync>,
+    sections: [Range<usize>; SECTION_COUNT],
+}
+
+/// Where a point lies relative to a ring.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RingHit {
+    Outside,
+    Inside,
+    OnEdge,
+}
+
+/// One even-odd raycast step: flip `inside` when the ray cast east from
+/// `(x, y)` crosses the edge `a`–`b`, and report whether the point lies on
+/// the edge.
+///
+/// Follows the raycast geometry-rs ports from tidwall/geometry step for step,
+/// including its exact-ratio on-edge test and nudging the point above
+/// vertices at its latitude, so in-place lookups answer boundary points the
+/// way the built polygons of the finder that wrote the snapshot do.
+pub(crate) fn test_edge(a: (f64, f64), b: (f64, f64), x: f64, y: f64, inside: &mut bool) -> bool {
+    let ((ax, ay), (bx, by)) = (a, b);
+    let (min_y, max_y) = (ay.min(by), ay.max(by));
+    if y < min_y || y > max_y {
+        return false;
+    }
+    if ay == by {
+        // A horizontal edge: the point lies on it or the ray misses it.
+        return if ax == bx {
+            x == ax
+        } else {
+            (ax.min(bx)..=ax.max(bx)).contains(&x)
+        };
+    }
+    if ax == bx && x == ax {
+        return true;
+    }
+    if (x - ax) / (bx - ax) == (y - ay) / (by - ay) {
+        return true;
+    }
+
+    let mut y = y;
+    while y == ay || y == by {
+        y = y.next_up();
+    }
+    if y > max_y {
+        return false;
+    }
+    let crosses = if ax > bx {
+        x < ax && (x <= bx || slope_above(a, b, x, y))
+    } else {
+        x < bx && (x <= ax || slope_above(a, b, x, y))
+    };
+    if crosses {
+        *inside = !*inside;
+    }
+    false
+}
+
+/// Whether `(x, y)` lies above the edge `a`–`b` as seen from its lower end,
+/// comparing slopes the way geometry-rs does.
+fn slope_above((ax, ay): (f64, f64), (bx, by): (f64, f64), x: f64, y: f64) -> bool {
+    if ay < by {
+        (y - ay) / (x - ax) >= (by - ay) / (bx - ax)
+    } else {
+        (y - by) / (x - bx) >= (ay - by) / (ax - bx)
+    }
+}
+
+impl SnapshotData {
+    fn bytes(&self) -> &[u8] {
+        (*self.buffer).as_ref()
+    }
+
+    fn len(&self, section: Section) -> usize {
+        let bytes = self.sections[section as usize].len();
+        match section.record_words() {
+            0 => bytes,
+            words => bytes / (4 * words),
+        }
+    }
+
+    /// Word `field` of record `index` in `section`.
+    fn word(&self, section: Section, index: usize, field: usize) -> u32 {
+        let at =
+            self.sections[section as usize].start + 4 * (index * section.record_words() + field);
+        read_u32(self.bytes(), at)
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn signed(&self, section: Section, index: usize, field: usize) -> i32 {
+        self.word(section, index, field) as i32
+    }
+
+    /// Grid cells per degree.
+    #[allow(clippy::cast_possible_truncation)]
+    fn cells_per_degree(&self) -> u8 {
+        read_u32(self.bytes(), 20) as u8
+    }
+
+    /// The polygon options of the finder that wrote the snapshot.
+    fn options(&self) -> FinderOptions {
+        let bytes = self.bytes();
+        FinderOptions {
+            // Checked in `check_references`.
+            index: decode_index(read_u32(bytes, 24), read_u32(bytes, 28)).unwrap_or_default(),
+            min_index_segments: read_u32(bytes, 32) as usize,
+            ..FinderOptions::default()
+        }
+    }
+
+    fn string(&self, offset: usize, len: usize) -> Option<&str> {
+        let strings = &self.bytes()[self.sections[Section::Strings as usize].clone()];
+        std::str::from_utf8(strings.get(offset..offset.checked_add(len)?)?).ok()
+    }
+
+    fn range(&self, section: Section, index: usize, start: usize) -> Range<usize> {
+        let first = self.word(section, index, start) as usize;
+        first..first + self.word(section, index, start + 1) as usize
+    }
+
+    fn point(&self, index: usize) -> (f64, f64) {
+        (
+            f64::from(self.signed(Section::Points, index, 0)),
+            f64::from(self.signed(Section::Points, index, 1)),
+        )
+    }
+
+    /// Ring `ring`'s points in degrees.
+    fn ring_degrees(&self, ring: usize) -> impl Iterator<Item = (f64, f64)> + '_ {
+        self.range(Section::Rings, ring, ring::POINT_START)
+            .map(|i| self.point(i))
+            .map(|(x, y)| (x / SCALE, y / SCALE))
+    }
+
+    /// Locate the scaled point `(x, y)` relative to ring `ring`: even-odd
+    /// raycast over the edges of the ring's Y stripe, or all its edges when
+    /// it has no stripes.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn ring_hit(&self, ring: usize, x: f64, y: f64) -> RingHit {
+        let field = |f| self.signed(Section::Rings, ring, f);
+        let (min_x, min_y) = (f64::from(field(ring::MIN_X)), f64::from(field(ring::MIN_Y)));
+        let (max_x, max_y) = (f64::from(field(ring::MAX_X)), f64::from(field(ring::MAX_Y)));
+        let points = self.range(Section::Rings, ring, ring::POINT_START);
+        if points.is_empty() || x < min_x || x > max_x || y < min_y || y > max_y {
+            return RingHit::Outside;
+        }
+
+        let n = points.len();
+        let mut inside = false;
+        let mut test = |edge: usize| {
+            let a = self.point(points.start + edge);
+            let b = self.point(points.start + (edge + 1) % n);
+            test_edge(a, b, x, y, &mut inside)
+        };
+
+        let stripe_count = self.word(Section::Rings, ring, ring::STRIPE_COUNT) as usize;
+        let on_edge = if stripe_count == 0 {
+            (0..n).any(&mut test)
+        } else {
+            let height = f64::from(self.word(Section::Rings, ring, ring::STRIPE_HEIGHT));
+            let stripe = (((y - min_y) / height) as usize).min(stripe_count - 1);
+            let offsets = self.word(Section::Rings, ring, ring::STRIPE_START) as usize + stripe;
+            let edges = self.word(Section::StripeOffsets, offsets, 0) as usize
+                ..self.word(Section::StripeOffsets, offsets + 1, 0) as usize;
+            edges
+                .map(|k| self.word(Section::StripeEdges, k, 0) as usize)
+                .any(&mut test)
+        };
+        match (on_edge, inside) {
+            (true, _) => RingHit::OnEdge,
+            (false, true) => RingHit::Inside,
+            (false, false) => RingHit::Outside,
+        }
+    }
+
+    /// Whether polygon `polygon` contains the scaled point, borders included.
+    fn polygon_contains(&self, polygon: usize, x: f64, y: f64) -> bool {
+        let mut rings = self.range(Section::Polygons, polygon, 0);
+        match rings.next().map(|exterior| self.ring_hit(exterior, x, y)) {
+            Some(RingHit::Inside) => rings.all(|hole| self.ring_hit(hole, x, y) != RingHit::Inside),
+            Some(RingHit::OnEdge) => true,
+            Some(RingHit::Outside) | None => false,
+        }
+    }
+
+    /// Parse the header and check every cross-reference, so queries can
+    /// index the buffer without further checks.
+    fn validate(buffer: Box<dyn AsRef<[u8]> + Send + Sync>) -> Result<Self, Error> {
+        let invalid = |reason| Err(Error::InvalidSnapshot(reason));
+        let bytes = (*buffer).as_ref();
+        if bytes.len() < HEADER_LEN || bytes[..8] != MAGIC {
+            return invalid("missing snapshot header");
+        }
+        let format_version = read_u32(bytes, 8);
+        if format_version != SNAPSHOT_FORMAT_VERSION {
+            return Err(Error::UnsupportedSnapshotVersion(format_version));
+        }
+
+        let mut sections: [Range<usize>; SECTION_COUNT] = Default::default();
+        for (i, slot) in sections.iter_mut().enumerate() {
+            let at = SLOTS_START + 16 * i;
+            let (offset, len) = (read_u64(bytes, at), read_u64(bytes, at + 8));
+            let (Ok(offset), Ok(len)) = (usize::try_from(offset), usize::try_from(len)) else {
+                return invalid("section out of bounds");
+            };
+            let Some(end) = offset.checked_add(len).filter(|&end| end <= bytes.len()) else {
+                return invalid("section out of bounds");
+            };
+            let words = Section::ALL[i].record_words();
+            if offset < HEADER_LEN || offset % ALIGN != 0 || (words > 0 && len % (4 * words) != 0) {
+                return invalid("misaligned section");
+            }
+            *slot = offset..end;
+        }
+        let data = Self { buffer, sections };
+        data.check_references()?;
+        Ok(data)
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn check_references(&self) -> Result<(), Error> {
+        let fail = |reason| Err(Error::InvalidSnapshot(reason));
+        let bytes = self.bytes();
+        if self.string(0, read_u32(bytes, 16) as usize).is_none() {
+            return fail("bad data version string");
+        }
+        if !matches!(read_u32(bytes, 20), 1 | 2 | 4) {
+            return fail("bad grid cell size");
+        }
+        if decode_index(read_u32(bytes, 24), read_u32(bytes, 28)).is_none() {
+            return fail("bad index mode");
+        }
+        let fits = |range: &Range<usize>, section| range.end <= self.len(section);
+
+        for zone in 0..self.len(Section::Zones) {
+            let name = self.range(Section::Zones, zone, 0);
+            if self.string(name.start, name.len()).is_none() {
+                return fail("bad zone name");
+            }
+            if !fits(&self.range(Section::Zones, zone, 2), Section::Polygons) {
+                return fail("zone polygons out of range");
+            }
+        }
+        for polygon in 0..self.len(Section::Polygons) {
+            let rings = self.range(Section::Polygons, polygon, 0);
+            if rings.is_empty() || !fits(&rings, Section::Rings) {
+                return fail("polygon rings out of range");
+            }
+        }
+        for ring in 0..self.len(Section::Rings) {
+            let points = self.range(Section::Rings, ring, ring::POINT_START);
+            if !fits(&points, Section::Points) {
+                return fail("ring points out of range");
+            }
+            let stripe_count = self.word(Section::Rings, ring, ring::STRIPE_COUNT) as usize;
+            if stripe_count == 0 {
+                continue;
+            }
+            let start = self.word(Section::Rings, ring, ring::STRIPE_START) as usize;
+            if self.word(Section::Rings, ring, ring::STRIPE_HEIGHT) == 0
+                || !fits(&(start..start + stripe_count + 1), Section::StripeOffsets)
+            {
+                return fail("ring stripes out of range");
+            }
+            let mut prev = self.word(Section::StripeOffsets, start, 0) as usize;
+            for offset in start + 1..=start + stripe_count {
+                let next = self.word(Section::StripeOffsets, offset, 0) as usize;
+                if next < prev || next > self.len(Section::StripeEdges) {
+                    return fail("stripe edges out of range");
+                }
+                if (prev..next)
+                    .any(|k| self.word(Section::StripeEdges, k, 0) as usize >= points.len())
+                {
+                    return fail("stripe edge out of range");
+                }
+                prev = next;
+            }
+        }
+        let zones = self.len(Section::Zones);
+        for cell in 0..self.len(Section::GridCells) {
+            let candidates = self.range(Section::GridCells, cell, 2);
+            if self.word(Section::GridCells, cell, 4) & !CELL_COVERED != 0 {
+                return fail("bad grid cell flags");
+            }
+            if !fits(&candidates, Section::GridCandidates)
+                || candidates
+                    .map(|k| self.word(Section::GridCandidates, k, 0) as usize)
+                    .any(|zone| zone >= zones)
+            {
+                return fail("grid candidates out of range");
+            }
+            let (lng, lat) = (
+                self.signed(Section::GridCells, cell, 0),
+                self.signed(Section::GridCells, cell, 1),
+            );
+            let n = i32::from(self.cells_per_degree());
+            if !(-180 * n..180 * n).contains(&lng) || !(-90 * n..90 * n).contains(&lat) {
+                return fail("grid cell out of range");
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A zone whose rings are read in place from a snapshot buffer.
+#[derive(Clone)]
+pub(crate) struct SnapshotZone {
+    name: String,
+    polygons: Range<usize>,
+    data: Arc<SnapshotData>,
+}
+
+impl Zone for SnapshotZone {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn contains_point(&self, p: &Point) -> bool {
+        let (x, y) = (p.x * SCALE, p.y * SCALE);
+        self.polygons
+            .clone()
+            .any(|polygon| self.data.polygon_contains(polygon, x, y))
+    }
+
+    fn for_each_edge(&self, mut f: impl FnMut((f64, f64), (f64, f64))) {
+        for polygon in self.polygons.clone() {
+            for ring in self.data.range(Section::Polygons, polygon, 0) {
+                for_each_ring_edge(self.data.ring_degrees(ring), &mut f);
+            }
+        }
+    }
+
+    fn for_each_edge_in(&self, bbox: [f64; 4], mut f: impl FnMut((f64, f64), (f64, f64))) {
+        for polygon in self.polygons.clone() {
+            for ring in self.data.range(Section::Polygons, polygon, 0) {
+                let ring_bbox = [ring::MIN_X, ring::MIN_Y, ring::MAX_X, ring::MAX_Y]
+                    .map(|field| f64::from(self.data.signed(Section::Rings, ring, field)) / SCALE);
+                if boxes_overlap(bbox, ring_bbox) {
+                    for_each_ring_edge(self.data.ring_degrees(ring), &mut f);
+                }
+            }
+        }
+    }
+
+    fn for_each_ring_in(
+        &self,
+        bbox: [f64; 4],
+        mut f: impl FnMut(bool, &mut dyn Iterator<Item = (f64, f64)>),
+    ) {
+        for polygon in self.polygons.clone() {
+            let exterior = self.data.word(Section::Polygons, polygon, 0) as usize;
+            let exterior_bbox = [ring::MIN_X, ring::MIN_Y, ring::MAX_X, ring::MAX_Y]
+                .map(|field| f64::from(self.data.signed(Section::Rings, exterior, field)) / SCALE);
+            if boxes_overlap(bbox, exterior_bbox) {
+                for (i, ring) in self.data.range(Section::Polygons, polygon, 0).enumerate() {
+                    f(i == 0, &mut self.data.ring_degrees(ring));
+                }
+            }
+        }
+    }
+
+    fn polygons(&self) -> Vec<PolygonRings> {
+        self.polygons
+            .clone()
+            .map(|polygon| {
+                self.data
+                    .range(Section::Polygons, polygon, 0)
+                    .map(|ring| self.data.ring_degrees(ring).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn bounds(&self) -> Vec<[f64; 4]> {
+        self.polygons
+            .clone()
+            .map(|polygon| {
+                let exterior = self.data.word(Section::Polygons, polygon, 0) as usize;
+                [ring::MIN_X, ring::MIN_Y, ring::MAX_X, ring::MAX_Y].map(|field| {
+                    f64::from(self.data.signed(Section::Rings, exterior, field)) / SCALE
+                })
+            })
+            .collect()
+    }
+}
+
+/// Accumulates the sections of a snapshot being written.
+#[derive(Default)]
+struct SnapshotWriter {
+    sections: [Vec<u8>; SECTION_COUNT],
+}
+
+impl SnapshotWriter {
+    fn section(&mut self, section: Section) -> &mut Vec<u8> {
+        &mut self.sections[section as usize]
+    }
+
+    /// Number of records written to `section` so far.
+    fn count(&self, section: Section) -> u32 {
+        let len = self.sections[section as usize].len() / (4 * section.record_words().max(1));
+        u32::try_from(len).expect("snapshot section exceeds u32 records")
+    }
+
+    fn push(&mut self, section: Section, words: &[u32]) {
+        let out = self.section(section);
+        for word in words {
+            out.extend_from_slice(&word.to_le_bytes());
+        }
+    }
+
+    fn push_str(&mut self, s: &str) -> [u32; 2] {
+        let out = self.section(Section::Strings);
+        let offset = u32::try_from(out.len()).expect("snapshot strings exceed 4 GiB");
+        out.extend_from_slice(s.as_bytes());
+        [
+            offset,
+            u32::try_from(s.len()).expect("string exceeds 4 GiB"),
+        ]
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    fn push_ring(&mut self, ring: &[(i32, i32)], indexed: bool) {
+        let point_start = self.count(Section::Points);
+        for &(x, y) in ring {
+            self.push(Section::Points, &[x as u32, y as u32]);
+        }
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (i32::MAX, i32::MAX, i32::MIN, i32::MIN);
+        for &(x, y) in ring {
+            (min_x, min_y) = (min_x.min(x), min_y.min(y));
+            (max_x, max_y) = (max_x.max(x), max_y.max(y));
+        }
+        if ring.is_empty() {
+            (min_x, min_y, max_x, max_y) = (0, 0, -1, -1);
+        }
+
+        let n = ring.len();
+        let stripe_start = self.count(Section::StripeOffsets);
+        let (stripe_count, stripe_height) = if indexed && n > 0 {
+            let span = i64::from(max_y) - i64::from(min_y);
+            let count = (n / EDGES_PER_STRIPE).clamp(1, MAX_STRIPES);
+            // `count * height > span`, so every y in the ring maps to a stripe.
+            let height = span / count as i64 + 1;
+            let stripe_of = |y: i32| ((i64::from(y) - i64::from(min_y)) / height) as usize;
+            let mut stripes: Vec<Vec<u32>> = vec![Vec::new(); count];
+            for edge in 0..n {
+                let (ay, by) = (ring[edge].1, ring[(edge + 1) % n].1);
+                for stripe in &mut stripes[stripe_of(ay.min(by))..=stripe_of(ay.max(by))] {
+                    stripe.push(edge as u32);
+                }
+            }
+            let mut offset = self.count(Section::StripeEdges);
+            self.push(Section::StripeOffsets, &[offset]);
+            for stripe in &stripes {
+                self.push(Section::StripeEdges, stripe);
+                offset += stripe.len() as u32;
+                self.push(Section::StripeOffsets, &[offset]);
+            }
+            (count as u32, height as u32)
+        } else {
+            (0, 0)
+        };
+
+        self.push(
+            Section::Rings,
+            &[
+                point_start,
+                n as u32,
+                stripe_start,
+                stripe_count,
+                min_x as u32,
+                min_y as u32,
+                max_x as u32,
+                max_y as u32,
+                stripe_height,
+                0,
+            ],
+        );
+    }
+
+    fn write(
+        self,
+        version: &str,
+        grid: Option<&Grid>,
+        options: FinderOptions,
+        w: &mut impl Write,
+    ) -> io::Result<()> {
+        let mut header = Vec::with_capacity(HEADER_LEN);
+        header.extend_from_slice(&MAGIC);
+        header.extend_from_slice(&SNAPSHOT_FORMAT_VERSION.to_le_bytes());
+        header.extend_from_slice(&(if grid.is_some() { FLAG_GRID } else { 0 }).to_le_bytes());
+        let version_len = u32::try_from(version.len()).expect("data version exceeds 4 GiB");
+        header.extend_from_slice(&version_len.to_le_bytes());
+        let cells_per_degree = grid.map_or(1, |grid| grid.cells_per_degree);
+        header.extend_from_slice(&u32::from(cells_per_degree).to_le_bytes());
+        let (mode, parameter) = encode_index(options.index);
+        let min_segments = u32::try_from(options.min_index_segments).unwrap_or(u32::MAX);
+        for word in [mode, parameter, min_segments, 0] {
+            header.extend_from_slice(&word.to_le_bytes());
+        }
+
+        let padded = |len: usize| len.next_multiple_of(ALIGN);
+        let mut offset = HEADER_LEN;
+        for section in &self.sections {
+            header.extend_from_slice(&(offset as u64).to_le_bytes());
+            header.extend_from_slice(&(section.len() as u64).to_le_bytes());
+            offset += padded(section.len());
+        }
+        w.write_all(&header)?;
+        for section in &self.sections {
+            w.write_all(section)?;
+            w.write_all(&[0; ALIGN][..padded(section.len()) - section.len()])?;
+        }
+        Ok(())
+    }
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn write_snapshot(&self, w: &mut impl Write) -> io::Result<()> {
+        let mut out = SnapshotWriter::default();
+        out.push_str(&self.data_version);
+        for zone in &self.all {
+            let name = out.push_str(zone.name());
+            let polygon_start = out.count(Section::Polygons);
+            let polygons = zone.polygons();
+            for rings in &polygons {
+                let ring_start = out.count(Section::Rings);
+                for ring in rings {
+                    let scaled: Vec<(i32, i32)> = ring
+                        .iter()
+                        .map(|&(lng, lat)| {
+                            ((lng * SCALE).round() as i32, (lat * SCALE).round() as i32)
+                        })
+                        .collect();
+                    out.push_ring(&scaled, self.options.indexes(scaled.len()));
+                }
+                out.push(Section::Polygons, &[ring_start, rings.len() as u32]);
+            }
+            out.push(
+                Section::Zones,
+                &[name[0], name[1], polygon_start, polygons.len() as u32],
+            );
+        }
+
+        if let Some(grid) = &self.grid {
+            let mut cells: Vec<_> = grid.cells.iter().collect();
+            cells.sort_unstable_by_key(|&(&(lng, lat), _)| (lat, lng));
+            for (&(lng, lat), candidates) in cells {
+                let start = out.count(Section::GridCandidates);
+                out.push(Section::GridCandidates, candidates);
+                let covered = if grid.is_covered((lng, lat), &self.all) {
+                    CELL_COVERED
+                } else {
+                    0
+                };
+                out.push(
+                    Section::GridCells,
+                    &[
+                        i32::from(lng) as u32,
+                        i32::from(lat) as u32,
+                        start,
+                        candidates.len() as u32,
+                        covered,
+                        0,
+                    ],
+                );
+            }
+        }
+        out.write(&self.data_version, self.grid.as_ref(), self.options, w)
+    }
+}
+
+impl Finder {
+    /// Write this finder as a versioned binary snapshot that
+    /// [`Finder::from_snapshot`] loads without decoding or index building.
+    ///
+    /// Coordinates are stored at the 1e-5° precision of the compressed topo
+    /// data; a finder built from float protobuf data is rounded to it. Rings
+    /// that the finder's [`FinderOptions`] index get a Y-stripes index,
+    /// whichever index the options chose, and the options' index mode is
+    /// stored so a loaded snapshot writes the same bytes again.
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// let mut snapshot = Vec::new();
+    /// finder.write_snapshot(&mut snapshot).unwrap();
+    ///
+    /// let restored = Finder::from_snapshot(&snapshot).unwrap();
+    /// assert_eq!("Asia/Tokyo", restored.get_tz_name(139.4382, 36.4432));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `w`.
+    pub fn write_snapshot(&self, w: &mut impl Write) -> io::Result<()> {
+        with_core!(self, core => core.write_snapshot(w))
+    }
+
+    /// Load a snapshot written by [`Finder::write_snapshot`], copying it into
+    /// a single owned buffer. Use [`Finder::from_snapshot_buffer`] to query a
+    /// buffer you already own, such as a memory map, without the copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnsupportedSnapshotVersion`] for snapshots of another
+    /// format version and [`Error::InvalidSnapshot`] for corrupt ones.
+    pub fn from_snapshot(bytes: &[u8]) -> Result<Self, Error> {
+        Self::from_snapshot_buffer(bytes.to_vec())
+    }
+
+    /// Like [`Finder::from_snapshot`], but queries `buffer` in place.
+    ///
+    /// # Errors
+    ///
+    /// See [`Finder::from_snapshot`].
+    pub fn from_snapshot_buffer(
+        buffer: impl AsRef<[u8]> + Send + Sync + 'static,
+    ) -> Result<Self, Error> {
+        let data = Arc::new(SnapshotData::validate(Box::new(buffer))?);
+        let version_len = read_u32(data.bytes(), 16) as usize;
+        let data_version = data.string(0, version_len).unwrap_or_default().to_string();
+
+        let all: Vec<SnapshotZone> = (0..data.len(Section::Zones))
+            .map(|zone| {
+                let name = data.range(Section::Zones, zone, 0);
+                SnapshotZone {
+                    name: data
+                        .string(name.start, name.len())
+                        .unwrap_or_default()
+                        .to_string(),
+                    polygons: data.range(Section::Zones, zone, 2),
+                    data: Arc::clone(&data),
+                }
+            })
+            .collect();
+
+        let grid = (read_u32(data.bytes(), 12) & FLAG_GRID != 0).then(|| {
+            let mut grid = Vec::with_capacity(data.len(Section::GridCells));
+            for cell in 0..data.len(Section::GridCells) {
+                // Validated to lie in -180n..180n and -90n..90n.
+                #[allow(clippy::cast_possible_truncation)]
+                let key = (
+                    data.signed(Section::GridCells, cell, 0) as i16,
+                    data.signed(Section::GridCells, cell, 1) as i16,
+                );
+                let candidates = data
+                    .range(Section::GridCells, cell, 2)
+                    .map(|k| data.word(Section::GridCandidates, k, 0))
+                    .collect();
+                let covered = data.word(Section::GridCells, cell, 4) & CELL_COVERED != 0;
+                grid.push((key, candidates, covered));
+            }
+            Grid::new(data.cells_per_degree(), grid)
+        });
+
+        Ok(Self {
+            inner: FinderKind::Snapshot(
+                FinderCore::new(all, data_version, grid).with_options(data.options()),
+            ),
+        })
+    }
+
+    /// Memory-map a snapshot file and query it in place; see
+    /// [`Finder::from_snapshot_buffer`].
+    ///
+    /// The file must not be modified or truncated while the finder is alive.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Io`] if the file cannot be mapped, otherwise see
+    /// [`Finder::from_snapshot`].
+    #[cfg(feature = "mmap")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "mmap")))]
+    pub fn from_snapshot_path(path: impl AsRef<std::path::Path>) -> Result<Self, Error> {
+        use crate::load::io_error;
+
+        let path = path.as_ref();
+        let file = std::fs::File::open(path).map_err(io_error(path))?;
+        // SAFETY: the map is read-only and owned by the finder; the caller
+        // keeps the file unchanged for the finder's lifetime, as documented.
+        let map = unsafe { memmap2::Mmap::map(&file) }.map_err(io_error(path))?;
+        Self::from_snapshot_buffer(map)
+    }
+}
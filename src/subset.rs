@@ -0,0 +1,155 @@
+//! Regional subsets of a [`Finder`].
+
+use crate::grid::Grid;
+use crate::{Finder, FinderCore, FinderOptions, Zone};
+use std::collections::BTreeSet;
+
+/// A query box in degrees; `min_lng > max_lng` crosses the antimeridian.
+struct LngLatBox {
+    min_lng: f64,
+    min_lat: f64,
+    max_lng: f64,
+    max_lat: f64,
+}
+
+impl LngLatBox {
+    /// Whether `[min_lng, min_lat, max_lng, max_lat]` overlaps the box.
+    fn overlaps(&self, [min_lng, min_lat, max_lng, max_lat]: [f64; 4]) -> bool {
+        let lat = min_lat <= self.max_lat && max_lat >= self.min_lat;
+        let lng = if self.min_lng <= self.max_lng {
+            min_lng <= self.max_lng && max_lng >= self.min_lng
+        } else {
+            max_lng >= self.min_lng || min_lng <= self.max_lng
+        };
+        lat && lng
+    }
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    /// Flags items with a polygon whose bounding box overlaps `bbox`. With a
+    /// grid, only candidates of the cells overlapping `bbox` are considered.
+    fn keep_in_bbox(&self, bbox: &LngLatBox) -> Vec<bool> {
+        let mut candidates = vec![self.grid.is_none(); self.all.len()];
+        if let Some(grid) = &self.grid {
+            for (&key, indices) in &grid.cells {
+                if bbox.overlaps(grid.cell_bounds(key)) {
+                    for &idx in indices {
+                        candidates[idx as usize] = true;
+                    }
+                }
+            }
+        }
+        self.all
+            .iter()
+            .zip(candidates)
+            .map(|(item, candidate)| {
+                candidate && item.bounds().into_iter().any(|b| bbox.overlaps(b))
+            })
+            .collect()
+    }
+
+    fn keep_named(&self, names: &[&str]) -> Vec<bool> {
+        self.all
+            .iter()
+            .map(|item| names.contains(&item.name()))
+            .collect()
+    }
+}
+
+impl<Z: Zone + Clone> FinderCore<Z> {
+    /// The items flagged in `keep`, sharing their geometry, with the grid
+    /// remapped to the new item indices.
+    #[allow(clippy::cast_possible_truncation)]
+    fn subset(&self, keep: &[bool]) -> Self {
+        let mut remap: Vec<Option<u32>> = vec![None; self.all.len()];
+        let mut all = Vec::new();
+        for (idx, item) in self.all.iter().enumerate() {
+            if keep[idx] {
+                remap[idx] = Some(all.len() as u32);
+                all.push(item.clone());
+            }
+        }
+
+        let grid = self.grid.as_ref().map(|grid| {
+            // Zones left alone in a cell that lost others, or whose coverage
+            // the source grid defers, get theirs worked out on first use.
+            let mut deferred = BTreeSet::new();
+            let cells: Vec<_> = grid
+                .cells
+                .iter()
+                .filter_map(|(&key, indices)| {
+                    let kept: Vec<u32> =
+                        indices.iter().filter_map(|&i| remap[i as usize]).collect();
+                    let alone = kept.len() == 1;
+                    let defer = alone && (indices.len() > 1 || grid.is_deferred(indices[0]));
+                    if defer {
+                        deferred.insert(kept[0]);
+                    }
+                    // Empty cells are dropped, which keeps exported grids small.
+                    let covered = alone && !defer && grid.is_covered(key, &self.all);
+                    (!kept.is_empty()).then_some((key, kept, covered))
+                })
+                .collect();
+            let mut subset = Grid::new(grid.cells_per_degree, cells);
+            subset.defer_coverage(deferred);
+            subset
+        });
+        // Subsets keep the grid only, not the quadtree.
+        let options = FinderOptions {
+            quadtree: None,
+            ..self.options
+        };
+        Self::new(all, self.data_version.clone(), grid).with_options(options)
+    }
+}
+
+impl Finder {
+    /// A finder holding only the timezones with a polygon whose bounding box
+    /// overlaps the given box, for deployments that only ever see coordinates
+    /// in one region. A box with `min_lng > max_lng` crosses the antimeridian.
+    ///
+    /// The subset shares polygon data with `self`; drop `self` to release
+    /// the rest. Points outside every kept timezone resolve to `""`, as they
+    /// would for any finder, and [`Finder::write_compressed_topo`] stores the
+    /// subset as a smaller data file.
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let europe = Finder::new().subset_by_bbox(-25.0, 34.0, 45.0, 72.0);
+    /// assert_eq!("Europe/Paris", europe.get_tz_name(2.3522, 48.8566));
+    /// assert!(!europe.timezonenames().contains(&"Asia/Tokyo"));
+    /// ```
+    #[must_use]
+    pub fn subset_by_bbox(&self, min_lng: f64, min_lat: f64, max_lng: f64, max_lat: f64) -> Self {
+        let bbox = LngLatBox {
+            min_lng,
+            min_lat,
+            max_lng,
+            max_lat,
+        };
+        let keep = with_core!(self, core => core.keep_in_bbox(&bbox));
+        self.subset(&keep)
+    }
+
+    /// A finder holding only the named timezones; unknown names are ignored.
+    /// See [`Finder::subset_by_bbox`].
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new().subset_by_names(&["Europe/Berlin", "Europe/Paris"]);
+    /// assert_eq!("Europe/Berlin", finder.get_tz_name(13.405, 52.52));
+    /// assert_eq!("", finder.get_tz_name(139.4382, 36.4432));
+    /// ```
+    #[must_use]
+    pub fn subset_by_names(&self, names: &[&str]) -> Self {
+        let keep = with_core!(self, core => core.keep_named(names));
+        self.subset(&keep)
+    }
+
+    fn subset(&self, keep: &[bool]) -> Self {
+        let inner = map_core!(self, core => core.subset(keep));
+        Self { inner }
+    }
+}
@@ -0,0 +1,372 @@
+//! Writing `CompressedTopoTimezones` data: shared-edge detection, polyline
+//! encoding and an embedded grid index.
+
+#[cfg(feature = "export-geojson")]
+use crate::BoundaryFile;
+use crate::grid::build_grid;
+use crate::{Finder, FinderCore, PolygonRings, Zone, encode_polyline, pbgen};
+use geometry_rs::I32Point;
+use prost::Message;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+/// A 1e5-scaled `(lng, lat)` vertex.
+type Vertex = (i32, i32);
+
+/// A closed ring of distinct consecutive vertices.
+type Ring = Vec<Vertex>;
+
+/// A timezone's polygons as rings, exterior first.
+type TopoZone = (String, Vec<Vec<Ring>>);
+
+/// Round a ring to 1e-5°, drop repeated vertices and close it. `None` for
+/// rings with fewer than three distinct vertices.
+#[allow(clippy::cast_possible_truncation)]
+fn quantize_ring(ring: impl IntoIterator<Item = (f64, f64)>) -> Option<Ring> {
+    let mut out: Ring = Vec::new();
+    for (lng, lat) in ring {
+        let v = ((lng * 1e5).round() as i32, (lat * 1e5).round() as i32);
+        if out.last() != Some(&v) {
+            out.push(v);
+        }
+    }
+    if out.len() > 1 && out.first() == out.last() {
+        out.pop();
+    }
+    if out.len() < 3 {
+        return None;
+    }
+    out.push(out[0]);
+    Some(out)
+}
+
+/// Quantize a polygon's rings; a degenerate exterior drops the polygon and
+/// degenerate holes are skipped.
+fn quantize_polygon(rings: impl IntoIterator<Item = Vec<(f64, f64)>>) -> Option<Vec<Ring>> {
+    let mut rings = rings.into_iter();
+    let exterior = quantize_ring(rings.next()?)?;
+    Some(
+        std::iter::once(exterior)
+            .chain(rings.filter_map(quantize_ring))
+            .collect(),
+    )
+}
+
+fn to_degrees(polygons: &[Vec<Ring>]) -> Vec<PolygonRings> {
+    polygons
+        .iter()
+        .map(|rings| {
+            rings
+                .iter()
+                .map(|ring| {
+                    ring.iter()
+                        .map(|&(x, y)| (f64::from(x) / 1e5, f64::from(y) / 1e5))
+                        .collect()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn encode_vertices(points: &[Vertex]) -> Vec<u8> {
+    let points: Vec<I32Point> = points.iter().map(|&(x, y)| I32Point { x, y }).collect();
+    encode_polyline(&points)
+}
+
+/// Undirected key of the segment `a`–`b`.
+fn segment_key(a: Vertex, b: Vertex) -> (Vertex, Vertex) {
+    if a <= b { (a, b) } else { (b, a) }
+}
+
+/// The shared-edge library being built, deduplicated by point sequence.
+#[derive(Default)]
+struct SharedEdges {
+    edges: Vec<pbgen::CompressedSharedEdge>,
+    ids: HashMap<Vec<Vertex>, i32>,
+}
+
+impl SharedEdges {
+    /// A segment for the arc `points`: a reference to the shared edge it
+    /// matches in either direction, a new shared edge, or inline points.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    fn segment(&mut self, points: Vec<Vertex>, shared: bool) -> pbgen::CompressedRingSegment {
+        use pbgen::compressed_ring_segment::Content;
+        let content = if shared {
+            let reversed: Vec<Vertex> = points.iter().rev().copied().collect();
+            if let Some(&id) = self.ids.get(&points) {
+                Content::EdgeForward(id)
+            } else if let Some(&id) = self.ids.get(&reversed) {
+                Content::EdgeReversed(id)
+            } else {
+                let id = self.edges.len() as i32;
+                self.edges.push(pbgen::CompressedSharedEdge {
+                    id,
+                    points: encode_vertices(&points),
+                });
+                self.ids.insert(points, id);
+                Content::EdgeForward(id)
+            }
+        } else {
+            Content::Inline(pbgen::CompressedInlinePoints {
+                points: encode_vertices(&points),
+            })
+        };
+        pbgen::CompressedRingSegment {
+            content: Some(content),
+        }
+    }
+
+    /// Split a ring into arcs at the vertices where the set of rings using
+    /// the adjacent segments changes, sharing every arc used by more than
+    /// one ring. Each arc keeps both end vertices, as the decoder expects.
+    fn encode_ring(
+        &mut self,
+        ring: &[Vertex],
+        users: &HashMap<(Vertex, Vertex), Vec<u32>>,
+    ) -> Vec<pbgen::CompressedRingSegment> {
+        let n = ring.len() - 1;
+        let sig = |i: usize| &users[&segment_key(ring[i], ring[i + 1])];
+        let nodes: Vec<usize> = (0..n).filter(|&i| sig((i + n - 1) % n) != sig(i)).collect();
+        let Some(&start) = nodes.first() else {
+            // One arc around the whole ring: start it at its smallest vertex
+            // so every ring tracing it agrees on the point sequence.
+            let min = (0..n).min_by_key(|&i| ring[i]).unwrap_or(0);
+            let arc = (0..=n).map(|k| ring[(min + k) % n]).collect();
+            return vec![self.segment(arc, sig(min).len() > 1)];
+        };
+        let mut bounds = nodes;
+        bounds.push(start + n);
+        bounds
+            .windows(2)
+            .map(|w| {
+                let arc = (w[0]..=w[1]).map(|k| ring[k % n]).collect();
+                self.segment(arc, sig(w[0] % n).len() > 1)
+            })
+            .collect()
+    }
+}
+
+/// Encode timezones, detecting edges shared between rings.
+#[allow(clippy::cast_possible_truncation)]
+fn encode_timezones(
+    timezones: &[TopoZone],
+) -> (
+    Vec<pbgen::CompressedSharedEdge>,
+    Vec<pbgen::CompressedTopoTimezone>,
+) {
+    // Rings using each undirected segment, in ring order.
+    let mut users: HashMap<(Vertex, Vertex), Vec<u32>> = HashMap::new();
+    let rings = timezones
+        .iter()
+        .flat_map(|(_, polygons)| polygons.iter().flatten());
+    for (id, ring) in rings.enumerate() {
+        for seg in ring.windows(2) {
+            users
+                .entry(segment_key(seg[0], seg[1]))
+                .or_default()
+                .push(id as u32);
+        }
+    }
+
+    let mut shared = SharedEdges::default();
+    let timezones = timezones
+        .iter()
+        .map(|(name, polygons)| pbgen::CompressedTopoTimezone {
+            polygons: polygons
+                .iter()
+                .map(|rings| {
+                    let mut rings = rings.iter().map(|ring| shared.encode_ring(ring, &users));
+                    pbgen::CompressedTopoPolygon {
+                        exterior: rings.next().unwrap_or_default(),
+                        holes: rings
+                            .map(|exterior| pbgen::CompressedTopoPolygon {
+                                exterior,
+                                holes: Vec::new(),
+                            })
+                            .collect(),
+                    }
+                })
+                .collect(),
+            name: name.clone(),
+        })
+        .collect();
+    (shared.edges, timezones)
+}
+
+/// Builds `CompressedTopoTimezones` data, the format of the bundled
+/// `*.topology.compress.topo.bin` files, from timezone boundaries.
+///
+/// Coordinates are rounded to 1e-5°. Edges shared between neighbouring rings
+/// are stored once and referenced from both, other runs are stored inline,
+/// all polyline-encoded; a 1°×1° [`pbgen::GridIndex`] is embedded unless
+/// disabled with [`CompressedTopoBuilder::grid_index`].
+///
+/// ```rust
+/// use tzf_rs::{CompressedTopoBuilder, Finder};
+///
+/// let square = |min_lng: f64| {
+///     vec![vec![vec![
+///         [min_lng, 0.0],
+///         [min_lng + 1.0, 0.0],
+///         [min_lng + 1.0, 1.0],
+///         [min_lng, 1.0],
+///         [min_lng, 0.0],
+///     ]]]
+/// };
+/// let mut builder = CompressedTopoBuilder::new("custom-1");
+/// builder.add_timezone("West", &square(0.0));
+/// builder.add_timezone("East", &square(1.0));
+/// let topo = builder.build();
+/// assert_eq!(1, topo.shared_edges.len());
+///
+/// let finder = Finder::try_from_compressed_topo(topo).unwrap();
+/// assert_eq!("East", finder.get_tz_name(1.5, 0.5));
+/// ```
+pub struct CompressedTopoBuilder {
+    version: String,
+    grid_index: bool,
+    timezones: Vec<TopoZone>,
+}
+
+impl CompressedTopoBuilder {
+    #[must_use]
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            grid_index: true,
+            timezones: Vec::new(),
+        }
+    }
+
+    /// Whether to embed a grid index; on by default.
+    #[must_use]
+    pub fn grid_index(mut self, enabled: bool) -> Self {
+        self.grid_index = enabled;
+        self
+    }
+
+    /// Add a timezone from GeoJSON-style `MultiPolygon` coordinates: polygons
+    /// of `[lng, lat]` rings, exterior first. Rings may be open or closed;
+    /// rings with fewer than three distinct points are skipped.
+    pub fn add_timezone(
+        &mut self,
+        name: impl Into<String>,
+        polygons: &[Vec<Vec<[f64; 2]>>],
+    ) -> &mut Self {
+        let polygons = polygons
+            .iter()
+            .filter_map(|rings| {
+                quantize_polygon(
+                    rings
+                        .iter()
+                        .map(|ring| ring.iter().map(|&[lng, lat]| (lng, lat)).collect()),
+                )
+            })
+            .collect();
+        self.timezones.push((name.into(), polygons));
+        self
+    }
+
+    /// Add every feature of a `BoundaryFile`, e.g. the output of
+    /// [`Finder::to_geojson`] after custom edits, named by its `tzid`.
+    #[cfg(feature = "export-geojson")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "export-geojson")))]
+    pub fn add_boundary_file(&mut self, file: &BoundaryFile) -> &mut Self {
+        for feature in &file.features {
+            self.add_timezone(
+                feature.properties.tzid.clone(),
+                &feature.geometry.coordinates,
+            );
+        }
+        self
+    }
+
+    /// Encode the timezones added so far, in insertion order.
+    #[must_use]
+    pub fn build(&self) -> pbgen::CompressedTopoTimezones {
+        let (shared_edges, timezones) = encode_timezones(&self.timezones);
+        let grid_index = self
+            .grid_index
+            .then(|| {
+                let zones = self
+                    .timezones
+                    .iter()
+                    .map(|(_, polygons)| to_degrees(polygons));
+                build_grid(1, zones).to_grid_index(&self.version)
+            })
+            .flatten();
+        pbgen::CompressedTopoTimezones {
+            method: pbgen::CompressMethod::Polyline as i32,
+            shared_edges,
+            timezones,
+            version: self.version.clone(),
+            grid_index,
+        }
+    }
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    fn to_compressed_topo(&self) -> pbgen::CompressedTopoTimezones {
+        let zones: Vec<TopoZone> = self
+            .all
+            .iter()
+            .map(|item| {
+                let polygons = item.polygons().into_iter().filter_map(quantize_polygon);
+                (item.name().to_string(), polygons.collect())
+            })
+            .collect();
+        let (shared_edges, timezones) = encode_timezones(&zones);
+        pbgen::CompressedTopoTimezones {
+            method: pbgen::CompressMethod::Polyline as i32,
+            shared_edges,
+            timezones,
+            version: self.data_version.clone(),
+            // The format only holds 1°×1° cells; a finer grid is rebuilt.
+            grid_index: self.grid.as_ref().and_then(|grid| {
+                grid.to_grid_index(&self.data_version).or_else(|| {
+                    build_grid(1, self.all.iter().map(Zone::polygons))
+                        .to_grid_index(&self.data_version)
+                })
+            }),
+        }
+    }
+}
+
+impl Finder {
+    /// Encode this finder's timezones and grid as `CompressedTopoTimezones`,
+    /// which [`Finder::from_compressed_topo`] loads back. Coordinates are
+    /// rounded to 1e-5° and shared edges are detected as in
+    /// [`CompressedTopoBuilder`]. A 1°×1° grid is kept as is; a finer one
+    /// (see [`crate::FinderOptions::grid_resolution`]) is rebuilt at 1°.
+    #[must_use]
+    pub fn to_compressed_topo(&self) -> pbgen::CompressedTopoTimezones {
+        with_core!(self, core => core.to_compressed_topo())
+    }
+
+    /// Write [`Finder::to_compressed_topo`] as an encoded data file, e.g. a
+    /// regional subset for devices with little memory.
+    ///
+    /// Load the file with [`Finder::from_compressed_topo`] or
+    /// [`Finder::try_from_compressed_topo`], not
+    /// [`DefaultFinder::from_paths`](crate::DefaultFinder::from_paths): the
+    /// preindex that `DefaultFinder` pairs with it covers the whole world,
+    /// and would answer with zones the subset dropped.
+    ///
+    /// ```rust
+    /// use tzf_rs::{Finder, pbgen::CompressedTopoTimezones};
+    ///
+    /// let europe = Finder::new().subset_by_bbox(-25.0, 34.0, 45.0, 72.0);
+    /// let mut topo = Vec::new();
+    /// europe.write_compressed_topo(&mut topo).unwrap();
+    ///
+    /// let loaded = Finder::from_compressed_topo(CompressedTopoTimezones::try_from(topo).unwrap());
+    /// assert_eq!("Europe/Paris", loaded.get_tz_name(2.3522, 48.8566));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from `w`.
+    pub fn write_compressed_topo(&self, w: &mut impl Write) -> io::Result<()> {
+        w.write_all(&self.to_compressed_topo().encode_to_vec())
+    }
+}
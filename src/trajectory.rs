@@ -0,0 +1,213 @@
+//! Timezone transitions along a path of timestamped points.
+//!
+//! Between consecutive points the path runs straight in `(lng, lat)`, the
+//! same space polygon edges live in, so every border crossing is found
+//! exactly by intersecting the step with the candidate zones' edges. The
+//! pieces between crossings are then classified by a lookup at their
+//! midpoints, which never lie on a border unless the path follows one.
+
+use crate::distance::wrap_lng;
+use crate::{Finder, FinderCore, Zone, on_antimeridian};
+
+/// A `(lng, lat, t)` point of a trajectory.
+type Sample = (f64, f64, f64);
+
+/// Distance along a step, in degrees, under which two crossings are merged:
+/// about 0.1 mm.
+const MERGE_DEG: f64 = 1e-9;
+
+/// A stretch of a trajectory spent in the same timezones; see
+/// [`Finder::transitions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneSegment<'a> {
+    /// The zone [`get_tz_name`](crate::Finder::get_tz_name) returns along
+    /// the segment, or `""` outside every zone.
+    pub name: &'a str,
+    /// Every zone claiming the segment, `name` first, as
+    /// [`get_tz_names`](crate::Finder::get_tz_names) returns them. More than
+    /// one only where zones overlap or the path runs along a border.
+    pub names: Vec<&'a str>,
+    /// Where and when the segment starts, as `(lng, lat, t)`: the first
+    /// point, or the border crossing that entered it.
+    pub start: (f64, f64, f64),
+    /// Where and when the segment ends: the border crossing that left it,
+    /// or the last point.
+    pub end: (f64, f64, f64),
+}
+
+/// Fold a longitude into `[-180, 180]`, keeping both ends of the range.
+fn normalize_lng(lng: f64) -> f64 {
+    if (-180.0..=180.0).contains(&lng) {
+        lng
+    } else {
+        wrap_lng(lng)
+    }
+}
+
+fn cross(a: (f64, f64), b: (f64, f64)) -> f64 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+/// Parameters in `[0, 1]` along `a`–`b` where it meets the edge `c`–`d`:
+/// one for a crossing, the ends of the shared stretch for collinear edges.
+fn intersections(a: (f64, f64), b: (f64, f64), c: (f64, f64), d: (f64, f64), out: &mut Vec<f64>) {
+    let r = (b.0 - a.0, b.1 - a.1);
+    let s = (d.0 - c.0, d.1 - c.1);
+    let ac = (c.0 - a.0, c.1 - a.1);
+    let denom = cross(r, s);
+    if denom == 0.0 {
+        if cross(ac, r) != 0.0 {
+            return;
+        }
+        // Collinear: where the edge's ends project onto the step, clamped.
+        let len2 = r.0 * r.0 + r.1 * r.1;
+        let t0 = (ac.0 * r.0 + ac.1 * r.1) / len2;
+        let t1 = t0 + (s.0 * r.0 + s.1 * r.1) / len2;
+        let (lo, hi) = (t0.min(t1).max(0.0), t0.max(t1).min(1.0));
+        if lo <= hi {
+            out.extend([lo, hi]);
+        }
+        return;
+    }
+    let t = cross(ac, s) / denom;
+    let u = cross(ac, r) / denom;
+    if (0.0..=1.0).contains(&t) && (0.0..=1.0).contains(&u) {
+        out.push(t);
+    }
+}
+
+impl<Z: Zone> FinderCore<Z> {
+    /// Parameters in `[0, 1]` along `a`–`b`, both within `[-180, 180]`,
+    /// where it meets a zone edge.
+    fn crossings(&self, a: (f64, f64), b: (f64, f64), out: &mut Vec<f64>) {
+        let bbox = [a.0.min(b.0), a.1.min(b.1), a.0.max(b.0), a.1.max(b.1)];
+        let overlaps = |other: [f64; 4]| {
+            other[0] <= bbox[2] && bbox[0] <= other[2] && other[1] <= bbox[3] && bbox[1] <= other[3]
+        };
+        for idx in self.candidates_in_bbox(bbox) {
+            self.all[idx].for_each_edge_in(bbox, |c, d| {
+                if overlaps([c.0.min(d.0), c.1.min(d.1), c.0.max(d.0), c.1.max(d.1)]) {
+                    intersections(a, b, c, d, out);
+                }
+            });
+        }
+    }
+
+    /// Append the zone pieces of the step `p`–`q` to `segments`, merging
+    /// with the last one where the zones carry on.
+    fn push_step<'a>(&'a self, p: Sample, q: Sample, segments: &mut Vec<ZoneSegment<'a>>) {
+        let lng0 = normalize_lng(p.0);
+        let dlng = wrap_lng(normalize_lng(q.0) - lng0);
+        let dlat = q.1 - p.1;
+        // Start on the side of the antimeridian the step heads into.
+        let lng0 = match lng0 {
+            180.0 if dlng > 0.0 => -180.0,
+            -180.0 if dlng < 0.0 => 180.0,
+            _ => lng0,
+        };
+        let at = |t: f64| {
+            (
+                normalize_lng(lng0 + dlng * t),
+                p.1 + dlat * t,
+                p.2 + (q.2 - p.2) * t,
+            )
+        };
+
+        let len = dlng.hypot(dlat);
+        let mut cuts = vec![0.0, 1.0];
+        if len > 0.0 {
+            // Split where the step crosses the antimeridian.
+            let lng1 = lng0 + dlng;
+            let pieces = if lng1 > 180.0 || lng1 < -180.0 {
+                let edge = 180.0_f64.copysign(dlng);
+                let t = (edge - lng0) / dlng;
+                cuts.push(t);
+                vec![(0.0, t, lng0), (t, 1.0, lng0 - 2.0 * edge)]
+            } else {
+                vec![(0.0, 1.0, lng0)]
+            };
+            for (t0, t1, offset) in pieces {
+                let a = (offset + dlng * t0, p.1 + dlat * t0);
+                let b = (offset + dlng * t1, p.1 + dlat * t1);
+                let first = cuts.len();
+                self.crossings(a, b, &mut cuts);
+                if dlng == 0.0 && on_antimeridian(a.0) {
+                    // Running along the antimeridian: zones on either side.
+                    self.crossings((-a.0, a.1), (-b.0, b.1), &mut cuts);
+                }
+                for t in &mut cuts[first..] {
+                    *t = t0 + (t1 - t0) * *t;
+                }
+            }
+            cuts.sort_by(f64::total_cmp);
+            cuts.dedup_by(|later, earlier| (*later - *earlier) * len <= MERGE_DEG);
+            if let Some(last) = cuts.last_mut() {
+                *last = 1.0;
+            }
+        }
+
+        for cut in cuts.windows(2) {
+            let (t0, t1) = (cut[0], cut[1]);
+            let (lng, lat, _) = at((t0 + t1) / 2.0);
+            let names = self.get_tz_names(lng, lat);
+            let (start, end) = (at(t0), at(t1));
+            match segments.last_mut() {
+                Some(last) if same_zones(&last.names, &names) => last.end = end,
+                _ => segments.push(ZoneSegment {
+                    name: names.first().copied().unwrap_or(""),
+                    names,
+                    start,
+                    end,
+                }),
+            }
+        }
+    }
+
+    fn transitions(&self, points: &[Sample]) -> Vec<ZoneSegment<'_>> {
+        let mut segments = vec![];
+        match points {
+            [] => {}
+            [p] => self.push_step(*p, *p, &mut segments),
+            _ => {
+                for step in points.windows(2) {
+                    self.push_step(step[0], step[1], &mut segments);
+                }
+            }
+        }
+        segments
+    }
+}
+
+/// Whether two lookups found the same zones, in whatever order.
+fn same_zones(a: &[&str], b: &[&str]) -> bool {
+    a.len() == b.len() && a.iter().all(|name| b.contains(name))
+}
+
+impl Finder {
+    /// Split a trajectory of `(lng, lat, t)` points into stretches of
+    /// constant timezone.
+    ///
+    /// Consecutive points are joined by straight lines in `(lng, lat)`,
+    /// taking the short way across the antimeridian. Each border crossing
+    /// is the exact intersection of the path with the zone's polygon edge,
+    /// and its timestamp is interpolated linearly between the neighbouring
+    /// points. Points that only touch a border, without crossing it, start
+    /// no new segment; a trajectory that stays on a border, such as a
+    /// single point on one, reports every zone there, like
+    /// [`Finder::get_tz_names`].
+    ///
+    /// ```rust
+    /// use tzf_rs::Finder;
+    ///
+    /// let finder = Finder::new();
+    /// // Driving from Strasbourg to Kehl, across the Rhine.
+    /// let segments = finder.transitions(&[(7.7521, 48.5734, 0.0), (7.8150, 48.5690, 600.0)]);
+    /// let names: Vec<&str> = segments.iter().map(|segment| segment.name).collect();
+    /// assert_eq!(["Europe/Paris", "Europe/Berlin"], names[..]);
+    /// assert!(segments[0].end.2 > 0.0 && segments[0].end.2 < 600.0);
+    /// ```
+    #[must_use]
+    pub fn transitions(&self, points: &[(f64, f64, f64)]) -> Vec<ZoneSegment<'_>> {
+        with_core!(self, core => core.transitions(points))
+    }
+}
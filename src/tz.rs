@@ -0,0 +1,145 @@
+//! Typed timezone results and UTC offsets for the `chrono-tz` and `jiff`
+//! features.
+
+use crate::DefaultFinder;
+
+/// The UTC offset in effect at a place and instant; see
+/// [`DefaultFinder::offset_at`].
+#[cfg(feature = "jiff")]
+#[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalOffset<'a> {
+    /// The zone name, as [`DefaultFinder::get_tz_name`] returns it.
+    pub name: &'a str,
+    /// Seconds east of UTC, DST included.
+    pub offset_seconds: i32,
+    /// Whether daylight saving time is in effect.
+    pub is_dst: bool,
+    /// The zone abbreviation, such as `CST` or `-02`.
+    pub abbreviation: String,
+}
+
+/// Resolve a zone name from the dataset, falling back to a fixed offset for
+/// the `Etc/GMT±N` nautical zones when the tz database lacks them.
+#[cfg(feature = "jiff")]
+fn time_zone(name: &str) -> Option<jiff::tz::TimeZone> {
+    if name.is_empty() {
+        return None;
+    }
+    jiff::tz::TimeZone::get(name)
+        .ok()
+        .or_else(|| etc_gmt_offset(name).map(jiff::tz::TimeZone::fixed))
+}
+
+/// The offset of an `Etc/GMT±N` zone. POSIX-style signs are inverted:
+/// `Etc/GMT+2` is two hours *behind* UTC.
+#[cfg(feature = "jiff")]
+fn etc_gmt_offset(name: &str) -> Option<jiff::tz::Offset> {
+    let rest = name.strip_prefix("Etc/GMT")?;
+    let hours: i8 = if rest.is_empty() {
+        0
+    } else {
+        rest.parse().ok()?
+    };
+    if !(-14..=12).contains(&hours) {
+        return None;
+    }
+    jiff::tz::Offset::from_hours(-hours).ok()
+}
+
+impl DefaultFinder {
+    /// Like [`DefaultFinder::get_tz_name`], but parsed into a
+    /// [`chrono_tz::Tz`].
+    ///
+    /// Returns `None` when no zone is found, or when the zone is unknown to
+    /// the linked `chrono-tz` release.
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// assert_eq!(Some(chrono_tz::Asia::Shanghai), finder.get_tz(116.3883, 39.9289));
+    /// ```
+    #[cfg(feature = "chrono-tz")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "chrono-tz")))]
+    #[must_use]
+    pub fn get_tz(&self, lng: f64, lat: f64) -> Option<chrono_tz::Tz> {
+        self.get_tz_name(lng, lat).parse().ok()
+    }
+
+    /// Like [`DefaultFinder::get_tz_name`], but resolved into a
+    /// [`jiff::tz::TimeZone`] from jiff's time zone database.
+    ///
+    /// Returns `None` when no zone is found, or when the database has no
+    /// such zone.
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// let tz = finder.get_time_zone(139.4382, 36.4432).unwrap();
+    /// assert_eq!(Some("Asia/Tokyo"), tz.iana_name());
+    /// ```
+    #[cfg(feature = "jiff")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+    #[must_use]
+    pub fn get_time_zone(&self, lng: f64, lat: f64) -> Option<jiff::tz::TimeZone> {
+        time_zone(self.get_tz_name(lng, lat))
+    }
+
+    /// The UTC offset, DST flag and abbreviation in effect at the point at
+    /// `unix_seconds`.
+    ///
+    /// Returns `None` when no zone is found, the zone cannot be resolved, or
+    /// the timestamp is out of range. Over open ocean the dataset answers
+    /// with `Etc/GMT±N` zones, whose sign is inverted: `Etc/GMT+2` is UTC-2.
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// // 2024-07-01T00:00:00Z in Chicago is CDT, UTC-5.
+    /// let offset = finder.offset_at(-97.8674, 34.42, 1_719_792_000).unwrap();
+    /// assert_eq!("America/Chicago", offset.name);
+    /// assert_eq!(-5 * 3600, offset.offset_seconds);
+    /// assert!(offset.is_dst);
+    /// assert_eq!("CDT", offset.abbreviation);
+    ///
+    /// // Mid-Atlantic: Etc/GMT+2 is two hours behind UTC.
+    /// let offset = finder.offset_at(-30.5, 0.5, 1_719_792_000).unwrap();
+    /// assert_eq!(-2 * 3600, offset.offset_seconds);
+    /// ```
+    #[cfg(feature = "jiff")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+    #[must_use]
+    pub fn offset_at(&self, lng: f64, lat: f64, unix_seconds: i64) -> Option<LocalOffset<'_>> {
+        let name = self.get_tz_name(lng, lat);
+        let timestamp = jiff::Timestamp::from_second(unix_seconds).ok()?;
+        let info = time_zone(name)?.to_offset_info(timestamp);
+        Some(LocalOffset {
+            name,
+            offset_seconds: info.offset().seconds(),
+            is_dst: info.dst().is_dst(),
+            abbreviation: info.abbreviation().to_string(),
+        })
+    }
+
+    /// The local wall-clock time at the point at `unix_seconds`.
+    ///
+    /// Returns `None` in the same cases as [`DefaultFinder::offset_at`].
+    ///
+    /// ```rust
+    /// use tzf_rs::DefaultFinder;
+    ///
+    /// let finder = DefaultFinder::new();
+    /// let local = finder.local_time_at(116.3883, 39.9289, 1_719_792_000).unwrap();
+    /// assert_eq!("2024-07-01T08:00:00", local.datetime().to_string());
+    /// ```
+    #[cfg(feature = "jiff")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "jiff")))]
+    #[must_use]
+    pub fn local_time_at(&self, lng: f64, lat: f64, unix_seconds: i64) -> Option<jiff::Zoned> {
+        let timestamp = jiff::Timestamp::from_second(unix_seconds).ok()?;
+        Some(timestamp.to_zoned(self.get_time_zone(lng, lat)?))
+    }
+}
@@ -0,0 +1,198 @@
+#[cfg(test)]
+mod tests {
+    use tzf_rs::pbgen::{Point, Polygon, Timezone, Timezones};
+    use tzf_rs::{Finder, FinderOptions};
+
+    fn polygon(points: &[(f32, f32)]) -> Polygon {
+        Polygon {
+            points: points
+                .iter()
+                .map(|&(lng, lat)| Point { lng, lat })
+                .collect(),
+            holes: Vec::new(),
+        }
+    }
+
+    /// "Fiji" stored split at the antimeridian: [178,180]x[-18,-16] and
+    /// [-180,-179]x[-18,-16.5]. "Samoa" lies nearby on the western side.
+    fn timezones() -> Timezones {
+        Timezones {
+            timezones: vec![
+                Timezone {
+                    polygons: vec![
+                        polygon(&[
+                            (178.0, -18.0),
+                            (180.0, -18.0),
+                            (180.0, -16.0),
+                            (178.0, -16.0),
+                            (178.0, -18.0),
+                        ]),
+                        polygon(&[
+                            (-180.0, -18.0),
+                            (-179.0, -18.0),
+                            (-179.0, -16.5),
+                            (-180.0, -16.5),
+                            (-180.0, -18.0),
+                        ]),
+                    ],
+                    name: "Fiji".to_string(),
+                },
+                Timezone {
+                    polygons: vec![polygon(&[
+                        (-173.0, -15.0),
+                        (-171.0, -15.0),
+                        (-171.0, -13.0),
+                        (-173.0, -13.0),
+                        (-173.0, -15.0),
+                    ])],
+                    name: "Samoa".to_string(),
+                },
+            ],
+            reduced: false,
+            version: "antimeridian".to_string(),
+        }
+    }
+
+    #[test]
+    fn both_signs_of_the_antimeridian_agree() {
+        let finder = Finder::from_pb(timezones());
+        let finders = [
+            Finder::from_compressed_topo(finder.to_compressed_topo()),
+            Finder::from_pb_with_options(timezones(), FinderOptions::default().quadtree(1)),
+            finder,
+        ];
+        for finder in &finders {
+            for lat in [-17.9, -17.0, -16.6] {
+                assert_eq!(finder.get_tz_name(180.0, lat), "Fiji", "{lat}");
+                assert_eq!(finder.get_tz_name(-180.0, lat), "Fiji", "{lat}");
+                assert_eq!(
+                    finder.get_tz_names(180.0, lat),
+                    finder.get_tz_names(-180.0, lat),
+                    "{lat}"
+                );
+                assert_eq!(
+                    finder.lookup(180.0, lat),
+                    finder.lookup(-180.0, lat),
+                    "{lat}"
+                );
+            }
+            for lat in [-20.0, 0.0] {
+                assert_eq!(finder.get_tz_name(180.0, lat), "", "{lat}");
+                assert_eq!(finder.get_tz_name(-180.0, lat), "", "{lat}");
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_antimeridian_queries_agree() {
+        let finder = tzf_rs::DefaultFinder::new();
+        for lat in [-17.0, 52.0, 66.0, 68.5] {
+            assert_eq!(
+                finder.get_tz_name(180.0, lat),
+                finder.get_tz_name(-180.0, lat),
+                "{lat}"
+            );
+            assert_eq!(
+                finder.get_tz_names(180.0, lat),
+                finder.get_tz_names(-180.0, lat),
+                "{lat}"
+            );
+        }
+    }
+
+    #[cfg(feature = "export-geojson")]
+    mod geojson {
+        use super::timezones;
+        use tzf_rs::pbgen::{Timezone, Timezones};
+        use tzf_rs::{AntimeridianMode, MultiPolygonCoordinates, revert_timezones};
+
+        /// Total area of every polygon's exterior.
+        fn area(polygons: &MultiPolygonCoordinates) -> f64 {
+            polygons
+                .iter()
+                .map(|polygon| {
+                    let ring = &polygon[0];
+                    let twice: f64 = ring
+                        .windows(2)
+                        .map(|w| w[0][0] * w[1][1] - w[1][0] * w[0][1])
+                        .sum();
+                    twice.abs() / 2.0
+                })
+                .sum()
+        }
+
+        #[test]
+        fn merge_360_joins_the_two_sides() {
+            let file = revert_timezones(&timezones()).with_antimeridian(AntimeridianMode::Merge360);
+            let fiji = &file.features[0].geometry.coordinates;
+            assert_eq!(fiji.len(), 1);
+            let ring = &fiji[0][0];
+            assert_eq!(ring.first(), ring.last());
+            assert!(ring.iter().all(|p| (178.0..=181.0).contains(&p[0])));
+            assert!((area(fiji) - 5.5).abs() < 1e-9);
+
+            // Samoa does not reach the antimeridian and stays put.
+            let samoa = &file.features[1].geometry.coordinates;
+            assert_eq!(
+                samoa,
+                &revert_timezones(&timezones()).features[1]
+                    .geometry
+                    .coordinates
+            );
+        }
+
+        #[test]
+        fn split_cuts_rings_crossing_the_antimeridian() {
+            let crossing = Timezones {
+                timezones: vec![Timezone {
+                    polygons: vec![super::polygon(&[
+                        (179.0, 10.0),
+                        (-179.0, 10.0),
+                        (-179.0, 12.0),
+                        (179.0, 12.0),
+                        (179.0, 10.0),
+                    ])],
+                    name: "Crossing".to_string(),
+                }],
+                reduced: false,
+                version: String::new(),
+            };
+            let file = revert_timezones(&crossing).with_antimeridian(AntimeridianMode::Split);
+            let parts = &file.features[0].geometry.coordinates;
+            assert_eq!(parts.len(), 2);
+            for part in parts {
+                let ring = &part[0];
+                assert_eq!(ring.first(), ring.last());
+                assert!(ring.iter().all(|p| (-180.0..=180.0).contains(&p[0])));
+            }
+            assert!((area(parts) - 4.0).abs() < 1e-9);
+        }
+
+        #[test]
+        fn split_undoes_merge_360() {
+            let stored = revert_timezones(&timezones());
+            let round_trip = stored
+                .clone()
+                .with_antimeridian(AntimeridianMode::Merge360)
+                .with_antimeridian(AntimeridianMode::Split);
+            for (before, after) in stored.features.iter().zip(&round_trip.features) {
+                let (before, after) = (&before.geometry.coordinates, &after.geometry.coordinates);
+                assert_eq!(before.len(), after.len());
+                assert!((area(before) - area(after)).abs() < 1e-9);
+                assert!(
+                    after
+                        .iter()
+                        .flatten()
+                        .flatten()
+                        .all(|p| (-180.0..=180.0).contains(&p[0]))
+                );
+            }
+            // Stored data is already split, so splitting changes nothing.
+            let split = stored.clone().with_antimeridian(AntimeridianMode::Split);
+            for (before, after) in stored.features.iter().zip(&split.features) {
+                assert_eq!(before.geometry.coordinates, after.geometry.coordinates);
+            }
+        }
+    }
+}
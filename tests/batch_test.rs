@@ -0,0 +1,38 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::SplitMix64;
+    use tzf_rs::DefaultFinder;
+
+    #[test]
+    fn batch_matches_single_lookups() {
+        let finder = DefaultFinder::new();
+        let mut rng = SplitMix64(0x7A5F_2026_1017_0007);
+        let points: Vec<(f64, f64)> = (0..5_000)
+            .map(|_| (rng.uniform(-180.0, 180.0), rng.uniform(-90.0, 90.0)))
+            .collect();
+        let expected: Vec<&str> = points
+            .iter()
+            .map(|&(lng, lat)| finder.get_tz_name(lng, lat))
+            .collect();
+
+        assert_eq!(finder.get_tz_names_batch(&points), expected);
+
+        let (lngs, lats): (Vec<f64>, Vec<f64>) = points.iter().copied().unzip();
+        assert_eq!(finder.get_tz_names_batch_columnar(&lngs, &lats), expected);
+    }
+
+    #[test]
+    fn empty_batch() {
+        let finder = DefaultFinder::new();
+        assert!(finder.get_tz_names_batch(&[]).is_empty());
+        assert!(finder.get_tz_names_batch_columnar(&[], &[]).is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "differ in length")]
+    fn columnar_length_mismatch_panics() {
+        let _ = DefaultFinder::new().get_tz_names_batch_columnar(&[0.0], &[]);
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rect_zone;
+    use tzf_rs::pbgen::Timezones;
+    use tzf_rs::{DefaultFinder, Finder};
+
+    /// "West" and "East" share the meridian at lng=1; "North" sits on top of
+    /// East along lat=1.
+    fn neighbours() -> Finder {
+        Finder::from_pb(Timezones {
+            timezones: vec![
+                rect_zone("West", (0.0, 1.0), (0.0, 1.0)),
+                rect_zone("East", (1.0, 2.0), (0.0, 1.0)),
+                rect_zone("North", (1.0, 2.0), (1.0, 2.0)),
+            ],
+            reduced: false,
+            version: "test".to_string(),
+        })
+    }
+
+    #[test]
+    fn nearest_edge_and_zone_across() {
+        let finder = neighbours();
+
+        let border = finder.distance_to_border(0.9, 0.5).unwrap();
+        assert_eq!((border.name, border.across), ("West", "East"));
+        assert!((border.distance_m - 11_119.0).abs() < 20.0, "{border:?}");
+
+        let border = finder.distance_to_border(1.5, 0.95).unwrap();
+        assert_eq!((border.name, border.across), ("East", "North"));
+        assert!((border.distance_m - 5_560.0).abs() < 20.0, "{border:?}");
+    }
+
+    #[test]
+    fn antimeridian_split_is_no_border() {
+        let mut fiji = rect_zone("Fiji", (179.0, 180.0), (0.0, 1.0));
+        fiji.polygons
+            .extend(rect_zone("Fiji", (-180.0, -179.0), (0.0, 1.0)).polygons);
+        let finder = Finder::from_pb(Timezones {
+            timezones: vec![fiji],
+            reduced: false,
+            version: "test".to_string(),
+        });
+        for lng in [179.99, -179.99] {
+            let border = finder.distance_to_border(lng, 0.5).unwrap();
+            assert_eq!((border.name, border.across), ("Fiji", ""));
+            assert!((border.distance_m - 55_597.0).abs() < 20.0, "{border:?}");
+        }
+    }
+
+    #[test]
+    fn outer_edge_has_nothing_across() {
+        let finder = neighbours();
+        let border = finder.distance_to_border(0.05, 0.5).unwrap();
+        assert_eq!((border.name, border.across), ("West", ""));
+    }
+
+    #[test]
+    fn outside_every_polygon() {
+        assert_eq!(neighbours().distance_to_border(5.0, 5.0), None);
+    }
+
+    #[test]
+    fn nautical_border() {
+        let finder = DefaultFinder::new();
+        let border = finder.distance_to_border(-22.4999, 54.5).unwrap();
+        assert_eq!((border.name, border.across), ("Etc/GMT+1", "Etc/GMT+2"));
+        assert!(border.distance_m < 20.0, "{border:?}");
+
+        let far = finder.distance_to_border(-15.0, 54.5).unwrap();
+        assert_eq!(far.name, "Etc/GMT+1");
+        assert!(far.distance_m > 100_000.0, "{far:?}");
+    }
+}
@@ -0,0 +1,84 @@
+//! Helpers shared by the integration tests. Each test binary uses a
+//! different subset of them.
+#![allow(dead_code)]
+
+use tzf_rs::pbgen::{Point, Polygon, Timezone};
+
+/// Deterministic splitmix64 so sampling needs no rand dependency.
+pub struct SplitMix64(pub u64);
+
+impl SplitMix64 {
+    pub fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `min..max`.
+    pub fn uniform(&mut self, min: f64, max: f64) -> f64 {
+        let unit = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
+        min + unit * (max - min)
+    }
+
+    /// Uniform in `0..n`.
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+}
+
+/// Google polyline encoding of `[lng, lat]` pairs at 1e5 precision.
+pub fn encode_polyline(points: &[(i64, i64)]) -> Vec<u8> {
+    let mut out = Vec::new();
+    let (mut prev_lng, mut prev_lat) = (0, 0);
+    for &(lng, lat) in points {
+        for delta in [lng - prev_lng, lat - prev_lat] {
+            let mut v = if delta < 0 { !(delta << 1) } else { delta << 1 };
+            while v >= 0x20 {
+                out.push((((v & 0x1F) | 0x20) + 63) as u8);
+                v >>= 5;
+            }
+            out.push((v + 63) as u8);
+        }
+        (prev_lng, prev_lat) = (lng, lat);
+    }
+    out
+}
+
+/// The open ring of a `[min_lng, max_lng] x [min_lat, max_lat]` box, for
+/// `CompressedTopoBuilder`.
+pub fn rect(min_lng: f64, min_lat: f64, max_lng: f64, max_lat: f64) -> Vec<[f64; 2]> {
+    vec![
+        [min_lng, min_lat],
+        [max_lng, min_lat],
+        [max_lng, max_lat],
+        [min_lng, max_lat],
+    ]
+}
+
+/// [`rect`] with sides of `size` degrees.
+pub fn square(min_lng: f64, min_lat: f64, size: f64) -> Vec<[f64; 2]> {
+    rect(min_lng, min_lat, min_lng + size, min_lat + size)
+}
+
+/// A zone made of one box, for `Finder::from_pb`.
+pub fn rect_zone(name: &str, lng: (f32, f32), lat: (f32, f32)) -> Timezone {
+    let points = [
+        (lng.0, lat.0),
+        (lng.1, lat.0),
+        (lng.1, lat.1),
+        (lng.0, lat.1),
+        (lng.0, lat.0),
+    ]
+    .iter()
+    .map(|&(lng, lat)| Point { lng, lat })
+    .collect();
+    Timezone {
+        polygons: vec![Polygon {
+            points,
+            holes: Vec::new(),
+        }],
+        name: name.to_string(),
+    }
+}
@@ -1,8 +1,11 @@
+mod common;
+
+use crate::common::SplitMix64;
 use serde::Deserialize;
 use std::fs;
 #[cfg(feature = "bundled")]
 use tzf_dist::load_topology_compress_topo;
-#[cfg(feature = "full")]
+#[cfg(all(feature = "full", not(feature = "bundled")))]
 use tzf_dist_git::load_topology_compress_topo;
 use tzf_rs::{Finder, FinderOptions, pbgen};
 
@@ -58,21 +61,30 @@ fn y_stripes_and_linear_scan_match_on_edge_cities() {
     }
 }
 
-// Deterministic splitmix64 so the cross-check needs no rand dependency.
-struct SplitMix64(u64);
-
-impl SplitMix64 {
-    fn next(&mut self) -> u64 {
-        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
-        let mut z = self.0;
-        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
-        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
-        z ^ (z >> 31)
-    }
-
-    fn uniform(&mut self, min: f64, max: f64) -> f64 {
-        let unit = (self.next() >> 11) as f64 / (1u64 << 53) as f64;
-        min + unit * (max - min)
+#[test]
+fn every_index_mode_matches_linear_scan_on_edge_cities() {
+    let linear =
+        Finder::from_compressed_topo_with_options(load(), FinderOptions::no_index_float_raycast());
+    let modes = [
+        FinderOptions::rtree(),
+        FinderOptions::compressed_quad(),
+        FinderOptions::default().polygon_indexes(true, true, true),
+        FinderOptions::y_stripes().min_index_segments(4),
+        // Small enough that large polygons fall back to cheaper indexes.
+        FinderOptions::auto(16 * 1024),
+        FinderOptions::auto(usize::MAX).lazy(true),
+    ];
+    for options in modes {
+        let indexed = Finder::from_compressed_topo_with_options(load(), options);
+        for coordinate in edge_coordinates() {
+            assert_eq!(
+                indexed.get_tz_names(coordinate.lng, coordinate.lat),
+                linear.get_tz_names(coordinate.lng, coordinate.lat),
+                "{options:?} mismatch at {}, {}",
+                coordinate.lng,
+                coordinate.lat,
+            );
+        }
     }
 }
 
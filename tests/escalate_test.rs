@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use tzf_rs::{CompressedTopoBuilder, Finder, FinderOptions, PrecisionEscalatingFinder};
+
+    /// "West" and "East" side by side on [0,2]x[0,1], split at `border`.
+    fn finder(border: f64, options: FinderOptions) -> Finder {
+        let mut builder = CompressedTopoBuilder::new("escalate");
+        builder
+            .add_timezone(
+                "West",
+                &[vec![vec![
+                    [0.0, 0.0],
+                    [border, 0.0],
+                    [border, 1.0],
+                    [0.0, 1.0],
+                ]]],
+            )
+            .add_timezone(
+                "East",
+                &[vec![vec![
+                    [border, 0.0],
+                    [2.0, 0.0],
+                    [2.0, 1.0],
+                    [border, 1.0],
+                ]]],
+            );
+        Finder::try_from_compressed_topo_with_options(builder.build(), options).unwrap()
+    }
+
+    /// The simplified border sits at lng 1, the full one ~55 m east of it.
+    fn escalating() -> PrecisionEscalatingFinder {
+        PrecisionEscalatingFinder::new(
+            finder(1.0, FinderOptions::y_stripes()),
+            finder(1.0005, FinderOptions::y_stripes().lazy(true)),
+        )
+    }
+
+    #[test]
+    fn points_near_a_border_use_full_data() {
+        let finder = escalating();
+        assert!(finder.escalates(1.0003, 0.5));
+        assert_eq!(finder.simplified().get_tz_name(1.0003, 0.5), "East");
+        assert_eq!(finder.get_tz_name(1.0003, 0.5), "West");
+        assert_eq!(finder.get_tz_names(1.0003, 0.5), ["West"]);
+
+        for (lng, lat, name) in [(0.5, 0.5, "West"), (1.5, 0.5, "East"), (5.0, 5.0, "")] {
+            assert!(!finder.escalates(lng, lat), "{lng}, {lat}");
+            assert_eq!(finder.get_tz_name(lng, lat), name);
+        }
+    }
+
+    #[test]
+    fn tolerance_bounds_escalation() {
+        let finder = escalating().tolerance_m(10.0);
+        assert!(!finder.escalates(1.0003, 0.5));
+        assert_eq!(finder.get_tz_name(1.0003, 0.5), "East");
+        assert!(finder.escalates(1.00005, 0.5));
+        // Outer edges count as borders too.
+        assert!(finder.escalates(0.00005, 0.5));
+
+        // West covers the (0, 0) cell; its center is ~55 km from the edges.
+        assert!(!escalating().tolerance_m(50_000.0).escalates(0.5, 0.5));
+        assert!(escalating().tolerance_m(60_000.0).escalates(0.5, 0.5));
+        assert!(escalating().tolerance_m(15_000.0).escalates(0.9, 0.5));
+    }
+
+    #[test]
+    fn from_bytes_decodes_both_datasets() {
+        let topo = |border: f64| {
+            let mut buf = Vec::new();
+            finder(border, FinderOptions::y_stripes())
+                .write_compressed_topo(&mut buf)
+                .unwrap();
+            buf
+        };
+        let finder = PrecisionEscalatingFinder::from_bytes(&topo(1.0), &topo(1.0005)).unwrap();
+        assert_eq!(finder.get_tz_name(1.0003, 0.5), "West");
+        assert!(PrecisionEscalatingFinder::from_bytes(&[0xFF], &topo(1.0)).is_err());
+    }
+
+    #[test]
+    #[cfg(all(feature = "bundled", feature = "full"))]
+    fn embedded_datasets_agree_with_full_precision() {
+        use tzf_rs::pbgen;
+
+        let finder = PrecisionEscalatingFinder::try_new().unwrap();
+        let full = Finder::from_compressed_topo_with_options(
+            pbgen::CompressedTopoTimezones::try_from(tzf_dist_git::load_compress_topo())
+                .unwrap_or_default(),
+            FinderOptions::y_stripes(),
+        );
+        assert_eq!(finder.get_tz_name(116.3883, 39.9289), "Asia/Shanghai");
+        // Walk across the Germany/Denmark land border in ~10 m steps.
+        for i in 0..200 {
+            let lat = 54.80 + f64::from(i) * 1e-4;
+            assert_eq!(
+                finder.get_tz_names(9.4196, lat),
+                full.get_tz_names(9.4196, lat),
+                "{lat}"
+            );
+        }
+    }
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+#[cfg(feature = "bundled")]
+mod tests {
+    use std::path::PathBuf;
+    use tzf_dist::{load_preindex, load_topology_compress_topo};
+    use tzf_rs::{DefaultFinder, Error};
+
+    const POINTS: [(f64, f64); 4] = [
+        (116.3883, 39.9289),
+        (139.4382, 36.4432),
+        (-97.8674, 34.42),
+        (-30.5, 0.5),
+    ];
+
+    /// Write the embedded data files to a fresh directory under the system
+    /// temp dir.
+    fn data_dir(name: &str) -> (PathBuf, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("tzf-rs-{name}-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let topo = dir.join("topo.bin");
+        let preindex = dir.join("preindex.bin");
+        std::fs::write(&topo, &load_topology_compress_topo()[..]).unwrap();
+        std::fs::write(&preindex, &load_preindex()[..]).unwrap();
+        (topo, preindex)
+    }
+
+    fn assert_matches_embedded(finder: &DefaultFinder) {
+        let embedded = DefaultFinder::new();
+        assert_eq!(finder.data_version(), embedded.data_version());
+        for (lng, lat) in POINTS {
+            assert_eq!(
+                finder.get_tz_name(lng, lat),
+                embedded.get_tz_name(lng, lat),
+                "{lng}, {lat}"
+            );
+        }
+    }
+
+    #[test]
+    fn from_paths_matches_embedded_data() {
+        let (topo, preindex) = data_dir("from-paths");
+        assert_matches_embedded(&DefaultFinder::from_paths(&topo, &preindex).unwrap());
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn from_mmap_paths_matches_embedded_data() {
+        let (topo, preindex) = data_dir("from-mmap-paths");
+        assert_matches_embedded(&DefaultFinder::from_mmap_paths(&topo, &preindex).unwrap());
+    }
+
+    #[test]
+    fn missing_file_reports_path() {
+        let (topo, _) = data_dir("missing-file");
+        let missing = topo.with_file_name("does-not-exist.bin");
+        match DefaultFinder::from_paths(&topo, &missing) {
+            Err(Error::Io { path, .. }) => assert_eq!(path, missing),
+            other => panic!("expected Error::Io, got {:?}", other.err()),
+        }
+    }
+}
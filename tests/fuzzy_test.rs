@@ -8,6 +8,8 @@ mod tests {
         let ret = deg2num(116.3883, 39.9289, 7);
         assert_eq!(ret.0, 105);
         assert_eq!(ret.1, 48);
+        assert_eq!(deg2num(180.0, 0.0, 7), deg2num(-180.0, 0.0, 7));
+        assert_eq!(deg2num(179.99, 0.0, 7).0, 127);
     }
 
     #[test]
@@ -0,0 +1,330 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::SplitMix64;
+    use tzf_rs::pbgen::{Point, Polygon, Timezone, Timezones};
+    use tzf_rs::{CompressedTopoBuilder, Finder, FinderOptions, GridResolution, LookupSource};
+
+    fn polygon(points: &[(f32, f32)], holes: &[&[(f32, f32)]]) -> Polygon {
+        let ring = |points: &[(f32, f32)]| {
+            points
+                .iter()
+                .map(|&(lng, lat)| Point { lng, lat })
+                .collect()
+        };
+        Polygon {
+            points: ring(points),
+            holes: holes
+                .iter()
+                .map(|hole| Polygon {
+                    points: ring(hole),
+                    holes: Vec::new(),
+                })
+                .collect(),
+        }
+    }
+
+    fn timezone(name: &str, polygons: Vec<Polygon>) -> Timezone {
+        Timezone {
+            polygons,
+            name: name.to_string(),
+        }
+    }
+
+    /// "Big" [0,4]x[0,4] with a hole filled by "Lake", a slanted "Wedge"
+    /// sharing Big's eastern edge, and a two-part "Islands" zone.
+    fn timezones() -> Timezones {
+        let lake = [(1.3, 1.3), (2.6, 1.4), (2.2, 2.7), (1.3, 1.3)];
+        Timezones {
+            timezones: vec![
+                timezone(
+                    "Big",
+                    vec![polygon(
+                        &[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0), (0.0, 0.0)],
+                        &[&lake],
+                    )],
+                ),
+                timezone("Lake", vec![polygon(&lake, &[])]),
+                timezone(
+                    "Wedge",
+                    vec![polygon(
+                        &[(4.0, 0.0), (5.7, 0.4), (4.0, 4.0), (4.0, 0.0)],
+                        &[],
+                    )],
+                ),
+                timezone(
+                    "Islands",
+                    vec![
+                        polygon(
+                            &[(-1.5, -1.5), (-0.5, -1.5), (-1.0, -0.6), (-1.5, -1.5)],
+                            &[],
+                        ),
+                        polygon(&[(5.1, 4.2), (5.9, 4.2), (5.9, 5.8), (5.1, 4.2)], &[]),
+                    ],
+                ),
+            ],
+            reduced: false,
+            version: "grid-test".to_string(),
+        }
+    }
+
+    fn finder(grid: GridResolution) -> Finder {
+        Finder::from_pb_with_options(timezones(), FinderOptions::default().grid_resolution(grid))
+    }
+
+    /// Random points plus the cell corners and vertices, where a grid that
+    /// misses a candidate would show.
+    fn probes() -> Vec<(f64, f64)> {
+        let mut rng = SplitMix64(0x6752_1D00_2026_1017);
+        let mut points: Vec<(f64, f64)> = (0..4_000)
+            .map(|_| (rng.uniform(-2.0, 6.5), rng.uniform(-2.0, 6.5)))
+            .collect();
+        for x in -8..=26 {
+            for y in -8..=26 {
+                points.push((f64::from(x) / 4.0, f64::from(y) / 4.0));
+            }
+        }
+        for tz in &timezones().timezones {
+            for polygon in &tz.polygons {
+                let rings = std::iter::once(polygon).chain(&polygon.holes);
+                points.extend(
+                    rings
+                        .flat_map(|ring| &ring.points)
+                        .map(|p| (f64::from(p.lng), f64::from(p.lat))),
+                );
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn built_grids_match_linear_scan() {
+        let scan = finder(GridResolution::Off);
+        for grid in [
+            GridResolution::Degree,
+            GridResolution::HalfDegree,
+            GridResolution::QuarterDegree,
+        ] {
+            let indexed = finder(grid);
+            for (lng, lat) in probes() {
+                assert_eq!(
+                    indexed.get_tz_name(lng, lat),
+                    scan.get_tz_name(lng, lat),
+                    "{grid:?} {lng}, {lat}"
+                );
+                assert_eq!(
+                    indexed.get_tz_names(lng, lat),
+                    scan.get_tz_names(lng, lat),
+                    "{grid:?} {lng}, {lat}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn from_pb_builds_a_grid_by_default() {
+        let finder = Finder::from_pb(timezones());
+        let result = finder.lookup(0.5, 3.5);
+        assert_eq!(result.name, "Big");
+        assert_eq!(result.source, LookupSource::GridSingleCandidate);
+        // The lake's cells hold both zones.
+        assert_eq!(finder.lookup(2.0, 2.0).source, LookupSource::Raycast);
+
+        let scan = Finder::from_pb_with_options(
+            timezones(),
+            FinderOptions::default().grid_resolution(GridResolution::Off),
+        );
+        assert_eq!(scan.lookup(0.5, 3.5).source, LookupSource::Raycast);
+    }
+
+    #[test]
+    fn finer_cells_answer_more_points_without_raycast() {
+        let shortcut = |grid| {
+            let finder = finder(grid);
+            probes()
+                .into_iter()
+                .filter(|&(lng, lat)| {
+                    finder.lookup(lng, lat).source == LookupSource::GridSingleCandidate
+                })
+                .count()
+        };
+        let (degree, quarter) = (
+            shortcut(GridResolution::Degree),
+            shortcut(GridResolution::QuarterDegree),
+        );
+        assert!(degree > 0);
+        assert!(quarter > degree, "{quarter} <= {degree}");
+    }
+
+    #[test]
+    fn topo_without_grid_index_gets_one() {
+        let mut builder = CompressedTopoBuilder::new("no-grid");
+        builder.add_timezone(
+            "Square",
+            &[vec![vec![[0.0, 0.0], [3.0, 0.0], [3.0, 3.0], [0.0, 3.0]]]],
+        );
+        let topo = builder.grid_index(false).build();
+        assert!(topo.grid_index.is_none());
+
+        for options in [
+            FinderOptions::y_stripes(),
+            FinderOptions::y_stripes().lazy(true),
+        ] {
+            let finder =
+                Finder::try_from_compressed_topo_with_options(topo.clone(), options).unwrap();
+            let result = finder.lookup(1.5, 1.5);
+            assert_eq!(result.name, "Square");
+            assert_eq!(result.source, LookupSource::GridSingleCandidate);
+            assert_eq!(finder.get_tz_name(2.9, 0.1), "Square");
+            assert_eq!(finder.get_tz_name(3.5, 0.1), "");
+
+            let scan = Finder::from_compressed_topo_with_options(
+                topo.clone(),
+                options.grid_resolution(GridResolution::Off),
+            );
+            assert_eq!(scan.lookup(1.5, 1.5).source, LookupSource::Raycast);
+        }
+    }
+
+    #[test]
+    fn finer_grids_survive_snapshots_and_topo_export() {
+        let quarter = finder(GridResolution::QuarterDegree);
+
+        let mut bytes = Vec::new();
+        quarter.write_snapshot(&mut bytes).unwrap();
+        let restored = Finder::from_snapshot(&bytes).unwrap();
+
+        // Topo data holds 1°×1° cells, so the export rebuilds the grid.
+        let topo = quarter.to_compressed_topo();
+        let cells = &topo.grid_index.as_ref().unwrap().cells;
+        assert!(cells.iter().all(|cell| (-2..6).contains(&cell.lng)));
+        let reloaded = Finder::try_from_compressed_topo(topo).unwrap();
+
+        for (lng, lat) in probes() {
+            let expected = quarter.get_tz_name(lng, lat);
+            assert_eq!(restored.get_tz_name(lng, lat), expected, "{lng}, {lat}");
+            assert_eq!(
+                restored.lookup(lng, lat).source,
+                quarter.lookup(lng, lat).source,
+                "{lng}, {lat}"
+            );
+            assert_eq!(reloaded.get_tz_name(lng, lat), expected, "{lng}, {lat}");
+        }
+    }
+
+    /// "Polar" fills the [-180,-178]x[88,90] corner of the map, and
+    /// "Corner" is a triangle alone in its cell without covering it.
+    fn edge_of_the_map() -> Timezones {
+        Timezones {
+            timezones: vec![
+                timezone(
+                    "Polar",
+                    vec![polygon(
+                        &[
+                            (-180.0, 88.0),
+                            (-178.0, 88.0),
+                            (-178.0, 90.0),
+                            (-180.0, 90.0),
+                            (-180.0, 88.0),
+                        ],
+                        &[],
+                    )],
+                ),
+                timezone(
+                    "Corner",
+                    vec![polygon(
+                        &[(10.0, 10.0), (10.5, 10.0), (10.0, 10.5), (10.0, 10.0)],
+                        &[],
+                    )],
+                ),
+            ],
+            reduced: false,
+            version: "edge".to_string(),
+        }
+    }
+
+    #[test]
+    fn covered_cells_at_the_map_edges_skip_the_raycast() {
+        let finder = Finder::from_pb(edge_of_the_map());
+        for (lng, lat) in [(-179.5, 89.5), (-179.9, 88.1), (-178.5, 89.99)] {
+            let result = finder.lookup(lng, lat);
+            assert_eq!(result.name, "Polar", "{lng}, {lat}");
+            assert_eq!(result.source, LookupSource::GridSingleCandidate);
+        }
+
+        // The triangle's cell lists it alone, but only half the cell is
+        // inside it.
+        assert_eq!(finder.lookup(10.1, 10.1).source, LookupSource::Raycast);
+        assert_eq!(finder.get_tz_name(10.1, 10.1), "Corner");
+        let outside = finder.lookup(10.9, 10.9);
+        assert_eq!(outside.name, "");
+        assert_eq!(outside.source, LookupSource::NotFound);
+    }
+
+    #[test]
+    fn embedded_grid_indexes_work_out_coverage_on_first_use() {
+        let finder = Finder::from_pb(edge_of_the_map());
+        let topo = finder.to_compressed_topo();
+        assert!(topo.grid_index.is_some());
+
+        for options in [
+            FinderOptions::y_stripes(),
+            FinderOptions::y_stripes().lazy(true),
+            FinderOptions::default().shared_edges(true),
+        ] {
+            let loaded = Finder::from_compressed_topo_with_options(topo.clone(), options);
+            for (lng, lat) in [(-179.5, 89.5), (10.1, 10.1), (10.9, 10.9)] {
+                let (expected, result) = (finder.lookup(lng, lat), loaded.lookup(lng, lat));
+                assert_eq!(result.name, expected.name, "{lng}, {lat}");
+                assert_eq!(result.source, expected.source, "{lng}, {lat}");
+            }
+            assert_eq!(
+                loaded.to_compressed_topo().grid_index.unwrap().cells,
+                topo.grid_index.as_ref().unwrap().cells
+            );
+        }
+    }
+
+    #[test]
+    fn cells_missing_from_an_embedded_grid_hold_no_zone() {
+        let mut topo = Finder::from_pb(edge_of_the_map()).to_compressed_topo();
+        let grid = topo.grid_index.as_mut().unwrap();
+        grid.cells.retain(|cell| (cell.lng, cell.lat) != (-180, 88));
+
+        let loaded = Finder::from_compressed_topo(topo.clone());
+        assert_eq!(loaded.get_tz_name(-179.5, 88.5), "");
+        assert_eq!(loaded.lookup(-179.5, 88.5).source, LookupSource::NotFound);
+        assert_eq!(loaded.get_tz_name(-179.5, 89.5), "Polar");
+
+        topo.grid_index = None;
+        let scan = Finder::from_compressed_topo_with_options(
+            topo,
+            FinderOptions::default().grid_resolution(GridResolution::Off),
+        );
+        assert_eq!(scan.get_tz_name(-179.5, 88.5), "Polar");
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_data_without_grid_index_matches() {
+        let finder = Finder::new();
+        let mut topo = finder.to_compressed_topo();
+        topo.grid_index = None;
+        let rebuilt = Finder::from_compressed_topo_with_options(
+            topo,
+            FinderOptions::default().grid_resolution(GridResolution::HalfDegree),
+        );
+
+        for lat in (-850..850).step_by(43) {
+            for lng in (-1800..1800).step_by(47) {
+                let (lng, lat) = (f64::from(lng) / 10.0, f64::from(lat) / 10.0);
+                assert_eq!(
+                    rebuilt.get_tz_name(lng, lat),
+                    finder.get_tz_name(lng, lat),
+                    "{lng}, {lat}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Counts heap allocations to tell whether a finder decoded its polygons at
+//! construction. The counting allocator serves the whole test binary, so
+//! this file holds a single test.
+
+mod common;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+    }
+}
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/// Bytes allocated while running `f`.
+fn allocated_by(f: impl FnOnce()) -> usize {
+    let before = ALLOCATED.load(Ordering::Relaxed);
+    f();
+    ALLOCATED.load(Ordering::Relaxed) - before
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::allocated_by;
+    use crate::common::rect;
+    use tzf_rs::{CompressedTopoBuilder, Finder, FinderOptions};
+
+    const POINTS: usize = 100_000;
+
+    #[test]
+    fn lazy_finders_leave_polygons_undecoded_until_queried() {
+        // A 5° circle dense enough that decoding it shows, overlapped by a
+        // square so the probed cell has two candidates and no covered-cell
+        // shortcut.
+        let circle: Vec<[f64; 2]> = (0..POINTS)
+            .map(|i| {
+                let angle = std::f64::consts::TAU * i as f64 / POINTS as f64;
+                [20.0 + 5.0 * angle.cos(), 20.0 + 5.0 * angle.sin()]
+            })
+            .collect();
+        let mut builder = CompressedTopoBuilder::new("lazy-alloc");
+        builder.add_timezone("Circle", &[vec![circle]]);
+        builder.add_timezone("Square", &[vec![rect(24.6, 20.0, 26.0, 21.0)]]);
+        let topo = builder.build();
+        assert!(topo.grid_index.is_some());
+
+        let decoded_bytes = POINTS * size_of::<(i32, i32)>();
+        for lazy in [false, true] {
+            let finder = Finder::from_compressed_topo_with_options(
+                topo.clone(),
+                FinderOptions::no_index().lazy(lazy),
+            );
+            let first_query = allocated_by(|| {
+                assert_eq!(finder.get_tz_name(24.2, 20.1), "Circle");
+            });
+            if lazy {
+                assert!(first_query >= decoded_bytes, "{first_query} bytes");
+            } else {
+                assert!(first_query < decoded_bytes / 10, "{first_query} bytes");
+            }
+        }
+    }
+}
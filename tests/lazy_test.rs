@@ -0,0 +1,81 @@
+mod common;
+
+#[cfg(test)]
+#[cfg(feature = "bundled")]
+mod tests {
+    use crate::common::SplitMix64;
+    use tzf_rs::{Finder, FinderOptions, pbgen};
+
+    fn topo() -> pbgen::CompressedTopoTimezones {
+        pbgen::CompressedTopoTimezones::try_from(tzf_dist::load_topology_compress_topo())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn lazy_matches_eager() {
+        let eager = Finder::from_compressed_topo_with_options(topo(), FinderOptions::y_stripes());
+        let lazy = Finder::from_compressed_topo_with_options(
+            topo(),
+            FinderOptions::y_stripes().lazy(true),
+        );
+        assert_eq!(lazy.timezonenames(), eager.timezonenames());
+        assert_eq!(lazy.data_version(), eager.data_version());
+
+        let mut rng = SplitMix64(0x1A2B_2026_1017_0013);
+        for _ in 0..5_000 {
+            let (lng, lat) = (rng.uniform(-180.0, 180.0), rng.uniform(-90.0, 90.0));
+            assert_eq!(
+                lazy.get_tz_names(lng, lat),
+                eager.get_tz_names(lng, lat),
+                "{lng}, {lat}"
+            );
+        }
+    }
+
+    #[test]
+    fn strict_lazy_build_validates_up_front() {
+        let lazy = Finder::try_from_compressed_topo_with_options(
+            topo(),
+            FinderOptions::no_index().lazy(true),
+        )
+        .unwrap();
+        assert_eq!(lazy.get_tz_name(24.5212, 50.2506), "Europe/Kyiv");
+    }
+
+    #[test]
+    fn concurrent_first_use() {
+        let lazy = Finder::from_compressed_topo_with_options(
+            topo(),
+            FinderOptions::y_stripes().lazy(true),
+        );
+        std::thread::scope(|scope| {
+            for _ in 0..8 {
+                scope.spawn(|| {
+                    assert_eq!(lazy.get_tz_name(114.0617, 22.5180), "Asia/Shanghai");
+                    assert_eq!(lazy.get_tz_name(114.1594, 22.3173), "Asia/Hong_Kong");
+                });
+            }
+        });
+    }
+
+    #[test]
+    fn options_keep_enum_paths() {
+        assert_eq!(FinderOptions::YStripes, FinderOptions::y_stripes());
+        assert_eq!(FinderOptions::default(), FinderOptions::NoIndex);
+        assert!(!FinderOptions::YStripes.is_lazy());
+        assert!(FinderOptions::YStripes.lazy(true).is_lazy());
+        assert_eq!(FinderOptions::RTree, FinderOptions::rtree());
+        assert_eq!(
+            FinderOptions::CompressedQuad,
+            FinderOptions::no_index().polygon_indexes(false, true, false)
+        );
+        assert_eq!(
+            FinderOptions::y_stripes().min_index_segments(32),
+            FinderOptions::y_stripes()
+        );
+        assert!(matches!(
+            FinderOptions::no_index_integer_raycast(),
+            FinderOptions::NoIndexIntegerRaycast
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use tzf_rs::pbgen::Timezones;
+    use tzf_rs::{DefaultFinder, Finder, LookupSource};
+
+    #[test]
+    fn preindex_tier() {
+        let finder = DefaultFinder::new();
+        let result = finder.lookup(139.4382, 36.4432);
+        assert_eq!(result.name, "Asia/Tokyo");
+        assert_eq!(result.name, finder.get_tz_name(139.4382, 36.4432));
+        assert!(matches!(result.source, LookupSource::Preindex { zoom } if zoom >= 0));
+        assert!(!result.on_shared_edge);
+        assert_eq!(result.candidates, ["Asia/Tokyo"]);
+    }
+
+    #[test]
+    fn grid_single_candidate_tier() {
+        // Mid-Atlantic, well inside the Etc/GMT+2 nautical strip.
+        let finder = Finder::new();
+        let result = finder.lookup(-30.5, 0.5);
+        assert_eq!(result.name, "Etc/GMT+2");
+        assert_eq!(result.source, LookupSource::GridSingleCandidate);
+        assert_eq!(result.candidates, ["Etc/GMT+2"]);
+    }
+
+    #[test]
+    fn raycast_tier_on_shared_edge() {
+        let finder = DefaultFinder::new();
+        for (lng, lat) in [(7.5, 54.5), (-22.5, 54.5)] {
+            let result = finder.lookup(lng, lat);
+            assert_eq!(result.source, LookupSource::Raycast);
+            assert!(result.on_shared_edge);
+            assert_eq!(result.name, finder.get_tz_name(lng, lat));
+            assert_eq!(result.candidates, finder.get_tz_names(lng, lat));
+        }
+
+        let result = finder.lookup(7.4999, 54.5);
+        assert_eq!(result.name, "Etc/GMT");
+        assert!(!result.on_shared_edge);
+    }
+
+    #[test]
+    fn not_found() {
+        let finder = Finder::from_pb(Timezones::default());
+        let result = finder.lookup(0.0, 0.0);
+        assert_eq!(result.name, "");
+        assert_eq!(result.source, LookupSource::NotFound);
+        assert!(result.candidates.is_empty());
+    }
+}
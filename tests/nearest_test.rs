@@ -0,0 +1,64 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rect_zone;
+    use tzf_rs::pbgen::Timezones;
+    use tzf_rs::{DefaultFinder, Finder};
+
+    /// Two unit squares with a 1° gap between them along the equator.
+    fn gapped() -> Finder {
+        Finder::from_pb(Timezones {
+            timezones: vec![
+                rect_zone("West", (0.0, 1.0), (0.0, 1.0)),
+                rect_zone("East", (2.0, 3.0), (0.0, 1.0)),
+            ],
+            reduced: true,
+            version: "test".to_string(),
+        })
+    }
+
+    #[test]
+    fn inside_returns_zero_distance() {
+        let finder = gapped();
+        assert_eq!(
+            finder.get_tz_name_nearest(0.5, 0.5, 1.0),
+            Some(("West", 0.0))
+        );
+    }
+
+    #[test]
+    fn gap_resolves_to_closest_polygon() {
+        let finder = gapped();
+        assert_eq!(finder.get_tz_name(1.25, 0.5), "");
+
+        let (name, km) = finder.get_tz_name_nearest(1.25, 0.5, 100.0).unwrap();
+        assert_eq!(name, "West");
+        // A quarter degree of longitude near the equator.
+        assert!((km - 27.8).abs() < 0.1, "{km}");
+
+        let (name, km) = finder.get_tz_name_nearest(1.9, 0.5, 100.0).unwrap();
+        assert_eq!(name, "East");
+        assert!((km - 11.1).abs() < 0.1, "{km}");
+    }
+
+    #[test]
+    fn nothing_within_max_distance() {
+        let finder = gapped();
+        assert_eq!(finder.get_tz_name_nearest(1.5, 0.5, 10.0), None);
+        assert_eq!(finder.get_tz_name_nearest(1.5, 30.0, 1000.0), None);
+    }
+
+    #[test]
+    fn default_finder_inside() {
+        let finder = DefaultFinder::new();
+        assert_eq!(
+            finder.get_tz_name_nearest(139.4382, 36.4432, 10.0),
+            Some(("Asia/Tokyo", 0.0))
+        );
+        assert_eq!(
+            finder.finder.get_tz_name_nearest(-0.9671, 52.0152, 10.0),
+            Some(("Europe/London", 0.0))
+        );
+    }
+}
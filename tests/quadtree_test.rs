@@ -0,0 +1,139 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::SplitMix64;
+    use tzf_rs::pbgen::{Point, Polygon, Timezone, Timezones};
+    use tzf_rs::{Finder, FinderOptions, LookupSource};
+
+    fn timezone(name: String, points: &[(f32, f32)]) -> Timezone {
+        Timezone {
+            polygons: vec![Polygon {
+                points: points
+                    .iter()
+                    .map(|&(lng, lat)| Point { lng, lat })
+                    .collect(),
+                holes: Vec::new(),
+            }],
+            name,
+        }
+    }
+
+    /// Six vertical strips crammed into the (0, 0) cell, the last one with a
+    /// slanted eastern border, so the cell has six candidates.
+    fn strips() -> Timezones {
+        let timezones = (0..6u8)
+            .map(|i| {
+                let (west, east) = (f32::from(i) / 6.0, f32::from(i + 1) / 6.0);
+                let north_east = if i == 5 { 0.9 } else { east };
+                timezone(
+                    format!("Strip/{i}"),
+                    &[
+                        (west, 0.0),
+                        (east, 0.0),
+                        (north_east, 1.0),
+                        (west, 1.0),
+                        (west, 0.0),
+                    ],
+                )
+            })
+            .collect();
+        Timezones {
+            timezones,
+            reduced: false,
+            version: "strips".to_string(),
+        }
+    }
+
+    fn probes() -> Vec<(f64, f64)> {
+        let mut rng = SplitMix64(0x0A0D_7EE0_2026_1017);
+        let mut points: Vec<(f64, f64)> = (0..5_000)
+            .map(|_| (rng.uniform(-0.2, 1.2), rng.uniform(-0.2, 1.2)))
+            .collect();
+        // Strip borders and quadrant boundaries.
+        for i in 0..=6u8 {
+            let border = f64::from(f32::from(i) / 6.0);
+            for j in 0..=16 {
+                points.push((border, f64::from(j) / 16.0));
+                points.push((f64::from(j) / 16.0, border));
+            }
+        }
+        points
+    }
+
+    #[test]
+    fn quadtree_matches_flat_grid() {
+        let flat = Finder::from_pb(strips());
+        for max_candidates in [1, 2, 3] {
+            let tree = Finder::from_pb_with_options(
+                strips(),
+                FinderOptions::default().quadtree(max_candidates),
+            );
+            for (lng, lat) in probes() {
+                assert_eq!(
+                    tree.get_tz_name(lng, lat),
+                    flat.get_tz_name(lng, lat),
+                    "{max_candidates}: {lng}, {lat}"
+                );
+                assert_eq!(
+                    tree.get_tz_names(lng, lat),
+                    flat.get_tz_names(lng, lat),
+                    "{max_candidates}: {lng}, {lat}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn leaves_inside_one_zone_skip_the_raycast() {
+        let flat = Finder::from_pb(strips());
+        assert_eq!(flat.lookup(0.05, 0.5).source, LookupSource::Raycast);
+
+        let tree = Finder::from_pb_with_options(strips(), FinderOptions::default().quadtree(2));
+        let result = tree.lookup(0.05, 0.5);
+        assert_eq!(result.name, "Strip/0");
+        assert_eq!(result.source, LookupSource::QuadtreeInside);
+        assert_eq!(result.candidates, ["Strip/0"]);
+
+        // On a shared border both neighbours still claim the point.
+        let border = tree.lookup(f64::from(2.0f32 / 6.0), 0.5);
+        assert_eq!(border.source, LookupSource::Raycast);
+        assert!(border.on_shared_edge);
+
+        // East of the slanted border, inside the cell but outside every zone.
+        assert_eq!(tree.get_tz_name(0.98, 0.95), "");
+    }
+
+    #[test]
+    fn subsets_keep_answering_without_the_tree() {
+        let tree = Finder::from_pb_with_options(strips(), FinderOptions::default().quadtree(1));
+        let subset = tree.subset_by_names(&["Strip/0", "Strip/1"]);
+        for (lng, lat) in probes() {
+            let expected = match tree.get_tz_name(lng, lat) {
+                name @ ("Strip/0" | "Strip/1") => name,
+                _ => "",
+            };
+            assert_eq!(subset.get_tz_name(lng, lat), expected, "{lng}, {lat}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_balkans_match_with_quadtree() {
+        let finder = Finder::new();
+        let tree = Finder::from_compressed_topo_with_options(
+            finder.to_compressed_topo(),
+            FinderOptions::y_stripes().quadtree(2),
+        );
+        for lat in (400..480).step_by(3) {
+            for lng in (130..300).step_by(3) {
+                let (lng, lat) = (f64::from(lng) / 10.0, f64::from(lat) / 10.0);
+                assert_eq!(
+                    tree.get_tz_name(lng, lat),
+                    finder.get_tz_name(lng, lat),
+                    "{lng}, {lat}"
+                );
+            }
+        }
+    }
+}
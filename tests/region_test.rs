@@ -0,0 +1,138 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::square;
+    use tzf_rs::{CompressedTopoBuilder, Finder, FinderOptions, GridResolution, RegionZone};
+
+    /// "West" [0,2]x[0,2] with a hole filled by "Enclave" [0.5,1]x[0.5,1],
+    /// and "East" [2,3]x[0,2]; "Kiribati" [179,180]x[0,1] and "Samoa"
+    /// [-180,-178]x[0,1] meet at the antimeridian.
+    fn finders() -> [Finder; 3] {
+        let mut builder = CompressedTopoBuilder::new("region");
+        builder
+            .add_timezone(
+                "West",
+                &[vec![square(0.0, 0.0, 2.0), square(0.5, 0.5, 0.5)]],
+            )
+            .add_timezone(
+                "East",
+                &[vec![vec![[2.0, 0.0], [3.0, 0.0], [3.0, 2.0], [2.0, 2.0]]]],
+            )
+            .add_timezone("Enclave", &[vec![square(0.5, 0.5, 0.5)]])
+            .add_timezone("Kiribati", &[vec![square(179.0, 0.0, 1.0)]])
+            .add_timezone(
+                "Samoa",
+                &[vec![vec![
+                    [-180.0, 0.0],
+                    [-178.0, 0.0],
+                    [-178.0, 1.0],
+                    [-180.0, 1.0],
+                ]]],
+            );
+        let topo = builder.build();
+        // Without a grid every zone is a candidate.
+        let gridless = builder.grid_index(false).build();
+        [
+            Finder::from_compressed_topo(topo.clone()),
+            Finder::from_compressed_topo_with_options(topo, FinderOptions::y_stripes().lazy(true)),
+            Finder::from_compressed_topo_with_options(
+                gridless,
+                FinderOptions::default().grid_resolution(GridResolution::Off),
+            ),
+        ]
+    }
+
+    /// Shares agree with planar ones to within the equal-area projection's
+    /// stretch over a degree or two of latitude.
+    fn assert_fractions(zones: &[RegionZone], expected: &[(&str, f64)]) {
+        assert_eq!(zones.len(), expected.len(), "{zones:?}");
+        for (zone, &(name, fraction)) in zones.iter().zip(expected) {
+            assert_eq!(zone.name, name, "{zones:?}");
+            assert!((zone.fraction - fraction).abs() < 1e-3, "{zones:?}");
+        }
+    }
+
+    #[test]
+    fn bbox_straddling_a_border_splits_by_area() {
+        for finder in finders() {
+            let zones = finder.zones_in_bbox_with_fractions(1.6, 0.2, 2.5, 1.2);
+            assert_fractions(&zones, &[("East", 5.0 / 9.0), ("West", 4.0 / 9.0)]);
+            assert_eq!(finder.zones_in_bbox(1.6, 0.2, 2.5, 1.2), ["East", "West"]);
+        }
+    }
+
+    #[test]
+    fn bbox_crossing_the_antimeridian_wraps() {
+        for finder in finders() {
+            let zones = finder.zones_in_bbox_with_fractions(179.5, 0.0, -179.0, 1.0);
+            assert_fractions(&zones, &[("Samoa", 2.0 / 3.0), ("Kiribati", 1.0 / 3.0)]);
+            assert_eq!(
+                finder.zones_in_bbox(179.5, 0.0, -179.0, 1.0),
+                ["Samoa", "Kiribati"]
+            );
+            assert_eq!(finder.zones_in_bbox(179.0, 0.0, 180.0, 1.0), ["Kiribati"]);
+        }
+    }
+
+    #[test]
+    fn holes_count_for_the_zone_inside_them() {
+        for finder in finders() {
+            // Inside the enclave only.
+            let zones = finder.zones_in_bbox_with_fractions(0.6, 0.6, 0.9, 0.9);
+            assert_fractions(&zones, &[("Enclave", 1.0)]);
+            // Exactly the enclave: its borders touch West but share no area.
+            let zones = finder.zones_in_bbox_with_fractions(0.5, 0.5, 1.0, 1.0);
+            assert_fractions(&zones, &[("Enclave", 1.0)]);
+            // The enclave plus a quarter of its area of West.
+            let zones = finder.zones_in_bbox_with_fractions(0.5, 0.5, 1.0, 1.125);
+            assert_fractions(&zones, &[("Enclave", 0.8), ("West", 0.2)]);
+        }
+    }
+
+    #[test]
+    fn query_polygons_with_holes() {
+        for finder in finders() {
+            // A frame across the West/East border with a hole over it,
+            // wound clockwise and closed, to check normalization.
+            let exterior = [[1.6, 0.2], [1.6, 1.2], [2.5, 1.2], [2.5, 0.2], [1.6, 0.2]];
+            let hole = vec![[1.8, 0.4], [2.2, 0.4], [2.2, 0.8], [1.8, 0.8]];
+            let zones = finder.zones_intersecting_polygon_with_fractions(&exterior, &[hole]);
+            assert_fractions(&zones, &[("East", 0.42 / 0.74), ("West", 0.32 / 0.74)]);
+
+            // A triangle reaching just past the border, with a hole in West.
+            let exterior = [[1.0, 0.0], [2.2, 0.0], [1.0, 1.2]];
+            let hole = vec![[1.2, 0.1], [1.6, 0.1], [1.2, 0.5]];
+            let zones = finder.zones_intersecting_polygon_with_fractions(&exterior, &[hole]);
+            assert_fractions(&zones, &[("West", 0.62 / 0.64), ("East", 0.02 / 0.64)]);
+        }
+    }
+
+    #[test]
+    fn regions_without_overlap_are_empty() {
+        for finder in finders() {
+            assert!(finder.zones_in_bbox(5.0, 5.0, 6.0, 6.0).is_empty());
+            // Touching West's outer edge only.
+            assert!(finder.zones_in_bbox(-1.0, 0.0, 0.0, 1.0).is_empty());
+            // No area.
+            assert!(finder.zones_in_bbox(1.0, 1.0, 1.0, 1.0).is_empty());
+            assert!(finder.zones_intersecting_polygon(&[], &[]).is_empty());
+            // Half outside every zone.
+            let zones = finder.zones_in_bbox_with_fractions(2.5, 1.5, 3.5, 2.5);
+            assert_fractions(&zones, &[("East", 0.25)]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_border_region() {
+        let finder = Finder::new();
+        // Around the Germany/Denmark land border.
+        let zones = finder.zones_in_bbox_with_fractions(9.0, 54.6, 9.8, 55.0);
+        let names: Vec<&str> = zones.iter().map(|zone| zone.name).collect();
+        assert!(names.contains(&"Europe/Berlin"), "{names:?}");
+        assert!(names.contains(&"Europe/Copenhagen"), "{names:?}");
+        let total: f64 = zones.iter().map(|zone| zone.fraction).sum();
+        assert!((total - 1.0).abs() < 1e-6, "{zones:?}");
+    }
+}
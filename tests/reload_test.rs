@@ -0,0 +1,107 @@
+#[cfg(test)]
+mod tests {
+    use tzf_rs::pbgen::{Point, Polygon, PreindexTimezones, Timezone, Timezones};
+    use tzf_rs::{DefaultFinder, Error, Finder, FuzzyFinder, ReloadableFinder};
+
+    /// A one-zone dataset covering the whole globe.
+    fn world(name: &str, version: &str) -> DefaultFinder {
+        let points = [
+            (-180.0, -90.0),
+            (180.0, -90.0),
+            (180.0, 90.0),
+            (-180.0, 90.0),
+            (-180.0, -90.0),
+        ]
+        .iter()
+        .map(|&(lng, lat)| Point { lng, lat })
+        .collect();
+        DefaultFinder {
+            finder: Finder::from_pb(Timezones {
+                timezones: vec![Timezone {
+                    polygons: vec![Polygon {
+                        points,
+                        holes: Vec::new(),
+                    }],
+                    name: name.to_string(),
+                }],
+                reduced: false,
+                version: version.to_string(),
+            }),
+            fuzzy_finder: FuzzyFinder::from_pb(PreindexTimezones {
+                version: version.to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn reload_swaps_and_keeps_old_snapshot() {
+        let finder = ReloadableFinder::new(world("Old/Zone", "v1"));
+        let before = finder.load();
+
+        let previous = finder.reload(world("New/Zone", "v2")).unwrap();
+        assert_eq!(previous.data_version(), "v1");
+        assert_eq!(finder.data_version(), "v2");
+        assert_eq!(finder.load().get_tz_name(10.0, 10.0), "New/Zone");
+
+        // A snapshot taken before the reload keeps answering from old data.
+        assert_eq!(before.get_tz_name(10.0, 10.0), "Old/Zone");
+    }
+
+    #[test]
+    fn reload_rejects_same_version() {
+        let finder = ReloadableFinder::new(world("Old/Zone", "v1"));
+        assert!(matches!(
+            finder.reload(world("New/Zone", "v1")),
+            Err(Error::SameDataVersion(version)) if version == "v1"
+        ));
+        assert_eq!(finder.load().get_tz_name(10.0, 10.0), "Old/Zone");
+    }
+
+    #[test]
+    fn reload_rejects_empty_data() {
+        let finder = ReloadableFinder::new(world("Old/Zone", "v1"));
+        let empty = DefaultFinder {
+            finder: Finder::from_pb(Timezones {
+                version: "v2".to_string(),
+                ..Default::default()
+            }),
+            fuzzy_finder: FuzzyFinder::from_pb(PreindexTimezones {
+                version: "v2".to_string(),
+                ..Default::default()
+            }),
+        };
+        assert!(matches!(finder.reload(empty), Err(Error::EmptyDataset)));
+        assert!(matches!(
+            DefaultFinder::from_bytes(&[], &[]),
+            Err(Error::EmptyDataset)
+        ));
+        assert!(matches!(
+            finder.reload_from_paths("/nonexistent/topo.bin", "/nonexistent/preindex.bin"),
+            Err(Error::Io { .. })
+        ));
+        assert_eq!(finder.data_version(), "v1");
+    }
+
+    #[test]
+    fn queries_run_during_reloads() {
+        let finder = ReloadableFinder::new(world("Zone/0", "0"));
+        std::thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..1_000 {
+                        let snapshot = finder.load();
+                        let name = snapshot.get_tz_name(10.0, 10.0);
+                        assert_eq!(name, format!("Zone/{}", snapshot.data_version()));
+                    }
+                });
+            }
+            for i in 1..=20 {
+                finder
+                    .reload(world(&format!("Zone/{i}"), &i.to_string()))
+                    .unwrap();
+            }
+        });
+        assert_eq!(finder.data_version(), "20");
+    }
+}
@@ -0,0 +1,197 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use tzf_rs::pbgen::compressed_ring_segment::Content;
+    use tzf_rs::{CompressedTopoBuilder, Error, Finder, FinderOptions, RingError, pbgen};
+
+    /// A `sides`-gon of `radius` degrees around `(lng, lat)`, enough points
+    /// to get a Y-stripes index.
+    fn disc(lng: f64, lat: f64, radius: f64, sides: usize) -> Vec<[f64; 2]> {
+        (0..sides)
+            .map(|i| {
+                let angle = std::f64::consts::TAU * i as f64 / sides as f64;
+                [lng + radius * angle.cos(), lat + radius * angle.sin()]
+            })
+            .collect()
+    }
+
+    /// "Land" [0,4]x[0,4] with a disc hole filled by "Lake", and "Coast"
+    /// [4,6]x[0,4] sharing Land's eastern edge.
+    fn topo() -> pbgen::CompressedTopoTimezones {
+        let lake = disc(2.0, 2.0, 1.0, 96);
+        let mut builder = CompressedTopoBuilder::new("shared");
+        builder
+            .add_timezone(
+                "Land",
+                &[vec![
+                    vec![[0.0, 0.0], [4.0, 0.0], [4.0, 4.0], [0.0, 4.0]],
+                    lake.clone(),
+                ]],
+            )
+            .add_timezone(
+                "Coast",
+                &[vec![vec![[4.0, 0.0], [6.0, 0.0], [6.0, 4.0], [4.0, 4.0]]]],
+            )
+            .add_timezone("Lake", &[vec![lake]]);
+        builder.build()
+    }
+
+    fn options() -> [FinderOptions; 4] {
+        [
+            FinderOptions::y_stripes(),
+            FinderOptions::y_stripes().lazy(true),
+            FinderOptions::no_index(),
+            FinderOptions::no_index().grid_resolution(tzf_rs::GridResolution::Off),
+        ]
+    }
+
+    /// Probes on a 0.05° lattice hit the shared borders at lng 4, the
+    /// outer edges and the disc's vertices.
+    fn probes() -> impl Iterator<Item = (f64, f64)> {
+        (-10..=130)
+            .flat_map(|i| (-10..=90).map(move |j| (f64::from(i) * 0.05, f64::from(j) * 0.05)))
+    }
+
+    #[test]
+    fn matches_expanded_rings() {
+        let expanded =
+            Finder::from_compressed_topo_with_options(topo(), FinderOptions::y_stripes());
+        for options in options() {
+            let shared =
+                Finder::try_from_compressed_topo_with_options(topo(), options.shared_edges(true))
+                    .unwrap();
+            assert_eq!(shared.timezonenames(), expanded.timezonenames());
+            assert_eq!(shared.data_version(), "shared");
+            for (lng, lat) in probes() {
+                assert_eq!(
+                    shared.get_tz_names(lng, lat),
+                    expanded.get_tz_names(lng, lat),
+                    "{options:?} {lng}, {lat}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn conversions_keep_the_zones() {
+        let shared = Finder::from_compressed_topo_with_options(
+            topo(),
+            FinderOptions::y_stripes().shared_edges(true),
+        );
+        let mut bytes = Vec::new();
+        shared.write_snapshot(&mut bytes).unwrap();
+        let others = [
+            Finder::from_snapshot(&bytes).unwrap(),
+            Finder::from_compressed_topo(shared.to_compressed_topo()),
+            shared.subset_by_names(&["Land", "Lake", "Coast"]),
+        ];
+        for other in &others {
+            for (lng, lat) in probes() {
+                assert_eq!(
+                    other.get_tz_names(lng, lat),
+                    shared.get_tz_names(lng, lat),
+                    "{lng}, {lat}"
+                );
+            }
+        }
+        let coast = shared.subset_by_bbox(4.5, 0.5, 5.5, 3.5);
+        assert_eq!(coast.timezonenames(), ["Coast"]);
+        assert_eq!(coast.get_tz_name(5.0, 2.0), "Coast");
+    }
+
+    #[test]
+    fn strict_errors_surface() {
+        let mut tzs = topo();
+        let exterior = &mut tzs.timezones[1].polygons[0].exterior;
+        let at = exterior
+            .iter()
+            .position(|segment| {
+                matches!(
+                    segment.content,
+                    Some(Content::EdgeForward(_) | Content::EdgeReversed(_))
+                )
+            })
+            .unwrap();
+        exterior[at].content = Some(Content::EdgeForward(99));
+        let options = FinderOptions::y_stripes().shared_edges(true);
+        match Finder::try_from_compressed_topo_with_options(tzs.clone(), options).err() {
+            Some(Error::MalformedRing { location, reason }) => {
+                assert_eq!(location.timezone, "Coast");
+                assert_eq!(location.segment, Some(at));
+                assert_eq!(reason, RingError::DanglingSharedEdge(99));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+
+        // Lenient decoding drops the broken ring only.
+        let lenient = Finder::from_compressed_topo_with_options(tzs, options);
+        assert_eq!(lenient.get_tz_name(5.0, 2.0), "");
+        assert_eq!(lenient.get_tz_name(2.0, 2.0), "Lake");
+        assert_eq!(lenient.get_tz_name(0.5, 0.5), "Land");
+    }
+
+    #[test]
+    fn index_modes_without_y_stripes_are_rejected() {
+        let expanded =
+            Finder::from_compressed_topo_with_options(topo(), FinderOptions::y_stripes());
+        for options in [
+            FinderOptions::rtree(),
+            FinderOptions::compressed_quad(),
+            FinderOptions::auto(256 * 1024),
+            FinderOptions::no_index_integer_raycast(),
+            FinderOptions::default().polygon_indexes(true, false, true),
+        ] {
+            let options = options.shared_edges(true);
+            let err = Finder::try_from_compressed_topo_with_options(topo(), options)
+                .err()
+                .unwrap();
+            assert!(matches!(err, Error::SharedEdgesIndexMode), "{err}");
+
+            // The lenient constructor keeps the index and expands the rings.
+            let finder = Finder::from_compressed_topo_with_options(topo(), options);
+            for (lng, lat) in probes() {
+                assert_eq!(
+                    finder.get_tz_names(lng, lat),
+                    expanded.get_tz_names(lng, lat),
+                    "{options:?}: {lng}, {lat}"
+                );
+            }
+        }
+        let stripes_only = FinderOptions::default()
+            .polygon_indexes(false, false, true)
+            .shared_edges(true);
+        assert!(Finder::try_from_compressed_topo_with_options(topo(), stripes_only).is_ok());
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_matches_expanded_rings() {
+        use crate::common::SplitMix64;
+
+        let bundled = || {
+            pbgen::CompressedTopoTimezones::try_from(tzf_dist::load_topology_compress_topo())
+                .unwrap_or_default()
+        };
+        let expanded =
+            Finder::from_compressed_topo_with_options(bundled(), FinderOptions::y_stripes());
+        let shared = Finder::try_from_compressed_topo_with_options(
+            bundled(),
+            FinderOptions::y_stripes().shared_edges(true).lazy(true),
+        )
+        .unwrap();
+        assert_eq!(shared.timezonenames(), expanded.timezonenames());
+
+        let mut rng = SplitMix64(0x5AED_2026_1017_0021);
+        for _ in 0..5_000 {
+            let (lng, lat) = (rng.uniform(-180.0, 180.0), rng.uniform(-90.0, 90.0));
+            assert_eq!(
+                shared.get_tz_names(lng, lat),
+                expanded.get_tz_names(lng, lat),
+                "{lng}, {lat}"
+            );
+        }
+        assert_eq!(shared.get_tz_name(116.3883, 39.9289), "Asia/Shanghai");
+        assert_eq!(shared.get_tz_name(24.5212, 50.2506), "Europe/Kyiv");
+    }
+}
@@ -0,0 +1,226 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use tzf_rs::pbgen::{Point, Polygon, Timezone, Timezones};
+    use tzf_rs::{Error, Finder, FinderOptions, LookupSource, SNAPSHOT_FORMAT_VERSION};
+
+    fn ring(points: &[(f32, f32)]) -> Vec<Point> {
+        points
+            .iter()
+            .map(|&(lng, lat)| Point { lng, lat })
+            .collect()
+    }
+
+    /// A 128-point circle around (10, 10), enough edges to get a stripe index.
+    fn circle() -> Vec<(f32, f32)> {
+        (0..128)
+            .map(|i| {
+                let a = i as f32 * std::f32::consts::TAU / 128.0;
+                (10.0 + 5.0 * a.cos(), 10.0 + 5.0 * a.sin())
+            })
+            .collect()
+    }
+
+    /// A square with a hole next to a circle.
+    fn synthetic() -> Finder {
+        synthetic_with_options(FinderOptions::default())
+    }
+
+    fn synthetic_with_options(options: FinderOptions) -> Finder {
+        Finder::from_pb_with_options(
+            Timezones {
+                timezones: vec![
+                    Timezone {
+                        polygons: vec![Polygon {
+                            points: ring(&[(-10.0, -10.0), (0.0, -10.0), (0.0, 0.0), (-10.0, 0.0)]),
+                            holes: vec![Polygon {
+                                points: ring(&[
+                                    (-6.0, -6.0),
+                                    (-4.0, -6.0),
+                                    (-4.0, -4.0),
+                                    (-6.0, -4.0),
+                                ]),
+                                holes: Vec::new(),
+                            }],
+                        }],
+                        name: "Test/Square".to_string(),
+                    },
+                    Timezone {
+                        polygons: vec![Polygon {
+                            points: ring(&circle()),
+                            holes: Vec::new(),
+                        }],
+                        name: "Test/Circle".to_string(),
+                    },
+                ],
+                reduced: false,
+                version: "test-1".to_string(),
+            },
+            options,
+        )
+    }
+
+    fn snapshot(finder: &Finder) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        finder.write_snapshot(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn synthetic_round_trip() {
+        let restored = Finder::from_snapshot(&snapshot(&synthetic())).unwrap();
+        assert_eq!(restored.data_version(), "test-1");
+        assert_eq!(restored.timezonenames(), synthetic().timezonenames());
+
+        assert_eq!(restored.get_tz_name(-8.0, -8.0), "Test/Square");
+        assert_eq!(restored.get_tz_name(-5.0, -5.0), "", "inside the hole");
+        assert_eq!(
+            restored.get_tz_name(-4.0, -5.0),
+            "Test/Square",
+            "hole border"
+        );
+        assert_eq!(
+            restored.get_tz_name(0.0, -5.0),
+            "Test/Square",
+            "outer border"
+        );
+        assert_eq!(restored.get_tz_name(10.0, 10.0), "Test/Circle");
+        assert_eq!(restored.get_tz_name(14.9, 10.0), "Test/Circle");
+        assert_eq!(restored.get_tz_name(14.0, 14.0), "");
+        assert_eq!(restored.get_tz_name(50.0, 50.0), "");
+    }
+
+    #[test]
+    fn snapshot_of_snapshot_is_identical() {
+        let bytes = snapshot(&synthetic());
+        let restored = Finder::from_snapshot(&bytes).unwrap();
+        assert_eq!(snapshot(&restored), bytes);
+    }
+
+    #[test]
+    fn snapshots_keep_the_index_options() {
+        let plain = snapshot(&synthetic());
+        let indexed = snapshot(&synthetic_with_options(FinderOptions::y_stripes()));
+        // Only the circle has enough edges for stripes.
+        assert!(indexed.len() > plain.len());
+        for bytes in [plain, indexed] {
+            let restored = Finder::from_snapshot(&bytes).unwrap();
+            assert_eq!(snapshot(&restored), bytes);
+            assert_eq!(restored.get_tz_name(10.0, 10.0), "Test/Circle");
+        }
+        let auto = snapshot(&synthetic_with_options(FinderOptions::auto(1024)));
+        assert_eq!(snapshot(&Finder::from_snapshot(&auto).unwrap()), auto);
+    }
+
+    #[test]
+    fn boundary_points_match_the_writing_finder() {
+        for options in [FinderOptions::default(), FinderOptions::y_stripes()] {
+            let finder = synthetic_with_options(options);
+            let restored = Finder::from_snapshot(&snapshot(&finder)).unwrap();
+            let points = [
+                (-10.0, -10.0),
+                (0.0, 0.0),
+                (-10.0, -5.0),
+                (-5.0, 0.0),
+                (-6.0, -6.0),
+                (-4.0, -5.0),
+                (-5.0, -6.0),
+                (0.0, 0.5),
+                (0.5, 0.0),
+            ];
+            for (lng, lat) in points {
+                assert_eq!(
+                    restored.get_tz_names(lng, lat),
+                    finder.get_tz_names(lng, lat),
+                    "{lng}, {lat}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn coverage_flags_survive_the_round_trip() {
+        let restored = Finder::from_snapshot(&snapshot(&synthetic())).unwrap();
+        // The cell [-9, -8] x [-9, -8] lies inside the square.
+        let lookup = restored.lookup(-8.5, -8.5);
+        assert_eq!(lookup.name, "Test/Square");
+        assert_eq!(lookup.source, LookupSource::GridSingleCandidate);
+        // The hole's cells need a raycast.
+        assert_eq!(restored.lookup(-5.5, -5.5).source, LookupSource::NotFound);
+    }
+
+    #[test]
+    fn rejects_bad_magic_and_truncation() {
+        let bytes = snapshot(&synthetic());
+        assert!(matches!(
+            Finder::from_snapshot(b"not a snapshot"),
+            Err(Error::InvalidSnapshot(_))
+        ));
+        let mut bad_magic = bytes.clone();
+        bad_magic[0] ^= 0xFF;
+        assert!(matches!(
+            Finder::from_snapshot(&bad_magic),
+            Err(Error::InvalidSnapshot(_))
+        ));
+        for len in [bytes.len() / 2, bytes.len() - 1] {
+            assert!(matches!(
+                Finder::from_snapshot(&bytes[..len]),
+                Err(Error::InvalidSnapshot(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn rejects_other_format_versions() {
+        let mut bytes = snapshot(&synthetic());
+        bytes[8..12].copy_from_slice(&(SNAPSHOT_FORMAT_VERSION + 1).to_le_bytes());
+        assert!(matches!(
+            Finder::from_snapshot(&bytes),
+            Err(Error::UnsupportedSnapshotVersion(v)) if v == SNAPSHOT_FORMAT_VERSION + 1
+        ));
+    }
+
+    #[test]
+    fn corrupt_bytes_never_panic() {
+        let bytes = snapshot(&synthetic());
+        for i in 8..bytes.len() {
+            let mut corrupt = bytes.clone();
+            corrupt[i] ^= 0xA5;
+            if let Ok(finder) = Finder::from_snapshot(&corrupt) {
+                let _ = finder.get_tz_names(-5.0, -5.0);
+                let _ = finder.get_tz_names(10.0, 10.0);
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_round_trip_matches() {
+        use crate::common::SplitMix64;
+
+        let finder = Finder::new();
+        let restored = Finder::from_snapshot(&snapshot(&finder)).unwrap();
+        assert_eq!(restored.data_version(), finder.data_version());
+        assert_eq!(restored.timezonenames(), finder.timezonenames());
+
+        let mut rng = SplitMix64(0x5A9_2026_1017_0014);
+        for _ in 0..10_000 {
+            let (lng, lat) = (rng.uniform(-180.0, 180.0), rng.uniform(-90.0, 90.0));
+            assert_eq!(
+                restored.get_tz_names(lng, lat),
+                finder.get_tz_names(lng, lat),
+                "{lng}, {lat}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "mmap")]
+    fn from_snapshot_path_maps_file() {
+        let path = std::env::temp_dir().join(format!("tzf-rs-snapshot-{}.bin", std::process::id()));
+        std::fs::write(&path, snapshot(&synthetic())).unwrap();
+        let finder = Finder::from_snapshot_path(&path).unwrap();
+        assert_eq!(finder.get_tz_name(10.0, 10.0), "Test/Circle");
+    }
+}
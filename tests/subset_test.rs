@@ -0,0 +1,139 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rect_zone;
+    use tzf_rs::pbgen::{CompressedTopoTimezones, GridIndex, GridIndexCell, Timezones};
+    use tzf_rs::{Finder, LookupSource};
+
+    fn cell(lng: i32, tz_indices: Vec<u32>) -> GridIndexCell {
+        GridIndexCell {
+            lng,
+            lat: 0,
+            tz_indices,
+        }
+    }
+
+    /// "West" owns [0,1.5]x[0,1] and "East" [1.5,2]x[0,1], so both are
+    /// candidates of the (1, 0) cell; "Dateline" sits just east of -180.
+    fn topo() -> CompressedTopoTimezones {
+        let mut tzs = Finder::from_pb(Timezones {
+            timezones: vec![
+                rect_zone("West", (0.0, 1.5), (0.0, 1.0)),
+                rect_zone("East", (1.5, 2.0), (0.0, 1.0)),
+                rect_zone("Dateline", (-180.0, -179.5), (0.0, 1.0)),
+            ],
+            reduced: false,
+            version: "test".to_string(),
+        })
+        .to_compressed_topo();
+        tzs.grid_index = Some(GridIndex {
+            cells: vec![cell(0, vec![0]), cell(1, vec![0, 1]), cell(-180, vec![2])],
+            version: "test".to_string(),
+        });
+        tzs
+    }
+
+    #[test]
+    fn subset_by_names_stays_exact_in_shrunk_cells() {
+        let finder = Finder::try_from_compressed_topo(topo()).unwrap();
+        assert_eq!(finder.get_tz_name(1.2, 0.5), "West");
+
+        let east = finder.subset_by_names(&["East", "Nowhere"]);
+        assert_eq!(east.timezonenames(), ["East"]);
+        assert_eq!(east.data_version(), "test");
+        // The (1, 0) cell lost West; its lone remaining candidate must not
+        // claim West's half of the cell.
+        assert_eq!(east.get_tz_name(1.2, 0.5), "");
+        assert_eq!(east.get_tz_name(1.7, 0.5), "East");
+        assert_eq!(east.get_tz_name(0.5, 0.5), "");
+        assert_eq!(east.lookup(1.2, 0.5).name, "");
+        assert_eq!(east.lookup(1.7, 0.5).source, LookupSource::Raycast);
+
+        // West still covers its own cell, not the shrunk one.
+        let west = finder.subset_by_names(&["West"]);
+        assert_eq!(
+            west.lookup(0.5, 0.5).source,
+            LookupSource::GridSingleCandidate
+        );
+        assert_eq!(west.lookup(1.2, 0.5).source, LookupSource::Raycast);
+    }
+
+    #[test]
+    fn subset_by_bbox_keeps_overlapping_zones() {
+        let finder = Finder::try_from_compressed_topo(topo()).unwrap();
+        assert_eq!(
+            finder.subset_by_bbox(1.6, 0.2, 1.9, 0.8).timezonenames(),
+            ["East"]
+        );
+        assert_eq!(
+            finder.subset_by_bbox(-1.0, -1.0, 1.0, 2.0).timezonenames(),
+            ["West"]
+        );
+        // min_lng > max_lng wraps across the antimeridian.
+        let dateline = finder.subset_by_bbox(170.0, 0.0, -170.0, 1.0);
+        assert_eq!(dateline.timezonenames(), ["Dateline"]);
+        assert_eq!(dateline.get_tz_name(-179.7, 0.5), "Dateline");
+        assert!(
+            finder
+                .subset_by_bbox(50.0, 50.0, 60.0, 60.0)
+                .timezonenames()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn subset_round_trips_through_compressed_topo() {
+        let east = Finder::try_from_compressed_topo(topo())
+            .unwrap()
+            .subset_by_names(&["East", "Dateline"]);
+        let mut encoded = Vec::new();
+        east.write_compressed_topo(&mut encoded).unwrap();
+
+        let tzs = CompressedTopoTimezones::try_from(encoded).unwrap();
+        let cells: Vec<(i32, Vec<u32>)> = tzs
+            .grid_index
+            .as_ref()
+            .unwrap()
+            .cells
+            .iter()
+            .map(|cell| (cell.lng, cell.tz_indices.clone()))
+            .collect();
+        // West's cell is gone, the shrunk cell keeps East and Dateline's
+        // index is remapped.
+        assert_eq!(cells, [(-180, vec![1]), (1, vec![0])]);
+
+        let loaded = Finder::try_from_compressed_topo(tzs).unwrap();
+        assert_eq!(loaded.timezonenames(), ["East", "Dateline"]);
+        for (lng, lat) in [(1.2, 0.5), (1.7, 0.5), (-179.7, 0.5), (0.5, 0.5)] {
+            assert_eq!(
+                loaded.get_tz_name(lng, lat),
+                east.get_tz_name(lng, lat),
+                "{lng}, {lat}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_europe_subset_matches_inside_bbox() {
+        let finder = Finder::new();
+        let europe = finder.subset_by_bbox(-25.0, 34.0, 45.0, 72.0);
+        assert!(europe.timezonenames().len() < finder.timezonenames().len());
+
+        let mut encoded = Vec::new();
+        europe.write_compressed_topo(&mut encoded).unwrap();
+        let loaded =
+            Finder::try_from_compressed_topo(CompressedTopoTimezones::try_from(encoded).unwrap())
+                .unwrap();
+
+        for lat in (340..720).step_by(7) {
+            for lng in (-250..450).step_by(7) {
+                let (lng, lat) = (f64::from(lng) / 10.0, f64::from(lat) / 10.0);
+                let expected = finder.get_tz_name(lng, lat);
+                assert_eq!(europe.get_tz_name(lng, lat), expected, "{lng}, {lat}");
+                assert_eq!(loaded.get_tz_name(lng, lat), expected, "{lng}, {lat}");
+            }
+        }
+    }
+}
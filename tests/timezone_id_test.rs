@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod tests {
+    use tzf_rs::{DefaultFinder, Finder, FuzzyFinder, TimezoneId};
+
+    #[test]
+    fn ids_round_trip_every_name() {
+        let finder = DefaultFinder::new();
+        for name in finder.timezonenames() {
+            let id = finder.id_of(name).unwrap();
+            assert_eq!(finder.name(id), Some(name));
+            assert_eq!(id.index(), usize::from(id.get()));
+        }
+        assert_eq!(finder.id_of("Not/A_Zone"), None);
+    }
+
+    #[test]
+    fn ids_order_like_names() {
+        let finder = Finder::new();
+        let mut names = finder.timezonenames();
+        names.sort_unstable();
+        names.dedup();
+        let ids: Vec<TimezoneId> = names.iter().map(|n| finder.id_of(n).unwrap()).collect();
+        assert!(ids.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn ids_agree_with_names_across_tiers() {
+        let finder = DefaultFinder::new();
+        let fuzzy = FuzzyFinder::new();
+        for (lng, lat) in [
+            (116.3883, 39.9289),
+            (139.4382, 36.4432),
+            (-97.8674, 34.42),
+            (-30.5, 0.5),
+            (7.4999, 54.5),
+        ] {
+            let name = finder.get_tz_name(lng, lat);
+            assert_eq!(
+                finder.get_tz_id(lng, lat),
+                finder.id_of(name),
+                "{lng}, {lat}"
+            );
+            assert_eq!(
+                finder
+                    .finder
+                    .get_tz_id(lng, lat)
+                    .and_then(|id| finder.finder.name(id)),
+                Some(finder.finder.get_tz_name(lng, lat)),
+            );
+            if let Some(id) = fuzzy.get_tz_id(lng, lat) {
+                assert_eq!(fuzzy.name(id), Some(fuzzy.get_tz_name(lng, lat)));
+            }
+            // The preindex inside a `DefaultFinder` shares the polygon tier's
+            // table, so its ids agree with the top-level ones.
+            if let Some(id) = finder.fuzzy_finder.get_tz_id(lng, lat) {
+                assert_eq!(Some(id), finder.get_tz_id(lng, lat), "{lng}, {lat}");
+            }
+        }
+    }
+
+    #[test]
+    fn fuzzy_ids_follow_a_shared_table() {
+        let finder = Finder::new();
+        let own = FuzzyFinder::new();
+        let shared = FuzzyFinder::new().with_names(finder.names());
+        assert_eq!(shared.names(), finder.names());
+
+        let (lng, lat) = (139.4382, 36.4432);
+        let id = shared.get_tz_id(lng, lat).unwrap();
+        assert_eq!(Some(id), finder.get_tz_id(lng, lat));
+        assert_eq!(finder.name(id), Some("Asia/Tokyo"));
+        assert_eq!(
+            own.name(own.get_tz_id(lng, lat).unwrap()),
+            Some("Asia/Tokyo")
+        );
+
+        // Every preindex zone resolves through the shared table.
+        for name in own.names().iter() {
+            assert_eq!(shared.name(shared.id_of(name).unwrap()), Some(name));
+        }
+        assert_eq!(shared.id_of("Not/A_Zone"), None);
+    }
+}
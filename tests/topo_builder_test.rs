@@ -0,0 +1,153 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::square;
+    use prost::Message;
+    use tzf_rs::pbgen::{CompressedTopoTimezones, compressed_ring_segment::Content};
+    use tzf_rs::{CompressedTopoBuilder, Finder, LookupSource};
+
+    /// "West" [0,2]x[0,2] with a hole filled by "Enclave", and "East"
+    /// [2,3]x[0,2] sharing West's eastern edge.
+    fn builder() -> CompressedTopoBuilder {
+        let mut builder = CompressedTopoBuilder::new("test");
+        builder
+            .add_timezone(
+                "West",
+                &[vec![square(0.0, 0.0, 2.0), square(0.25, 0.25, 0.5)]],
+            )
+            .add_timezone(
+                "East",
+                &[vec![vec![[2.0, 0.0], [3.0, 0.0], [3.0, 2.0], [2.0, 2.0]]]],
+            )
+            .add_timezone("Enclave", &[vec![square(0.25, 0.25, 0.5)]]);
+        builder
+    }
+
+    const PROBES: [(f64, f64, &str); 6] = [
+        (0.2, 1.5, "West"),
+        (1.5, 0.2, "West"),
+        (0.5, 0.5, "Enclave"),
+        (2.5, 1.5, "East"),
+        (2.5, 0.5, "East"),
+        (5.0, 5.0, ""),
+    ];
+
+    #[test]
+    fn shared_edges_are_referenced_from_both_sides() {
+        let topo = builder().build();
+        assert_eq!(topo.version, "test");
+        // West/East along lng 2 and West's hole/Enclave.
+        assert_eq!(topo.shared_edges.len(), 2);
+
+        let references: Vec<i32> = topo
+            .timezones
+            .iter()
+            .flat_map(|tz| &tz.polygons)
+            .flat_map(|polygon| {
+                let holes = polygon.holes.iter().flat_map(|hole| &hole.exterior);
+                polygon.exterior.iter().chain(holes)
+            })
+            .filter_map(|segment| match segment.content {
+                Some(Content::EdgeForward(id) | Content::EdgeReversed(id)) => Some(id),
+                _ => None,
+            })
+            .collect();
+        for id in 0..2 {
+            assert_eq!(references.iter().filter(|&&r| r == id).count(), 2);
+        }
+    }
+
+    #[test]
+    fn built_topo_round_trips() {
+        let encoded = builder().build().encode_to_vec();
+        let topo = CompressedTopoTimezones::try_from(encoded).unwrap();
+        let finder = Finder::try_from_compressed_topo(topo).unwrap();
+        assert_eq!(finder.timezonenames(), ["West", "East", "Enclave"]);
+        for (lng, lat, name) in PROBES {
+            assert_eq!(finder.get_tz_name(lng, lat), name, "{lng}, {lat}");
+        }
+    }
+
+    #[test]
+    fn embedded_grid_lists_candidates_by_cell() {
+        let topo = builder().build();
+        let grid_index = topo.grid_index.as_ref().unwrap();
+        let cells: Vec<(i32, i32, Vec<u32>)> = grid_index
+            .cells
+            .iter()
+            .map(|cell| (cell.lng, cell.lat, cell.tz_indices.clone()))
+            .collect();
+        // The (0, 0) cell holds the enclave, so it lists both zones. East's
+        // cells also list West, whose edge runs along their western border,
+        // down to the (2, 2) corner.
+        assert_eq!(
+            cells,
+            [
+                (0, 0, vec![0, 2]),
+                (1, 0, vec![0]),
+                (2, 0, vec![0, 1]),
+                (3, 0, vec![1]),
+                (0, 1, vec![0]),
+                (1, 1, vec![0]),
+                (2, 1, vec![0, 1]),
+                (3, 1, vec![1]),
+                (0, 2, vec![0]),
+                (1, 2, vec![0]),
+                (2, 2, vec![0, 1]),
+                (3, 2, vec![1]),
+            ]
+        );
+
+        // West alone covers its other cells, which skip the raycast once
+        // loaded. Cells only touched by one zone's outer edges list it
+        // without being covered.
+        let finder = Finder::try_from_compressed_topo(topo.clone()).unwrap();
+        for (lng, lat) in [(1.5, 0.5), (0.5, 1.5), (1.5, 1.5)] {
+            let lookup = finder.lookup(lng, lat);
+            assert_eq!(lookup.name, "West", "{lng}, {lat}");
+            assert_eq!(lookup.source, LookupSource::GridSingleCandidate);
+        }
+        for (lng, lat) in [(0.5, 2.5), (3.5, 0.5)] {
+            let source = finder.lookup(lng, lat).source;
+            assert_ne!(source, LookupSource::GridSingleCandidate, "{lng}, {lat}");
+        }
+
+        let without = builder().grid_index(false).build();
+        assert!(without.grid_index.is_none());
+        assert_eq!(without.timezones, topo.timezones);
+    }
+
+    #[test]
+    #[cfg(feature = "export-geojson")]
+    fn builds_from_boundary_file() {
+        let source = Finder::try_from_compressed_topo(builder().build()).unwrap();
+        let mut builder = CompressedTopoBuilder::new("geojson");
+        builder.add_boundary_file(&source.to_geojson());
+        let finder = Finder::try_from_compressed_topo(builder.build()).unwrap();
+        assert_eq!(finder.data_version(), "geojson");
+        for (lng, lat, name) in PROBES {
+            assert_eq!(finder.get_tz_name(lng, lat), name, "{lng}, {lat}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_data_round_trips_with_shared_edges() {
+        let finder = Finder::new();
+        let topo = finder.to_compressed_topo();
+        assert!(!topo.shared_edges.is_empty());
+        let loaded = Finder::try_from_compressed_topo(topo).unwrap();
+
+        for lat in (-850..850).step_by(37) {
+            for lng in (-1800..1800).step_by(41) {
+                let (lng, lat) = (f64::from(lng) / 10.0, f64::from(lat) / 10.0);
+                assert_eq!(
+                    loaded.get_tz_name(lng, lat),
+                    finder.get_tz_name(lng, lat),
+                    "{lng}, {lat}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,292 @@
+//! Malformed `CompressedTopoTimezones` payloads: the strict constructors must
+//! say where the problem is, and the lenient ones must never panic.
+
+mod common;
+
+use crate::common::{SplitMix64, encode_polyline};
+use prost::Message;
+use tzf_rs::pbgen::{
+    CompressedInlinePoints, CompressedRingSegment, CompressedSharedEdge, CompressedTopoPolygon,
+    CompressedTopoTimezone, CompressedTopoTimezones, GridIndex, GridIndexCell,
+    compressed_ring_segment::Content,
+};
+use tzf_rs::{Error, Finder, FinderOptions, PolylineError, RingError, RingLocation};
+
+fn inline(points: Vec<u8>) -> CompressedRingSegment {
+    CompressedRingSegment {
+        content: Some(Content::Inline(CompressedInlinePoints { points })),
+    }
+}
+
+/// Two unit squares sharing the meridian at lng=1: "West" owns [0,1]x[0,1]
+/// and "East" owns [1,2]x[0,1]. East has a hole at [1.4,1.6]x[0.4,0.6].
+fn two_squares() -> CompressedTopoTimezones {
+    let shared = encode_polyline(&[(100_000, 0), (100_000, 100_000)]);
+    let west_rest = encode_polyline(&[(100_000, 100_000), (0, 100_000), (0, 0), (100_000, 0)]);
+    let east_rest = encode_polyline(&[
+        (100_000, 0),
+        (200_000, 0),
+        (200_000, 100_000),
+        (100_000, 100_000),
+    ]);
+    let hole = encode_polyline(&[
+        (140_000, 40_000),
+        (140_000, 60_000),
+        (160_000, 60_000),
+        (160_000, 40_000),
+        (140_000, 40_000),
+    ]);
+    CompressedTopoTimezones {
+        shared_edges: vec![CompressedSharedEdge {
+            id: 0,
+            points: shared,
+        }],
+        timezones: vec![
+            CompressedTopoTimezone {
+                polygons: vec![CompressedTopoPolygon {
+                    exterior: vec![
+                        CompressedRingSegment {
+                            content: Some(Content::EdgeForward(0)),
+                        },
+                        inline(west_rest),
+                    ],
+                    holes: Vec::new(),
+                }],
+                name: "West".to_string(),
+            },
+            CompressedTopoTimezone {
+                polygons: vec![CompressedTopoPolygon {
+                    exterior: vec![
+                        inline(east_rest),
+                        CompressedRingSegment {
+                            content: Some(Content::EdgeReversed(0)),
+                        },
+                    ],
+                    holes: vec![CompressedTopoPolygon {
+                        exterior: vec![inline(hole)],
+                        holes: Vec::new(),
+                    }],
+                }],
+                name: "East".to_string(),
+            },
+        ],
+        version: "test".to_string(),
+        grid_index: Some(GridIndex {
+            cells: vec![
+                GridIndexCell {
+                    lng: 0,
+                    lat: 0,
+                    tz_indices: vec![0],
+                },
+                GridIndexCell {
+                    lng: 1,
+                    lat: 0,
+                    tz_indices: vec![0, 1],
+                },
+            ],
+            version: String::new(),
+        }),
+        ..Default::default()
+    }
+}
+
+fn ring_error(tzs: CompressedTopoTimezones) -> (RingLocation, RingError) {
+    match Finder::try_from_compressed_topo(tzs).err() {
+        Some(Error::MalformedRing { location, reason }) => (location, reason),
+        Some(err) => panic!("unexpected error: {err}"),
+        None => panic!("malformed ring was accepted"),
+    }
+}
+
+#[test]
+fn well_formed_payload_round_trips() {
+    let finder = Finder::try_from_compressed_topo(two_squares()).unwrap();
+    assert_eq!(finder.get_tz_name(0.5, 0.5), "West");
+    assert_eq!(finder.get_tz_name(1.2, 0.5), "East");
+    assert_eq!(finder.get_tz_name(1.5, 0.5), "");
+    assert_eq!(finder.get_tz_names(1.0, 0.5), ["West", "East"]);
+}
+
+#[test]
+fn truncated_inline_polyline_reports_segment() {
+    let mut tzs = two_squares();
+    let Some(Content::Inline(ref mut seg)) = tzs.timezones[1].polygons[0].exterior[0].content
+    else {
+        unreachable!()
+    };
+    // Drop the final latitude chunk, leaving a dangling longitude.
+    seg.points.pop();
+
+    let (location, reason) = ring_error(tzs);
+    assert_eq!(
+        location,
+        RingLocation {
+            timezone: "East".to_string(),
+            polygon: 0,
+            hole: None,
+            segment: Some(0),
+        }
+    );
+    assert!(
+        matches!(reason, RingError::Polyline(PolylineError::Truncated { .. })),
+        "{reason}"
+    );
+}
+
+#[test]
+fn overflowing_polyline_is_rejected() {
+    let mut tzs = two_squares();
+    tzs.timezones[0].polygons[0].exterior[1] =
+        inline(encode_polyline(&[(i64::from(i32::MAX) + 1, 0)]));
+    let (location, reason) = ring_error(tzs);
+    assert_eq!(location.segment, Some(1));
+    assert!(
+        matches!(reason, RingError::Polyline(PolylineError::Overflow { .. })),
+        "{reason}"
+    );
+
+    // An endless run of continuation bytes must not shift past 64 bits.
+    let mut tzs = two_squares();
+    tzs.timezones[0].polygons[0].exterior[1] = inline(vec![b'~'; 64]);
+    let (_, reason) = ring_error(tzs);
+    assert!(
+        matches!(reason, RingError::Polyline(PolylineError::Overflow { .. })),
+        "{reason}"
+    );
+}
+
+#[test]
+fn invalid_polyline_byte_is_rejected() {
+    let mut tzs = two_squares();
+    tzs.timezones[0].polygons[0].exterior[1] = inline(vec![b' ', b'?']);
+    let (_, reason) = ring_error(tzs);
+    assert_eq!(
+        reason,
+        RingError::Polyline(PolylineError::InvalidByte {
+            offset: 0,
+            byte: b' '
+        })
+    );
+}
+
+#[test]
+fn unclosed_hole_reports_hole() {
+    let mut tzs = two_squares();
+    tzs.timezones[1].polygons[0].holes[0].exterior = vec![inline(encode_polyline(&[
+        (140_000, 40_000),
+        (140_000, 60_000),
+        (160_000, 60_000),
+    ]))];
+    let (location, reason) = ring_error(tzs);
+    assert_eq!(location.timezone, "East");
+    assert_eq!((location.hole, location.segment), (Some(0), None));
+    assert_eq!(reason, RingError::NotClosed);
+}
+
+#[test]
+fn malformed_shared_edge_is_rejected() {
+    let mut tzs = two_squares();
+    tzs.shared_edges[0].points.truncate(1);
+    let err = Finder::try_from_compressed_topo(tzs).err().unwrap();
+    assert!(
+        matches!(
+            err,
+            Error::MalformedSharedEdge {
+                id: 0,
+                reason: PolylineError::Truncated { .. }
+            }
+        ),
+        "{err}"
+    );
+}
+
+#[test]
+fn lenient_constructor_drops_broken_rings() {
+    let mut tzs = two_squares();
+    tzs.timezones[0].polygons[0].exterior[0].content = Some(Content::EdgeForward(7));
+    tzs.grid_index.as_mut().unwrap().cells[1]
+        .tz_indices
+        .push(99);
+    let finder = Finder::from_compressed_topo(tzs);
+    assert!(finder.get_tz_names(0.5, 0.5).is_empty());
+    assert_eq!(finder.get_tz_names(1.2, 0.5), ["East"]);
+}
+
+fn query_everywhere(finder: &Finder) {
+    for (lng, lat) in [
+        (0.5, 0.5),
+        (1.0, 0.5),
+        (1.5, 0.5),
+        (-3.0, 7.0),
+        (1.99, 0.01),
+    ] {
+        let _ = finder.get_tz_name(lng, lat);
+        let _ = finder.get_tz_names(lng, lat);
+    }
+}
+
+/// Byte-level mutations of an encoded payload. Whatever prost still accepts
+/// must build without panicking through both constructors.
+#[test]
+fn fuzz_mutated_payload_never_panics() {
+    let bytes = two_squares().encode_to_vec();
+    let mut rng = SplitMix64(0x7A5F_2026_1017_0002);
+    for _ in 0..5_000 {
+        let mut mutated = bytes.clone();
+        for _ in 0..=rng.below(4) {
+            let at = rng.below(mutated.len());
+            match rng.below(3) {
+                0 => mutated[at] = rng.next() as u8,
+                1 => mutated[at] ^= 1 << rng.below(8),
+                _ => mutated.truncate(at),
+            }
+            if mutated.is_empty() {
+                break;
+            }
+        }
+        let Ok(tzs) = CompressedTopoTimezones::decode(&mutated[..]) else {
+            continue;
+        };
+        if let Ok(finder) = Finder::try_from_compressed_topo(tzs.clone()) {
+            query_everywhere(&finder);
+        }
+        query_everywhere(&Finder::from_compressed_topo_with_options(
+            tzs.clone(),
+            FinderOptions::default().shared_edges(true),
+        ));
+        query_everywhere(&Finder::from_compressed_topo(tzs));
+    }
+}
+
+/// Structured mutations that survive protobuf decoding: random polyline
+/// bytes and random edge / grid indices.
+#[test]
+fn fuzz_random_segments_never_panic() {
+    let mut rng = SplitMix64(0x7A5F_2026_1017_0003);
+    for _ in 0..5_000 {
+        let mut tzs = two_squares();
+        let tz = rng.below(2);
+        let seg = rng.below(tzs.timezones[tz].polygons[0].exterior.len());
+        tzs.timezones[tz].polygons[0].exterior[seg].content = Some(match rng.below(3) {
+            0 => Content::EdgeForward(rng.next() as i32 % 4),
+            1 => Content::EdgeReversed(rng.next() as i32 % 4),
+            _ => Content::Inline(CompressedInlinePoints {
+                points: (0..rng.below(24)).map(|_| rng.next() as u8).collect(),
+            }),
+        });
+        if rng.below(4) == 0 {
+            tzs.shared_edges[0].id = rng.next() as i32 % 3;
+        }
+        if rng.below(4) == 0 {
+            tzs.grid_index.as_mut().unwrap().cells[0].tz_indices = vec![rng.next() as u32 % 4];
+        }
+        if let Ok(finder) = Finder::try_from_compressed_topo(tzs.clone()) {
+            query_everywhere(&finder);
+        }
+        query_everywhere(&Finder::from_compressed_topo_with_options(
+            tzs.clone(),
+            FinderOptions::default().shared_edges(true),
+        ));
+        query_everywhere(&Finder::from_compressed_topo(tzs));
+    }
+}
@@ -0,0 +1,160 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::rect;
+    use tzf_rs::{CompressedTopoBuilder, Finder, FinderOptions, GridResolution, ZoneSegment};
+
+    /// "West" [0,1.3]x[0,1] next to "East" [1.3,2]x[0,1], their border off
+    /// the grid lines; "Fiji" stored split at the antimeridian over
+    /// [179,-179]x[0,1], and "Kiribati" and "Samoa" meeting there over [2,3].
+    fn finders() -> [Finder; 4] {
+        let mut builder = CompressedTopoBuilder::new("trajectory");
+        builder
+            .add_timezone("West", &[vec![rect(0.0, 0.0, 1.3, 1.0)]])
+            .add_timezone("East", &[vec![rect(1.3, 0.0, 2.0, 1.0)]])
+            .add_timezone(
+                "Fiji",
+                &[
+                    vec![rect(179.0, 0.0, 180.0, 1.0)],
+                    vec![rect(-180.0, 0.0, -179.0, 1.0)],
+                ],
+            )
+            .add_timezone("Kiribati", &[vec![rect(179.0, 2.0, 180.0, 3.0)]])
+            .add_timezone("Samoa", &[vec![rect(-180.0, 2.0, -179.0, 3.0)]]);
+        let topo = builder.build();
+        // Without a grid every zone is a candidate.
+        let gridless = builder.grid_index(false).build();
+        [
+            Finder::from_compressed_topo(topo.clone()),
+            Finder::from_compressed_topo_with_options(
+                topo.clone(),
+                FinderOptions::y_stripes().lazy(true),
+            ),
+            Finder::from_compressed_topo_with_options(
+                topo,
+                FinderOptions::default().shared_edges(true),
+            ),
+            Finder::from_compressed_topo_with_options(
+                gridless,
+                FinderOptions::default().grid_resolution(GridResolution::Off),
+            ),
+        ]
+    }
+
+    fn assert_close(actual: (f64, f64, f64), expected: (f64, f64, f64)) {
+        let close = (actual.0 - expected.0).abs() < 1e-9
+            && (actual.1 - expected.1).abs() < 1e-9
+            && (actual.2 - expected.2).abs() < 1e-6;
+        assert!(close, "{actual:?} != {expected:?}");
+    }
+
+    fn names<'a>(segments: &[ZoneSegment<'a>]) -> Vec<&'a str> {
+        segments.iter().map(|segment| segment.name).collect()
+    }
+
+    #[test]
+    fn crossings_are_refined_to_the_border() {
+        for finder in finders() {
+            let segments = finder.transitions(&[(0.2, 0.2, 10.0), (1.8, 0.6, 90.0)]);
+            assert_eq!(names(&segments), ["West", "East"]);
+            assert_close(segments[0].start, (0.2, 0.2, 10.0));
+            assert_close(segments[0].end, (1.3, 0.475, 65.0));
+            assert_close(segments[1].start, (1.3, 0.475, 65.0));
+            assert_close(segments[1].end, (1.8, 0.6, 90.0));
+            assert_eq!(segments[1].names, ["East"]);
+        }
+    }
+
+    #[test]
+    fn one_step_may_cross_several_borders() {
+        for finder in finders() {
+            let segments = finder.transitions(&[(0.5, 0.5, 0.0), (2.5, 0.5, 20.0)]);
+            assert_eq!(names(&segments), ["West", "East", ""]);
+            assert_close(segments[0].end, (1.3, 0.5, 8.0));
+            assert_close(segments[1].end, (2.0, 0.5, 15.0));
+            assert!(segments[2].names.is_empty());
+
+            // Several steps inside one zone make one segment.
+            let segments = finder.transitions(&[(0.1, 0.1, 0.0), (0.9, 0.1, 1.0), (0.9, 0.9, 2.0)]);
+            assert_eq!(names(&segments), ["West"]);
+            assert_close(segments[0].end, (0.9, 0.9, 2.0));
+        }
+    }
+
+    #[test]
+    fn points_on_borders_follow_get_tz_names() {
+        for finder in finders() {
+            // Touching the border and turning back is no transition.
+            let segments = finder.transitions(&[(0.5, 0.5, 0.0), (1.3, 0.5, 1.0), (0.5, 0.6, 2.0)]);
+            assert_eq!(names(&segments), ["West"]);
+
+            // Crossing at a point on the border switches there.
+            let segments = finder.transitions(&[(0.5, 0.5, 0.0), (1.3, 0.5, 1.0), (1.5, 0.5, 2.0)]);
+            assert_eq!(names(&segments), ["West", "East"]);
+            assert_close(segments[0].end, (1.3, 0.5, 1.0));
+
+            // Staying on the border claims both zones.
+            let mut expected = finder.get_tz_names(1.3, 0.5);
+            expected.sort_unstable();
+            assert_eq!(expected, ["East", "West"]);
+            for path in [
+                &[(1.3, 0.5, 0.0)][..],
+                &[(1.3, 0.2, 0.0), (1.3, 0.8, 1.0)][..],
+            ] {
+                let segments = finder.transitions(path);
+                assert_eq!(segments.len(), 1, "{segments:?}");
+                let mut names = segments[0].names.clone();
+                names.sort_unstable();
+                assert_eq!(names, expected);
+            }
+
+            assert!(finder.transitions(&[]).is_empty());
+        }
+    }
+
+    #[test]
+    fn paths_take_the_short_way_across_the_antimeridian() {
+        for finder in finders() {
+            // Fiji spans the antimeridian, so there is nothing to report.
+            let segments = finder.transitions(&[(179.5, 0.5, 0.0), (-179.5, 0.5, 10.0)]);
+            assert_eq!(names(&segments), ["Fiji"]);
+            assert_close(segments[0].end, (-179.5, 0.5, 10.0));
+
+            for (from, to) in [(179.5, -179.5), (-179.5, 179.5)] {
+                let segments = finder.transitions(&[(from, 2.5, 0.0), (to, 2.5, 10.0)]);
+                let expected = if from > 0.0 {
+                    ["Kiribati", "Samoa"]
+                } else {
+                    ["Samoa", "Kiribati"]
+                };
+                assert_eq!(names(&segments), expected);
+                let (lng, lat, t) = segments[0].end;
+                assert_eq!(lng.abs(), 180.0);
+                assert_close((lng.abs(), lat, t), (180.0, 2.5, 5.0));
+                assert_eq!(segments[0].end, segments[1].start);
+            }
+
+            // Starting on the antimeridian heading east.
+            let segments = finder.transitions(&[(180.0, 2.5, 0.0), (-179.5, 2.5, 10.0)]);
+            assert_eq!(names(&segments), ["Samoa"]);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "bundled")]
+    fn bundled_border_crossing() {
+        let finder = Finder::new();
+        // Walking north across the Germany/Denmark land border.
+        let segments = finder.transitions(&[(9.4196, 54.80, 0.0), (9.4196, 54.82, 100.0)]);
+        assert_eq!(segments.first().unwrap().name, "Europe/Berlin");
+        assert_eq!(segments.last().unwrap().name, "Europe/Copenhagen");
+        for pair in segments.windows(2) {
+            let (lng, lat, t) = pair[0].end;
+            assert_eq!(pair[0].end, pair[1].start);
+            assert!(t > 0.0 && t < 100.0, "{t}");
+            assert_eq!(finder.get_tz_name(lng, lat - 1e-6), pair[0].name);
+            assert_eq!(finder.get_tz_name(lng, lat + 1e-6), pair[1].name);
+        }
+    }
+}
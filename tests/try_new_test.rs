@@ -0,0 +1,175 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::encode_polyline;
+    use tzf_rs::pbgen::{
+        CompressedRingSegment, CompressedSharedEdge, CompressedTopoPolygon, CompressedTopoTimezone,
+        CompressedTopoTimezones, GridIndex, GridIndexCell, PreindexTimezones,
+        compressed_ring_segment::Content,
+    };
+    use tzf_rs::{DefaultFinder, Error, Finder, FuzzyFinder, RingError};
+
+    fn one_edge_topo(segment: Content) -> CompressedTopoTimezones {
+        let square = [
+            (0, 0),
+            (100_000, 0),
+            (100_000, 100_000),
+            (0, 100_000),
+            (0, 0),
+        ];
+        CompressedTopoTimezones {
+            shared_edges: vec![CompressedSharedEdge {
+                id: 0,
+                points: encode_polyline(&square),
+            }],
+            timezones: vec![CompressedTopoTimezone {
+                polygons: vec![CompressedTopoPolygon {
+                    exterior: vec![CompressedRingSegment {
+                        content: Some(segment),
+                    }],
+                    holes: Vec::new(),
+                }],
+                name: "Etc/GMT".to_string(),
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn try_new_matches_new() {
+        let finder = DefaultFinder::try_new().unwrap();
+        assert_eq!(finder.get_tz_name(116.3883, 39.9289), "Asia/Shanghai");
+        assert_eq!(
+            finder.data_version(),
+            finder.fuzzy_finder.data_version(),
+            "bundled datasets must come from the same release"
+        );
+
+        let finder = Finder::try_new().unwrap();
+        assert_eq!(finder.get_tz_name(139.4382, 36.4432), "Asia/Tokyo");
+
+        let finder = FuzzyFinder::try_new().unwrap();
+        assert_eq!(finder.get_tz_name(-97.8674, 34.4200), "America/Chicago");
+    }
+
+    #[test]
+    fn dangling_shared_edge_is_rejected() {
+        let err = Finder::try_from_compressed_topo(one_edge_topo(Content::EdgeReversed(3)))
+            .err()
+            .unwrap();
+        match err {
+            Error::MalformedRing { location, reason } => {
+                assert_eq!(location.timezone, "Etc/GMT");
+                assert_eq!((location.polygon, location.hole), (0, None));
+                assert_eq!(location.segment, Some(0));
+                assert_eq!(reason, RingError::DanglingSharedEdge(3));
+            }
+            err => panic!("unexpected error: {err}"),
+        }
+
+        let err = Finder::try_from_compressed_topo(one_edge_topo(Content::EdgeForward(-1)))
+            .err()
+            .unwrap();
+        assert!(
+            matches!(
+                err,
+                Error::MalformedRing {
+                    reason: RingError::DanglingSharedEdge(-1),
+                    ..
+                }
+            ),
+            "{err}"
+        );
+
+        let finder =
+            Finder::try_from_compressed_topo(one_edge_topo(Content::EdgeForward(0))).unwrap();
+        assert_eq!(finder.get_tz_name(0.5, 0.5), "Etc/GMT");
+    }
+
+    #[test]
+    fn shared_edge_id_out_of_range_is_rejected() {
+        let mut tzs = one_edge_topo(Content::EdgeForward(0));
+        tzs.shared_edges[0].id = 1;
+        let err = Finder::try_from_compressed_topo(tzs).err().unwrap();
+        assert!(
+            matches!(err, Error::SharedEdgeIdOutOfRange { id: 1, len: 1 }),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn grid_index_out_of_range_is_rejected() {
+        let mut tzs = one_edge_topo(Content::EdgeForward(0));
+        tzs.grid_index = Some(GridIndex {
+            cells: vec![GridIndexCell {
+                lng: 7,
+                lat: 54,
+                tz_indices: vec![0, 1],
+            }],
+            version: String::new(),
+        });
+        let err = Finder::try_from_compressed_topo(tzs).err().unwrap();
+        assert!(
+            matches!(
+                err,
+                Error::GridIndexOutOfRange {
+                    lng: 7,
+                    lat: 54,
+                    tz_index: 1,
+                    len: 1
+                }
+            ),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn grid_cell_off_the_globe_is_rejected() {
+        let mut tzs = one_edge_topo(Content::EdgeForward(0));
+        tzs.grid_index = Some(GridIndex {
+            cells: vec![
+                GridIndexCell {
+                    lng: 0,
+                    lat: 0,
+                    tz_indices: vec![0],
+                },
+                GridIndexCell {
+                    lng: 65_536,
+                    lat: 0,
+                    tz_indices: Vec::new(),
+                },
+            ],
+            version: String::new(),
+        });
+        let err = Finder::try_from_compressed_topo(tzs.clone()).err().unwrap();
+        assert!(
+            matches!(
+                err,
+                Error::GridCellOutOfRange {
+                    lng: 65_536,
+                    lat: 0
+                }
+            ),
+            "{err}"
+        );
+
+        // Lenient loading drops the cell instead of folding it onto (0, 0).
+        let finder = Finder::from_compressed_topo(tzs);
+        assert_eq!(finder.get_tz_name(0.5, 0.5), "Etc/GMT");
+    }
+
+    #[test]
+    fn truncated_payload_fails_to_decode() {
+        use prost::Message;
+
+        let mut bytes = PreindexTimezones {
+            version: "truncated".to_string(),
+            ..Default::default()
+        }
+        .encode_to_vec();
+        bytes.truncate(bytes.len() - 1);
+        let err = Error::from(PreindexTimezones::decode(&bytes[..]).err().unwrap());
+        assert!(matches!(err, Error::Decode(_)), "{err}");
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(test)]
+mod tests {
+    use tzf_rs::DefaultFinder;
+
+    #[test]
+    #[cfg(feature = "chrono-tz")]
+    fn every_name_resolves_in_chrono_tz() {
+        let finder = DefaultFinder::new();
+        let unknown: Vec<&str> = finder
+            .timezonenames()
+            .into_iter()
+            .filter(|name| name.parse::<chrono_tz::Tz>().is_err())
+            .collect();
+        assert!(unknown.is_empty(), "unknown to chrono-tz: {unknown:?}");
+
+        assert_eq!(
+            finder.get_tz(-97.8674, 34.42),
+            Some(chrono_tz::America::Chicago)
+        );
+        assert_eq!(finder.get_tz(-30.5, 0.5), Some(chrono_tz::Etc::GMTPlus2));
+    }
+
+    #[test]
+    #[cfg(feature = "jiff")]
+    fn every_name_resolves_in_jiff() {
+        let finder = DefaultFinder::new();
+        let unknown: Vec<&str> = finder
+            .timezonenames()
+            .into_iter()
+            .filter(|name| jiff::tz::TimeZone::get(name).is_err())
+            .collect();
+        assert!(unknown.is_empty(), "unknown to jiff: {unknown:?}");
+
+        let tz = finder.get_time_zone(116.3883, 39.9289).unwrap();
+        assert_eq!(tz.iana_name(), Some("Asia/Shanghai"));
+    }
+
+    #[test]
+    #[cfg(feature = "jiff")]
+    fn offsets_follow_dst_and_nautical_signs() {
+        // 2024-01-15T00:00:00Z and 2024-07-01T00:00:00Z.
+        const WINTER: i64 = 1_705_276_800;
+        const SUMMER: i64 = 1_719_792_000;
+        let finder = DefaultFinder::new();
+
+        let winter = finder.offset_at(-97.8674, 34.42, WINTER).unwrap();
+        assert_eq!(winter.offset_seconds, -6 * 3600);
+        assert!(!winter.is_dst);
+        assert_eq!(winter.abbreviation, "CST");
+        let summer = finder.offset_at(-97.8674, 34.42, SUMMER).unwrap();
+        assert_eq!(summer.offset_seconds, -5 * 3600);
+        assert!(summer.is_dst);
+
+        // Etc/GMT+N is N hours behind UTC, Etc/GMT-N is N hours ahead.
+        for (lng, lat) in [(-30.5, 0.5), (-150.0, -40.0), (90.0, -50.0), (7.4999, 54.5)] {
+            let offset = finder.offset_at(lng, lat, SUMMER).unwrap();
+            let Some(rest) = offset.name.strip_prefix("Etc/GMT") else {
+                panic!(
+                    "{lng}, {lat}: expected a nautical zone, got {}",
+                    offset.name
+                );
+            };
+            let hours: i32 = if rest.is_empty() {
+                0
+            } else {
+                rest.parse().unwrap()
+            };
+            assert_eq!(offset.offset_seconds, -hours * 3600, "{}", offset.name);
+            assert!(!offset.is_dst);
+        }
+
+        let local = finder.local_time_at(-30.5, 0.5, SUMMER).unwrap();
+        assert_eq!(local.datetime().to_string(), "2024-06-30T22:00:00");
+        assert!(finder.offset_at(116.3883, 39.9289, i64::MAX).is_none());
+    }
+}
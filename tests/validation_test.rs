@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use tzf_rs::{DefaultFinder, Error, Finder, FuzzyFinder};
+
+    const BAD_LATITUDES: [f64; 5] = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 90.5, -91.0];
+    const BAD_LONGITUDES: [f64; 5] = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, 180.5, -1e9];
+
+    #[test]
+    fn rejects_invalid_coordinates_on_every_finder() {
+        let default_finder = DefaultFinder::new();
+        let finder = Finder::new();
+        let fuzzy_finder = FuzzyFinder::new();
+
+        for lat in BAD_LATITUDES {
+            for result in [
+                default_finder.try_get_tz_name(116.3883, lat),
+                finder.try_get_tz_name(116.3883, lat),
+                fuzzy_finder.try_get_tz_name(116.3883, lat),
+                default_finder.try_get_tz_name_wrapped(116.3883, lat),
+            ] {
+                assert!(
+                    matches!(result, Err(Error::InvalidLatitude(_))),
+                    "lat {lat}: {result:?}"
+                );
+            }
+        }
+
+        for lng in BAD_LONGITUDES {
+            for result in [
+                default_finder.try_get_tz_name(lng, 39.9289),
+                finder.try_get_tz_name(lng, 39.9289),
+                fuzzy_finder.try_get_tz_name(lng, 39.9289),
+            ] {
+                assert!(
+                    matches!(result, Err(Error::InvalidLongitude(_))),
+                    "lng {lng}: {result:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn accepts_range_bounds() {
+        let finder = DefaultFinder::new();
+        for (lng, lat) in [(-180.0, 0.0), (180.0, 0.0), (0.0, 90.0), (0.0, -90.0)] {
+            assert!(finder.try_get_tz_name(lng, lat).is_ok(), "{lng}, {lat}");
+        }
+    }
+
+    #[test]
+    fn wraps_longitudes() {
+        let finder = DefaultFinder::new();
+        for lng in [116.3883 + 360.0, 116.3883 - 720.0] {
+            assert_eq!(
+                finder.try_get_tz_name_wrapped(lng, 39.9289).unwrap(),
+                "Asia/Shanghai"
+            );
+        }
+        assert_eq!(
+            Finder::new()
+                .try_get_tz_name_wrapped(-97.8674 - 360.0, 34.42)
+                .unwrap(),
+            "America/Chicago"
+        );
+        assert_eq!(
+            FuzzyFinder::new()
+                .try_get_tz_name_wrapped(139.4382 + 360.0, 36.4432)
+                .unwrap(),
+            "Asia/Tokyo"
+        );
+
+        // Non-finite longitudes cannot be wrapped.
+        assert!(matches!(
+            finder.try_get_tz_name_wrapped(f64::NAN, 0.0),
+            Err(Error::InvalidLongitude(_))
+        ));
+    }
+}